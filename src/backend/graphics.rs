@@ -11,10 +11,11 @@
 
 
 use {Backend, Color, Point, Rect, Scalar};
-use graph::{self, Container, Graph, NodeIndex};
+use graph::{Container, Graph, NodeIndex};
 use piston_graphics;
+use render::{self, Primitive};
 use std::any::Any;
-use std::iter::once;
+use std::collections::VecDeque;
 use theme::Theme;
 use widget::primitive;
 
@@ -25,6 +26,9 @@ pub use piston_graphics::character::{Character, CharacterCache};
 
 
 /// Draw the given **Graph** using the given **CharacterCache** and **Graphics** backends.
+///
+/// This is a consumer of the backend-agnostic
+/// [**render::Primitives**](../../render/struct.Primitives.html) stream.
 pub fn draw_from_graph<B, G>(context: Context,
                              graphics: &mut G,
                              character_cache: &mut B::CharacterCache,
@@ -34,63 +38,42 @@ pub fn draw_from_graph<B, G>(context: Context,
     where B: Backend,
           G: Graphics<Texture=B::Texture>,
 {
+    draw_from_graph_with_focus::<B, G>(context, graphics, character_cache, graph, depth_order,
+                                       theme, None)
+}
 
-    // A stack of contexts, one for each scroll group.
-    //
-    // FIXME: This allocation every time draw is called is unnecessary. We should re-use a buffer
-    // (perhaps owned by the Ui) for this.
-    let mut crop_stack: Vec<(NodeIndex, Context)> = Vec::new();
-
-    // Retrieve the core window widget so that we can use it to filter visible widgets.
-    let window_idx = NodeIndex::new(0);
-    let window = match graph.widget(window_idx){
-        Some(window) => window,
-        // If we don't yet have the window widget, we won't have *any* widgets, so bail out.
-        None => return,
-    };
-
-    // A function for checking whether or not a widget would be visible.
-    //
-    // TODO: Refactor this into a `visible_area_of_widget` graph algo. Also, consider calculating
-    // the visible area during the `set_widgets` stage, as it might be more optimal than doing so
-    // here.
-    let is_visible = |idx: NodeIndex, container: &Container| -> bool {
-        container.rect.overlap(window.rect).is_some()
-        && graph::algo::cropped_area_of_widget(graph, idx).is_some()
-    };
-
-    // The depth order describes the order in which widgets should be drawn.
-    for &idx in depth_order {
-        if let Some(ref container) = graph.widget(idx) {
-
-            // If we're currently using a cropped context and the current `crop_parent_idx` is
-            // *not* a depth-wise parent of the widget at the current `idx`, we should pop that
-            // cropped context from the stack as we are done with it.
-            while let Some(&(crop_parent_idx, _)) = crop_stack.last() {
-                if graph.does_recursive_depth_edge_exist(crop_parent_idx, idx) {
-                    break;
-                } else {
-                    crop_stack.pop();
-                }
-            }
-
-            // Check the stack for the current Context.
-            let context = crop_stack.last().map(|&(_, ctxt)| ctxt).unwrap_or(context);
-
-            // Draw the widget, but only if it would actually be visible on the window.
-            if is_visible(idx, container) {
-                draw_from_container::<B, G>(&context, graphics, character_cache, container, theme);
-            }
 
-            // If the current widget should crop its children, we need to add a context for it to
-            // the top of the stack.
-            if container.crop_kids {
-                let context = crop_context(context, container.kid_area.rect);
-                crop_stack.push((idx, context));
-            }
+/// Draw the given **Graph** in the same manner as `draw_from_graph`, drawing the `Theme`'s focus
+/// ring around the `maybe_focused` widget if one is given.
+pub fn draw_from_graph_with_focus<B, G>(context: Context,
+                                        graphics: &mut G,
+                                        character_cache: &mut B::CharacterCache,
+                                        graph: &Graph,
+                                        depth_order: &[NodeIndex],
+                                        theme: &Theme,
+                                        maybe_focused: Option<NodeIndex>)
+    where B: Backend,
+          G: Graphics<Texture=B::Texture>,
+{
+    // The context cropped to the most recently yielded scissor rect.
+    let mut cropped_context = context;
+
+    // The most recently yielded transform.
+    let mut matrix = ::position::transform::identity();
+
+    // The primitives are yielded in the order in which they should be drawn.
+    let primitives =
+        render::primitives_with_focus::<B::Texture>(graph, depth_order, theme, maybe_focused);
+    for primitive in primitives {
+        match primitive {
+            Primitive::Scissor(rect) => cropped_context = crop_context(context, rect),
+            Primitive::Transform(m) => matrix = m,
+            primitive => {
+                let context = cropped_context.append_transform(matrix);
+                draw_primitive::<B, G>(&context, graphics, character_cache, primitive);
+            },
         }
     }
-        
 }
 
 
@@ -183,160 +166,149 @@ pub fn draw_from_container<B, G>(context: &Context,
           B::Texture: Any,
           G: Graphics<Texture=B::Texture>,
 {
-    use widget::primitive::shape::Style as ShapeStyle;
-
-    match container.kind {
-
-        primitive::shape::rectangle::KIND => {
-            if let Some(rectangle) = container.unique_widget_state::<::Rectangle>() {
-                match rectangle.style {
-                    ShapeStyle::Fill(_) => {
-                        let color = rectangle.style.get_color(theme);
-                        draw_rectangle(context, graphics, container.rect, color);
-                    },
-                    ShapeStyle::Outline(line_style) => {
-                        let (l, r, b, t) = container.rect.l_r_b_t();
-                        let points = [[l, b], [l, t], [r, t], [r, b], [l, b]];
-                        let points = points.iter().cloned();
-                        draw_lines(context, graphics, theme, points, line_style);
-                    },
-                }
-            }
-        },
+    let mut primitives = VecDeque::new();
+    render::push_container_primitives::<B::Texture>(container, theme, &mut primitives);
+    for primitive in primitives {
+        draw_primitive::<B, G>(context, graphics, character_cache, primitive);
+    }
+}
 
-        primitive::shape::framed_rectangle::KIND => {
-            if let Some(framed_rectangle) = container.unique_widget_state::<::FramedRectangle>() {
-                let frame = framed_rectangle.style.frame(theme);
-                if frame > 0.0 {
-                    let frame_color = framed_rectangle.style.frame_color(theme);
-                    let frame_rect = container.rect;
-                    draw_rectangle(context, graphics, frame_rect, frame_color);
-                }
-                let color = framed_rectangle.style.color(theme);
-                let rect = container.rect.pad(frame);
-                draw_rectangle(context, graphics, rect, color);
-            }
+
+/// Use the given **CharacterCache** and **Graphics** backends to draw the given **Primitive**.
+///
+/// **Primitive::Scissor**s and **Primitive::Transform**s are ignored, as cropping and transforming
+/// the **Context** is the responsibility of the caller.
+pub fn draw_primitive<B, G>(context: &Context,
+                            graphics: &mut G,
+                            character_cache: &mut B::CharacterCache,
+                            primitive: Primitive<B::Texture>)
+    where B: Backend,
+          G: Graphics<Texture=B::Texture>,
+{
+    match primitive {
+
+        Primitive::Rectangle { color, rect } => {
+            draw_rectangle(context, graphics, rect, color);
         },
 
-        primitive::shape::oval::KIND => {
-            if let Some(oval) = container.unique_widget_state::<::Oval>() {
-                use std::f64::consts::PI;
-                const CIRCLE_RESOLUTION: usize = 50;
-                const NUM_POINTS: usize = CIRCLE_RESOLUTION + 1;
-                let (x, y, w, h) = container.rect.x_y_w_h();
-                let t = 2.0 * PI / CIRCLE_RESOLUTION as Scalar;
-                let hw = w / 2.0;
-                let hh = h / 2.0;
-                let f = |i: Scalar| [x + hw * (t*i).cos(), y + hh * (t*i).sin()];
-                let mut points = [[0.0, 0.0]; NUM_POINTS];
-                for i in 0..NUM_POINTS {
-                    points[i] = f(i as f64);
-                }
-
-                match oval.style {
-                    ShapeStyle::Fill(_) => {
-                        let color = oval.style.get_color(theme).to_fsa();
-                        let polygon = piston_graphics::Polygon::new(color);
-                        polygon.draw(&points, &context.draw_state, context.transform, graphics);
-                    },
-                    ShapeStyle::Outline(line_style) => {
-                        let points = points.iter().cloned();
-                        draw_lines(context, graphics, theme, points, line_style)
-                    },
-                }
-            }
+        Primitive::Polygon { color, points } => {
+            let polygon = piston_graphics::Polygon::new(color.to_fsa());
+            polygon.draw(&points, &context.draw_state, context.transform, graphics);
         },
 
-        primitive::shape::polygon::KIND => {
-            use widget::primitive::shape::Style;
-            use widget::primitive::shape::polygon::State;
-
-            if let Some(polygon) = container.state_and_style::<State, Style>() {
-                match polygon.style {
-                    ShapeStyle::Fill(_) => {
-                        let color = polygon.style.get_color(theme).to_fsa();
-                        let points = &polygon.state.points[..];
-                        let polygon = piston_graphics::Polygon::new(color);
-                        polygon.draw(points, &context.draw_state, context.transform, graphics);
-                    },
-                    ShapeStyle::Outline(line_style) => {
-                        let mut points = polygon.state.points.iter().cloned();
-                        let first = points.next();
-                        let points = first.into_iter().chain(points).chain(first);
-                        draw_lines(context, graphics, theme, points, line_style);
-                    },
-                }
-            }
+        Primitive::Lines { color, pattern, dash, thickness, cap, join, points } => {
+            draw_pattern_lines(context, graphics, &points, color, pattern, dash, thickness, cap,
+                               join);
         },
 
-        primitive::line::KIND => {
-            if let Some(line) = container.unique_widget_state::<::Line>() {
-                let points = once(line.state.start).chain(once(line.state.end));
-                draw_lines(context, graphics, theme, points, line.style);
+        Primitive::Triangles { triangles } => {
+            use piston_graphics::triangulation::{tx, ty};
+
+            // The number of vertex components that we submit to the backend at once.
+            const BUFFER_SIZE: usize = 720;
+
+            // Piston has no notion of vertex colors, so each triangle is subdivided until filling
+            // each piece with a single color is indistinguishable from interpolating the vertex
+            // colors. Consecutive pieces of the same color are submitted together.
+            let m = context.transform;
+            let mut vertices = Vec::new();
+            let mut current_color = None;
+            for triangle in &triangles {
+                shade_triangle(triangle, |points, color| {
+                    if current_color != Some(color) {
+                        if let Some(prev_color) = current_color {
+                            graphics.tri_list(&context.draw_state, &prev_color, |f| {
+                                for chunk in vertices.chunks(BUFFER_SIZE) {
+                                    f(chunk);
+                                }
+                            });
+                        }
+                        vertices.clear();
+                        current_color = Some(color);
+                    }
+                    for p in &points {
+                        vertices.push(tx(m, p[0], p[1]));
+                        vertices.push(ty(m, p[0], p[1]));
+                    }
+                });
+            }
+            if let Some(color) = current_color {
+                graphics.tri_list(&context.draw_state, &color, |f| {
+                    for chunk in vertices.chunks(BUFFER_SIZE) {
+                        f(chunk);
+                    }
+                });
             }
         },
 
-        primitive::point_path::KIND => {
-            use widget::primitive::point_path::{State, Style};
-            if let Some(point_path) = container.state_and_style::<State, Style>() {
-                let points = point_path.state.points.iter().cloned();
-                draw_lines(context, graphics, theme, points, point_path.style);
-            }
+        Primitive::Text { color, font_size, text, rect } => {
+            let offset = [rect.left().round(), rect.bottom().round()];
+            let context = context.trans(offset[0], offset[1]).scale(1.0, -1.0);
+            let transform = context.transform;
+            let draw_state = &context.draw_state;
+            piston_graphics::text::Text::new_color(color.to_fsa(), font_size)
+                .round()
+                .draw(text, character_cache, draw_state, transform, graphics);
         },
 
-        primitive::text::KIND => {
-            if let Some(text) = container.unique_widget_state::<::Text>() {
-                use {Align, graph, text};
-
-                let graph::UniqueWidgetState { ref state, ref style } = *text;
-
-                let font_size = style.font_size(theme);
-                let line_spacing = style.line_spacing(theme);
-                let color = style.color(theme).to_fsa();
-                let x_align = style.text_align(theme);
-                let y_align = Align::End; // Always align text to top of Text's Rect.
-                let rect = container.rect;
-                let line_infos = state.line_infos.iter().cloned();
-                let string = &state.string;
-
-                let lines = line_infos.clone().map(|info| &string[info.byte_range()]);
-                let line_rects =
-                    text::line::rects(line_infos, font_size, rect, x_align, y_align, line_spacing);
-
-                for (line, line_rect) in lines.zip(line_rects) {
-                    let offset = [line_rect.left().round(), line_rect.bottom().round()];
-                    let context = context.trans(offset[0], offset[1]).scale(1.0, -1.0);
-                    let transform = context.transform;
-                    let draw_state = &context.draw_state;
-                    piston_graphics::text::Text::new_color(color, font_size)
-                        .round()
-                        .draw(line, character_cache, draw_state, transform, graphics);
-                }
-            }
+        Primitive::Image { texture, color, src_rect, rect } => {
+            let mut image = piston_graphics::image::Image::new();
+            image.color = color.map(|c| c.to_fsa());
+            image.source_rectangle = Some({
+                let (x, y, w, h) = src_rect.x_y_w_h();
+                [x as i32, y as i32, w as i32, h as i32]
+            });
+            let (left, top, w, h) = rect.l_t_w_h();
+            image.rectangle = Some([0.0, 0.0, w, h]);
+            let context = context.trans(left, top).scale(1.0, -1.0);
+            let transform = context.transform;
+            let draw_state = &context.draw_state;
+            image.draw(texture, draw_state, transform, graphics);
         },
 
-        primitive::image::KIND => {
-            use widget::primitive::image::{State, Style};
-            if let Some(image) = container.state_and_style::<State<B::Texture>, Style>() {
-                let ::graph::UniqueWidgetState { ref state, ref style } = *image;
-                if let Some(texture) = state.texture.as_ref() {
-                    let mut image = piston_graphics::image::Image::new();
-                    image.color = style.maybe_color.and_then(|c| c.map(|c| c.to_fsa()));
-                    image.source_rectangle = Some({
-                        let (x, y, w, h) = texture.src_rect.x_y_w_h();
-                        [x as i32, y as i32, w as i32, h as i32]
-                    });
-                    let (left, top, w, h) = container.rect.l_t_w_h();
-                    image.rectangle = Some([0.0, 0.0, w, h]);
-                    let context = context.trans(left, top).scale(1.0, -1.0);
-                    let transform = context.transform;
-                    let draw_state = &context.draw_state;
-                    image.draw(texture.arc.as_ref(), draw_state, transform, graphics);
-                }
+        Primitive::Scissor(_) | Primitive::Transform(_) => (),
+    }
+}
+
+
+/// The greatest difference between any color channel of a triangle's vertices for which
+/// **shade_triangle** fills the triangle with a single color.
+const MAX_VERTEX_COLOR_DIFFERENCE: f32 = 1.0 / 128.0;
+
+/// Limits the number of times that **shade_triangle** may bisect a single triangle.
+const MAX_SHADING_DEPTH: u32 = 16;
+
+/// Approximate interpolating the vertex colors of the given triangle by subdividing it into pieces
+/// whose vertex colors are all within `MAX_VERTEX_COLOR_DIFFERENCE` of one another.
+///
+/// `f` is called with the points and average color of each piece.
+fn shade_triangle<F>(triangle: &[(Point, Color); 3], mut f: F)
+    where F: FnMut([Point; 3], [f32; 4]),
+{
+    let vertex = |i: usize| (triangle[i].0, triangle[i].1.to_fsa());
+    let mut stack = vec![([vertex(0), vertex(1), vertex(2)], 0)];
+    while let Some((tri, depth)) = stack.pop() {
+        let difference = |i: usize| {
+            let (a, b) = (tri[i].1, tri[(i + 1) % 3].1);
+            (0..4).fold(0.0, |max: f32, c| max.max((a[c] - b[c]).abs()))
+        };
+        let edge = (0..3).fold(0, |j, i| if difference(i) > difference(j) { i } else { j });
+        if difference(edge) > MAX_VERTEX_COLOR_DIFFERENCE && depth < MAX_SHADING_DEPTH {
+            // Bisect the edge along which the color changes the most.
+            let (a, b, c) = (tri[edge], tri[(edge + 1) % 3], tri[(edge + 2) % 3]);
+            let mut mid = ([(a.0[0] + b.0[0]) / 2.0, (a.0[1] + b.0[1]) / 2.0], [0.0; 4]);
+            for i in 0..4 {
+                mid.1[i] = (a.1[i] + b.1[i]) / 2.0;
             }
+            stack.push(([a, mid, c], depth + 1));
+            stack.push(([mid, b, c], depth + 1));
+        } else {
+            let mut color = [0.0; 4];
+            for i in 0..4 {
+                color[i] = (tri[0].1[i] + tri[1].1[i] + tri[2].1[i]) / 3.0;
+            }
+            f([tri[0].0, tri[1].0, tri[2].0], color);
         }
-
-        _ => (),
     }
 }
 
@@ -367,33 +339,59 @@ pub fn draw_rectangle<G>(context: &Context,
 pub fn draw_lines<G, I>(context: &Context,
                         graphics: &mut G,
                         theme: &Theme,
-                        mut points: I,
+                        points: I,
                         style: primitive::line::Style)
     where G: Graphics,
           I: Iterator<Item=Point>,
 {
-    use widget::primitive::line::{Cap, Pattern};
-
-    if let Some(first) = points.next() {
-        let pattern = style.get_pattern(theme);
-        let color = style.get_color(theme).to_fsa();
-        let thickness = style.get_thickness(theme);
-        let cap = style.get_cap(theme);
-        match pattern {
-            Pattern::Solid => {
-                let line = match cap {
-                    Cap::Flat => piston_graphics::Line::new(color, thickness / 2.0),
-                    Cap::Round => piston_graphics::Line::new_round(color, thickness / 2.0),
-                };
-                let mut start = first;
-                for end in points {
-                    let coords = [start[0], start[1], end[0], end[1]];
-                    line.draw(coords, &context.draw_state, context.transform, graphics);
-                    start = end;
-                }
-            },
-            Pattern::Dashed => unimplemented!(),
-            Pattern::Dotted => unimplemented!(),
+    let points: Vec<Point> = points.collect();
+    let color = style.get_color(theme);
+    let pattern = style.get_pattern(theme);
+    let dash = style.get_dash(theme);
+    let thickness = style.get_thickness(theme);
+    let cap = style.get_cap(theme);
+    let join = style.get_join(theme);
+    draw_pattern_lines(context, graphics, &points, color, pattern, dash, thickness, cap, join);
+}
+
+
+/// Draw a series of lines between the given **Point**s, broken up by the given **Pattern**.
+///
+/// Each dash (or the whole path if **Solid**) is tessellated as a single stroke so that corners
+/// are joined without gaps or overlaps.
+fn draw_pattern_lines<G>(context: &Context,
+                         graphics: &mut G,
+                         points: &[Point],
+                         color: Color,
+                         pattern: primitive::line::Pattern,
+                         dash: primitive::line::Dash,
+                         thickness: Scalar,
+                         cap: primitive::line::Cap,
+                         join: primitive::line::Join)
+    where G: Graphics,
+{
+    use piston_graphics::triangulation::{tx, ty};
+    use widget::primitive::line::{self, Cap, Pattern};
+
+    // The number of vertex components that we submit to the backend at once.
+    const BUFFER_SIZE: usize = 720;
+
+    // Dots are drawn as zero-length lines with round ends.
+    let cap = if let Pattern::Dotted = pattern { Cap::Round } else { cap };
+    let color = color.to_fsa();
+    let m = context.transform;
+    let mut vertices = Vec::new();
+    for path in line::pattern_paths(points, pattern, dash, thickness) {
+        for triangle in line::stroke_triangles(&path, thickness, cap, join) {
+            for p in &triangle {
+                vertices.push(tx(m, p[0], p[1]));
+                vertices.push(ty(m, p[0], p[1]));
+            }
         }
     }
+    graphics.tri_list(&context.draw_state, &color, |f| {
+        for chunk in vertices.chunks(BUFFER_SIZE) {
+            f(chunk);
+        }
+    });
 }
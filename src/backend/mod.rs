@@ -14,6 +14,9 @@ pub use self::event::{RawEvent, ToRawEvent};
 
 pub mod event;
 pub mod graphics;
+pub mod software;
+pub mod svg;
+pub mod terminal;
 
 /// A trait to be implemented by all backends to conrod.
 ///
@@ -0,0 +1,598 @@
+//! A headless, CPU-only backend that rasterizes a **Ui** into an in-memory RGBA **Image**.
+//!
+//! This is useful for rendering a **Ui** on machines without a GPU, for example when generating
+//! screenshots or writing visual tests.
+//!
+//! The rasterizer is a consumer of the
+//! [**render::Primitives**](../../render/struct.Primitives.html) stream. Each pixel is sampled
+//! once at its centre, so no anti-aliasing is performed. This keeps the output deterministic
+//! across platforms.
+//!
+//! To use the software backend, the **Ui** must be constructed with a **CharacterCache** whose
+//! `Texture` type is the software **Image**, i.e. `Ui<(software::Image, C)>`.
+
+use {CharacterCache, Color, Dimensions, Point, Rect, Scalar, Ui};
+use backend::graphics::ImageSize;
+use position::transform::{self, Matrix2d};
+use render::Primitive;
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::ops::{Deref, DerefMut};
+use std::path::Path;
+use widget::primitive::line::{self, Cap, Pattern};
+
+
+/// An in-memory image storing non-premultiplied RGBA pixels in row-major order, starting at the
+/// top-left pixel.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Image {
+    width: u32,
+    height: u32,
+    pixels: Vec<[u8; 4]>,
+}
+
+/// The area of the target **Image** to which drawing is currently restricted, in pixels.
+///
+/// The bounds are `[left, top, right, bottom]` where `right` and `bottom` are exclusive.
+type PixelBounds = [i64; 4];
+
+/// Describes how to map conrod's coordinate system onto the pixels of the target **Image**.
+struct Target<'a> {
+    image: &'a mut Image,
+    view_dim: Dimensions,
+    scale: [Scalar; 2],
+    scissor: PixelBounds,
+    /// The transform applied to all points before they are mapped onto the image.
+    transform: Matrix2d,
+}
+
+
+impl Image {
+
+    /// Construct a new, fully transparent **Image** with the given dimensions in pixels.
+    pub fn new(width: u32, height: u32) -> Self {
+        Image::from_pixels(width, height, vec![[0; 4]; width as usize * height as usize])
+    }
+
+    /// Construct an **Image** from the given RGBA pixels.
+    ///
+    /// **Panics** if the number of pixels does not match the given dimensions.
+    pub fn from_pixels(width: u32, height: u32, pixels: Vec<[u8; 4]>) -> Self {
+        assert_eq!(pixels.len(), width as usize * height as usize);
+        Image {
+            width: width,
+            height: height,
+            pixels: pixels,
+        }
+    }
+
+    /// The width of the **Image** in pixels.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// The height of the **Image** in pixels.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// All pixels within the **Image** in row-major order, starting at the top-left pixel.
+    pub fn pixels(&self) -> &[[u8; 4]] {
+        &self.pixels
+    }
+
+    /// Consume the **Image** and produce its raw RGBA bytes in row-major order.
+    pub fn into_raw(self) -> Vec<u8> {
+        let mut raw = Vec::with_capacity(self.pixels.len() * 4);
+        for pixel in self.pixels {
+            raw.extend(pixel.iter().cloned());
+        }
+        raw
+    }
+
+    /// The pixel at the given location, where `[0, 0]` is the top-left pixel.
+    ///
+    /// Returns `None` if the location is out of bounds.
+    pub fn pixel(&self, x: u32, y: u32) -> Option<[u8; 4]> {
+        if x < self.width && y < self.height {
+            Some(self.pixels[(y * self.width + x) as usize])
+        } else {
+            None
+        }
+    }
+
+    /// Set the pixel at the given location, where `[0, 0]` is the top-left pixel.
+    ///
+    /// Does nothing if the location is out of bounds.
+    pub fn set_pixel(&mut self, x: u32, y: u32, rgba: [u8; 4]) {
+        if x < self.width && y < self.height {
+            self.pixels[(y * self.width + x) as usize] = rgba;
+        }
+    }
+
+    /// Set every pixel in the **Image** to the given color.
+    pub fn clear(&mut self, color: Color) {
+        let rgba = color.to_byte_fsa();
+        for pixel in self.pixels.iter_mut() {
+            *pixel = rgba;
+        }
+    }
+
+    /// Write the **Image** to the given writer in the uncompressed
+    /// [PAM](http://netpbm.sourceforge.net/doc/pam.html) format using the `RGB_ALPHA` tuple type.
+    ///
+    /// PAM is used as it is a trivial, lossless format that may be viewed or converted by most
+    /// image tools, and requires no extra dependencies.
+    pub fn write_pam<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        write!(writer,
+               "P7\nWIDTH {}\nHEIGHT {}\nDEPTH 4\nMAXVAL 255\nTUPLTYPE RGB_ALPHA\nENDHDR\n",
+               self.width,
+               self.height)?;
+        for pixel in &self.pixels {
+            writer.write_all(pixel)?;
+        }
+        Ok(())
+    }
+
+    /// Read an **Image** from the given reader in the PAM format written by `Image::write_pam`.
+    pub fn read_pam<R: Read>(mut reader: R) -> io::Result<Image> {
+        fn invalid(msg: &str) -> io::Error {
+            io::Error::new(io::ErrorKind::InvalidData, msg)
+        }
+
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+
+        // Find the end of the header.
+        const END_HEADER: &'static [u8] = b"ENDHDR\n";
+        let header_len = match bytes.windows(END_HEADER.len()).position(|w| w == END_HEADER) {
+            Some(pos) => pos + END_HEADER.len(),
+            None => return Err(invalid("missing PAM ENDHDR")),
+        };
+        let header = match ::std::str::from_utf8(&bytes[..header_len]) {
+            Ok(header) => header,
+            Err(_) => return Err(invalid("PAM header is not valid UTF-8")),
+        };
+
+        let mut lines = header.lines();
+        if lines.next() != Some("P7") {
+            return Err(invalid("not a PAM image"));
+        }
+        let (mut width, mut height) = (None, None);
+        for line in lines {
+            let mut words = line.split_whitespace();
+            match (words.next(), words.next()) {
+                (Some("WIDTH"), Some(w)) => width = w.parse().ok(),
+                (Some("HEIGHT"), Some(h)) => height = h.parse().ok(),
+                (Some("DEPTH"), Some(d)) if d != "4" => return Err(invalid("PAM DEPTH must be 4")),
+                (Some("MAXVAL"), Some(m)) if m != "255" =>
+                    return Err(invalid("PAM MAXVAL must be 255")),
+                _ => (),
+            }
+        }
+        let (width, height): (u32, u32) = match (width, height) {
+            (Some(w), Some(h)) => (w, h),
+            _ => return Err(invalid("missing PAM WIDTH or HEIGHT")),
+        };
+
+        let data = &bytes[header_len..];
+        if data.len() != width as usize * height as usize * 4 {
+            return Err(invalid("PAM data does not match its dimensions"));
+        }
+        let pixels = data.chunks(4).map(|c| [c[0], c[1], c[2], c[3]]).collect();
+        Ok(Image::from_pixels(width, height, pixels))
+    }
+
+    /// Save the **Image** to the file at the given path in the PAM format.
+    pub fn save_pam<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let file = File::create(path)?;
+        self.write_pam(io::BufWriter::new(file))
+    }
+
+    /// Load an **Image** from the PAM file at the given path.
+    pub fn open_pam<P: AsRef<Path>>(path: P) -> io::Result<Image> {
+        let file = File::open(path)?;
+        Image::read_pam(io::BufReader::new(file))
+    }
+
+    /// Blend the given non-premultiplied color over the pixel at the given location.
+    fn blend_pixel(&mut self, x: u32, y: u32, rgba: [f32; 4]) {
+        let idx = (y * self.width + x) as usize;
+        let dst = self.pixels[idx];
+        let src_a = rgba[3];
+        let dst_a = dst[3] as f32 / 255.0;
+        let out_a = src_a + dst_a * (1.0 - src_a);
+        if out_a <= 0.0 {
+            self.pixels[idx] = [0; 4];
+            return;
+        }
+        let channel = |i: usize| {
+            let dst_c = dst[i] as f32 / 255.0;
+            let c = (rgba[i] * src_a + dst_c * dst_a * (1.0 - src_a)) / out_a;
+            (c * 255.0).round().max(0.0).min(255.0) as u8
+        };
+        self.pixels[idx] = [channel(0), channel(1), channel(2), (out_a * 255.0).round() as u8];
+    }
+
+}
+
+impl ImageSize for Image {
+    fn get_size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+}
+
+
+impl<'a> Target<'a> {
+
+    /// The full bounds of the target **Image**.
+    fn image_bounds(&self) -> PixelBounds {
+        [0, 0, self.image.width as i64, self.image.height as i64]
+    }
+
+    /// Convert the given **Point** in conrod's coordinate system to a pixel position.
+    fn to_pixel(&self, p: Point) -> [Scalar; 2] {
+        let x = (p[0] + self.view_dim[0] / 2.0) * self.scale[0];
+        let y = (self.view_dim[1] / 2.0 - p[1]) * self.scale[1];
+        [x, y]
+    }
+
+    /// Convert the given pixel position to a **Point** in conrod's coordinate system.
+    fn from_pixel(&self, p: [Scalar; 2]) -> Point {
+        let x = p[0] / self.scale[0] - self.view_dim[0] / 2.0;
+        let y = self.view_dim[1] / 2.0 - p[1] / self.scale[1];
+        [x, y]
+    }
+
+    /// Map the given **Point** by the current transform and convert it to a pixel position.
+    fn transformed_to_pixel(&self, p: Point) -> [Scalar; 2] {
+        self.to_pixel(transform::transform_point(self.transform, p))
+    }
+
+    /// Restrict all following drawing to the given **Rect**.
+    fn set_scissor(&mut self, rect: Rect) {
+        let (l, r, b, t) = rect.l_r_b_t();
+        let tl = self.to_pixel([l, t]);
+        let br = self.to_pixel([r, b]);
+        let bounds = self.image_bounds();
+        self.scissor = [
+            ::std::cmp::max(bounds[0], tl[0].round() as i64),
+            ::std::cmp::max(bounds[1], tl[1].round() as i64),
+            ::std::cmp::min(bounds[2], br[0].round() as i64),
+            ::std::cmp::min(bounds[3], br[1].round() as i64),
+        ];
+    }
+
+    /// The pixel bounds of the given pixel-space bounding box, restricted to the scissor.
+    fn clip(&self, min: [Scalar; 2], max: [Scalar; 2]) -> PixelBounds {
+        let s = self.scissor;
+        [
+            ::std::cmp::max(s[0], (min[0] - 0.5).ceil() as i64),
+            ::std::cmp::max(s[1], (min[1] - 0.5).ceil() as i64),
+            ::std::cmp::min(s[2], (max[0] - 0.5).ceil() as i64),
+            ::std::cmp::min(s[3], (max[1] - 0.5).ceil() as i64),
+        ]
+    }
+
+    /// Fill the polygon described by the given points (in conrod's coordinate system).
+    ///
+    /// Uses the even-odd rule, so both convex and concave polygons are supported.
+    fn fill_polygon(&mut self, points: &[Point], color: [f32; 4]) {
+        if color[3] <= 0.0 {
+            return;
+        }
+        let mut spans = Vec::new();
+        self.polygon_spans(points, |y, start, end| spans.push((y, start, end)));
+        for (y, start, end) in spans {
+            for x in start..end {
+                self.image.blend_pixel(x as u32, y as u32, color);
+            }
+        }
+    }
+
+    /// Produce each horizontal span of pixels (within the scissor) whose centres lie within the
+    /// polygon described by the given points, as `(y, start_x, end_x)` with an exclusive end.
+    fn polygon_spans<F>(&self, points: &[Point], mut f: F)
+        where F: FnMut(i64, i64, i64),
+    {
+        if points.len() < 3 {
+            return;
+        }
+        let points: Vec<[Scalar; 2]> = points.iter()
+            .map(|&p| self.transformed_to_pixel(p))
+            .collect();
+        let (mut min, mut max) = (points[0], points[0]);
+        for p in &points {
+            min = [min[0].min(p[0]), min[1].min(p[1])];
+            max = [max[0].max(p[0]), max[1].max(p[1])];
+        }
+        let bounds = self.clip(min, max);
+        let (l, t, r, b) = (bounds[0], bounds[1], bounds[2], bounds[3]);
+        let mut crossings = Vec::new();
+        for y in t..b {
+            let cy = y as Scalar + 0.5;
+            crossings.clear();
+            for i in 0..points.len() {
+                let p0 = points[i];
+                let p1 = points[(i + 1) % points.len()];
+                if (p0[1] <= cy && p1[1] > cy) || (p1[1] <= cy && p0[1] > cy) {
+                    crossings.push(p0[0] + (cy - p0[1]) / (p1[1] - p0[1]) * (p1[0] - p0[0]));
+                }
+            }
+            crossings.sort_by(|a, b| a.partial_cmp(b).expect("Crossing was NaN!"));
+            for span in crossings.chunks(2) {
+                if span.len() < 2 {
+                    break;
+                }
+                let start = ::std::cmp::max(l, (span[0] - 0.5).ceil() as i64);
+                let end = ::std::cmp::min(r, (span[1] - 0.5).ceil() as i64);
+                if start < end {
+                    f(y, start, end);
+                }
+            }
+        }
+    }
+
+    /// Fill the union of the given triangles, blending each covered pixel exactly once.
+    ///
+    /// This ensures that translucent strokes are not darkened where their triangles overlap.
+    fn fill_triangles(&mut self, triangles: &[[Point; 3]], color: [f32; 4]) {
+        if triangles.is_empty() || color[3] <= 0.0 {
+            return;
+        }
+        let mut spans = Vec::new();
+        for triangle in triangles {
+            self.polygon_spans(triangle, |y, start, end| spans.push((y, start, end)));
+        }
+        if spans.is_empty() {
+            return;
+        }
+
+        // Mark the covered pixels within the bounds of all spans.
+        let (mut l, mut t, mut r, mut b) = (i64::max_value(), i64::max_value(), 0, 0);
+        for &(y, start, end) in &spans {
+            l = ::std::cmp::min(l, start);
+            r = ::std::cmp::max(r, end);
+            t = ::std::cmp::min(t, y);
+            b = ::std::cmp::max(b, y + 1);
+        }
+        let w = (r - l) as usize;
+        let mut covered = vec![false; w * (b - t) as usize];
+        for &(y, start, end) in &spans {
+            let row = (y - t) as usize * w;
+            for x in start..end {
+                covered[row + (x - l) as usize] = true;
+            }
+        }
+
+        for y in t..b {
+            for x in l..r {
+                if covered[(y - t) as usize * w + (x - l) as usize] {
+                    self.image.blend_pixel(x as u32, y as u32, color);
+                }
+            }
+        }
+    }
+
+    /// Fill the given triangle, interpolating the color of each vertex across its area.
+    fn fill_shaded_triangle(&mut self, triangle: &[(Point, Color); 3]) {
+        let points = [triangle[0].0, triangle[1].0, triangle[2].0];
+        let a = self.transformed_to_pixel(points[0]);
+        let b = self.transformed_to_pixel(points[1]);
+        let c = self.transformed_to_pixel(points[2]);
+        let area = (b[0] - a[0]) * (c[1] - a[1]) - (c[0] - a[0]) * (b[1] - a[1]);
+        if area == 0.0 {
+            return;
+        }
+        let colors = [triangle[0].1.to_fsa(), triangle[1].1.to_fsa(), triangle[2].1.to_fsa()];
+        let mut spans = Vec::new();
+        self.polygon_spans(&points, |y, start, end| spans.push((y, start, end)));
+        for (y, start, end) in spans {
+            let py = y as Scalar + 0.5;
+            for x in start..end {
+                let px = x as Scalar + 0.5;
+                // The barycentric weights of the pixel centre.
+                let wa = ((b[0] - px) * (c[1] - py) - (c[0] - px) * (b[1] - py)) / area;
+                let wb = ((c[0] - px) * (a[1] - py) - (a[0] - px) * (c[1] - py)) / area;
+                let wc = 1.0 - wa - wb;
+                let (wa, wb, wc) = (wa as f32, wb as f32, wc as f32);
+                let channel = |i: usize| wa * colors[0][i] + wb * colors[1][i] + wc * colors[2][i];
+                let color = [channel(0), channel(1), channel(2), channel(3)];
+                if color[3] > 0.0 {
+                    self.image.blend_pixel(x as u32, y as u32, color);
+                }
+            }
+        }
+    }
+
+    /// Fill the given **Rect**.
+    fn fill_rect(&mut self, rect: Rect, color: [f32; 4]) {
+        let (l, r, b, t) = rect.l_r_b_t();
+        self.fill_polygon(&[[l, b], [l, t], [r, t], [r, b]], color);
+    }
+
+    /// Draw the `src_rect` area of the given texture into the `dst` area, where `dst` is the
+    /// top-left position and dimensions in conrod's coordinate system.
+    ///
+    /// Each sampled texel is multiplied by the given `tint`. Textures are sampled using the
+    /// nearest texel.
+    fn draw_image(&mut self,
+                  texture: &Image,
+                  src_rect: [Scalar; 4],
+                  dst_top_left: Point,
+                  dst_dim: Dimensions,
+                  tint: [f32; 4])
+    {
+        let m = self.transform;
+        let bottom_right = [dst_top_left[0] + dst_dim[0], dst_top_left[1] - dst_dim[1]];
+
+        // Rotated, sheared or flipped images are sampled by mapping each pixel centre back onto
+        // the un-transformed destination area.
+        if !transform::is_axis_aligned(m) || m[0][0] <= 0.0 || m[1][1] <= 0.0 {
+            let inverse = match transform::invert(m) {
+                Some(inverse) => inverse,
+                None => return,
+            };
+            if dst_dim[0] <= 0.0 || dst_dim[1] <= 0.0 {
+                return;
+            }
+            let (l, t) = (dst_top_left[0], dst_top_left[1]);
+            let (r, b) = (bottom_right[0], bottom_right[1]);
+            let mut spans = Vec::new();
+            self.polygon_spans(&[[l, b], [l, t], [r, t], [r, b]],
+                               |y, start, end| spans.push((y, start, end)));
+            for (y, start, end) in spans {
+                for x in start..end {
+                    let centre = [x as Scalar + 0.5, y as Scalar + 0.5];
+                    let p = transform::transform_point(inverse, self.from_pixel(centre));
+                    let u = ((p[0] - l) / dst_dim[0]).max(0.0).min(1.0);
+                    let v = ((t - p[1]) / dst_dim[1]).max(0.0).min(1.0);
+                    self.blend_texel(texture, src_rect, u, v, tint, x, y);
+                }
+            }
+            return;
+        }
+
+        let min = self.transformed_to_pixel(dst_top_left);
+        let max = self.transformed_to_pixel(bottom_right);
+        let (w, h) = (max[0] - min[0], max[1] - min[1]);
+        if w <= 0.0 || h <= 0.0 {
+            return;
+        }
+        let bounds = self.clip(min, max);
+        let (l, t, r, b) = (bounds[0], bounds[1], bounds[2], bounds[3]);
+        for y in t..b {
+            let v = (y as Scalar + 0.5 - min[1]) / h;
+            for x in l..r {
+                let u = (x as Scalar + 0.5 - min[0]) / w;
+                self.blend_texel(texture, src_rect, u, v, tint, x, y);
+            }
+        }
+    }
+
+    /// Blend the texel found at the given `u`, `v` position within the `src_rect` area of the
+    /// given texture over the pixel at `x`, `y`.
+    fn blend_texel(&mut self,
+                   texture: &Image,
+                   src_rect: [Scalar; 4],
+                   u: Scalar,
+                   v: Scalar,
+                   tint: [f32; 4],
+                   x: i64,
+                   y: i64)
+    {
+        let tx = (src_rect[0] + u * src_rect[2]).floor() as i64;
+        let ty = (src_rect[1] + v * src_rect[3]).floor() as i64;
+        if tx < 0 || ty < 0 {
+            return;
+        }
+        if let Some(texel) = texture.pixel(tx as u32, ty as u32) {
+            let color = [
+                texel[0] as f32 / 255.0 * tint[0],
+                texel[1] as f32 / 255.0 * tint[1],
+                texel[2] as f32 / 255.0 * tint[2],
+                texel[3] as f32 / 255.0 * tint[3],
+            ];
+            if color[3] > 0.0 {
+                self.image.blend_pixel(x as u32, y as u32, color);
+            }
+        }
+    }
+
+}
+
+
+/// Rasterize the given **Ui** into the given **Image**.
+///
+/// The **Ui**'s window is scaled to fill the entire **Image**.
+///
+/// Note that unlike `Ui::draw`, this does not affect the **Ui**'s `redraw_count`.
+pub fn draw_ui<C>(ui: &Ui<(Image, C)>, image: &mut Image)
+    where C: CharacterCache<Texture=Image>,
+{
+    let view_dim = [ui.win_w, ui.win_h];
+    let mut ref_mut_character_cache = ui.glyph_cache.deref().borrow_mut();
+    let character_cache = ref_mut_character_cache.deref_mut();
+    draw_primitives(ui.render_primitives(), view_dim, character_cache, image);
+}
+
+
+/// Rasterize the given **Primitive**s into the given **Image**.
+///
+/// `view_dim` describes the dimensions of the window in conrod's coordinate system. The view is
+/// scaled to fill the entire **Image**.
+pub fn draw_primitives<'a, I, C>(primitives: I,
+                                 view_dim: Dimensions,
+                                 character_cache: &mut C,
+                                 image: &mut Image)
+    where I: IntoIterator<Item=Primitive<'a, Image>>,
+          C: CharacterCache<Texture=Image>,
+{
+    let scale = [image.width as Scalar / view_dim[0], image.height as Scalar / view_dim[1]];
+    let scissor = [0, 0, image.width as i64, image.height as i64];
+    let mut target = Target {
+        image: image,
+        view_dim: view_dim,
+        scale: scale,
+        scissor: scissor,
+        transform: transform::identity(),
+    };
+
+    for primitive in primitives {
+        match primitive {
+
+            Primitive::Rectangle { color, rect } => {
+                target.fill_rect(rect, color.to_fsa());
+            },
+
+            Primitive::Polygon { color, points } => {
+                target.fill_polygon(&points, color.to_fsa());
+            },
+
+            Primitive::Lines { color, pattern, dash, thickness, cap, join, points } => {
+                // Dots are drawn as zero-length lines with round ends.
+                let cap = if let Pattern::Dotted = pattern { Cap::Round } else { cap };
+                let mut triangles = Vec::new();
+                for path in line::pattern_paths(&points, pattern, dash, thickness) {
+                    triangles.extend(line::stroke_triangles(&path, thickness, cap, join));
+                }
+                target.fill_triangles(&triangles, color.to_fsa());
+            },
+
+            Primitive::Triangles { triangles } => {
+                for triangle in &triangles {
+                    target.fill_shaded_triangle(triangle);
+                }
+            },
+
+            Primitive::Text { color, font_size, text, rect } => {
+                let color = color.to_fsa();
+                let origin = [rect.left().round(), rect.bottom().round()];
+                let mut x = 0.0;
+                for ch in text.chars() {
+                    let character = character_cache.character(font_size, ch);
+                    let (w, h) = character.texture.get_size();
+                    let src_rect = [0.0, 0.0, w as Scalar, h as Scalar];
+                    let top_left = [
+                        (origin[0] + x + character.left()).round(),
+                        (origin[1] + character.top()).round(),
+                    ];
+                    let dim = [w as Scalar, h as Scalar];
+                    target.draw_image(character.texture, src_rect, top_left, dim, color);
+                    x += character.width();
+                }
+            },
+
+            Primitive::Image { texture, color, src_rect, rect } => {
+                let tint = color.map(|c| c.to_fsa()).unwrap_or([1.0; 4]);
+                let (x, y, w, h) = src_rect.x_y_w_h();
+                let (left, top, dst_w, dst_h) = rect.l_t_w_h();
+                target.draw_image(texture, [x, y, w, h], [left, top], [dst_w, dst_h], tint);
+            },
+
+            Primitive::Scissor(rect) => target.set_scissor(rect),
+
+            Primitive::Transform(matrix) => target.transform = matrix,
+        }
+    }
+}
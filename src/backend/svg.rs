@@ -0,0 +1,354 @@
+//! Export a frame of a **Ui** as an [SVG](https://www.w3.org/TR/SVG11/) document.
+//!
+//! The exporter is a consumer of the [**render::Primitives**](../../render/struct.Primitives.html)
+//! stream, so elements are written in depth order, crop areas are described using `clipPath`s and
+//! transformed widgets are wrapped in groups with a `transform` attribute.
+//!
+//! The output is deterministic, making it suitable as a diffable text artifact for tests as well
+//! as for producing vector mockups and printable exports.
+
+use {Backend, Color, Dimensions, Point, Rect, Scalar, Ui};
+use backend::graphics::ImageSize;
+use position::transform::{self, Matrix2d};
+use render::{self, Primitive};
+use std::fmt::Write;
+use widget::primitive::line::{Cap, Join, Pattern};
+
+
+/// Produce an SVG document describing the current frame of the given **Ui**.
+///
+/// As conrod knows nothing about where the **Ui**'s textures come from, `image_href` is used to
+/// produce the `href` (e.g. a file path or data URI) for each texture. **Image**s for which
+/// `image_href` returns `None` are omitted.
+pub fn from_ui<B, F>(ui: &Ui<B>, image_href: F) -> String
+    where B: Backend,
+          F: FnMut(&B::Texture) -> Option<String>,
+{
+    from_primitives(ui.render_primitives(), [ui.win_w, ui.win_h], image_href)
+}
+
+
+/// Produce an SVG document from the given **Primitive**s.
+///
+/// `view_dim` describes the dimensions of the window in conrod's coordinate system.
+///
+/// See [**from_ui**](./fn.from_ui.html) for details on `image_href`.
+pub fn from_primitives<'a, T, I, F>(primitives: I,
+                                    view_dim: Dimensions,
+                                    mut image_href: F) -> String
+    where T: ImageSize + 'a,
+          I: IntoIterator<Item=Primitive<'a, T>>,
+          F: FnMut(&T) -> Option<String>,
+{
+    // Convert a **Point** in conrod's coordinate system to SVG's coordinate system.
+    let to_svg = |p: Point| [p[0] + view_dim[0] / 2.0, view_dim[1] / 2.0 - p[1]];
+    // The top-left position and dimensions of the **Rect** in SVG's coordinate system.
+    let svg_rect = |rect: Rect| {
+        let (l, t, w, h) = rect.l_t_w_h();
+        let tl = to_svg([l, t]);
+        [tl[0], tl[1], w, h]
+    };
+
+    let mut svg = String::new();
+    let (w, h) = (view_dim[0], view_dim[1]);
+    writeln!(svg, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>").unwrap();
+    writeln!(svg,
+             "<svg xmlns=\"http://www.w3.org/2000/svg\" \
+              xmlns:xlink=\"http://www.w3.org/1999/xlink\" \
+              width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">",
+             w, h, w, h).unwrap();
+
+    // The number of `clipPath`s written so far, used to produce unique ids.
+    let mut num_clips = 0;
+    // The number of `linearGradient`s written so far, used to produce unique ids.
+    let mut num_gradients = 0;
+    // The number of tinting `filter`s written so far, used to produce unique ids.
+    let mut num_tints = 0;
+    // Whether or not we are currently within a clipped group.
+    let mut is_clipped = false;
+    // The most recently yielded transform, converted to SVG's coordinate system.
+    let mut svg_transform = transform::identity();
+    // Maps conrod's coordinate system to SVG's coordinate system and back again.
+    let to_svg_matrix = [[1.0, 0.0, view_dim[0] / 2.0], [0.0, -1.0, view_dim[1] / 2.0]];
+    let from_svg_matrix = [[1.0, 0.0, -view_dim[0] / 2.0], [0.0, -1.0, view_dim[1] / 2.0]];
+
+    for primitive in primitives {
+        match primitive {
+
+            Primitive::Rectangle { color, rect } => {
+                let r = svg_rect(rect);
+                writeln!(svg, "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" {}/>",
+                         r[0], r[1], r[2], r[3], fill(color)).unwrap();
+            },
+
+            Primitive::Polygon { color, points } => {
+                let points = svg_points(points.iter().map(|&p| to_svg(p)));
+                writeln!(svg, "<polygon points=\"{}\" {}/>", points, fill(color)).unwrap();
+            },
+
+            Primitive::Lines { color, pattern, dash, thickness, cap, join, mut points } => {
+                // Closed paths are joined at their first point, matching `line::stroke_triangles`.
+                let is_closed = points.len() > 2 && points[0] == points[points.len() - 1];
+                let element = if is_closed {
+                    points.pop();
+                    "polygon"
+                } else {
+                    "polyline"
+                };
+                let points = svg_points(points.iter().map(|&p| to_svg(p)));
+                // SVG carries the dash pattern across the vertices of the polyline, matching
+                // `line::pattern_paths`. Dots are drawn as zero-length dashes with round ends.
+                let (linecap, dasharray) = match pattern {
+                    Pattern::Solid => (cap, String::new()),
+                    Pattern::Dashed => (cap, dasharray(dash.length, dash.gap, dash.phase)),
+                    Pattern::Dotted => {
+                        (Cap::Round, dasharray(0.0, thickness + dash.gap, dash.phase))
+                    },
+                };
+                let linecap = match linecap {
+                    Cap::Flat => "butt",
+                    Cap::Round => "round",
+                    Cap::Square => "square",
+                };
+                let linejoin = match join {
+                    Join::Miter(limit) => format!("miter\" stroke-miterlimit=\"{}", limit),
+                    Join::Round => "round".to_owned(),
+                    Join::Bevel => "bevel".to_owned(),
+                };
+                writeln!(svg,
+                         "<{} points=\"{}\" fill=\"none\" {} stroke-width=\"{}\" \
+                          stroke-linecap=\"{}\" stroke-linejoin=\"{}\"{}/>",
+                         element, points, stroke(color), thickness, linecap, linejoin,
+                         dasharray).unwrap();
+            },
+
+            Primitive::Triangles { triangles } => {
+                // Where the vertex colors of a triangle vary along a single direction they are
+                // described exactly by a `linearGradient`. Otherwise the triangle is filled with
+                // the average of its vertex colors.
+                for triangle in &triangles {
+                    let triangle = [(to_svg(triangle[0].0), triangle[0].1),
+                                    (to_svg(triangle[1].0), triangle[1].1),
+                                    (to_svg(triangle[2].0), triangle[2].1)];
+                    let (a, b, c) = (triangle[0].0, triangle[1].0, triangle[2].0);
+                    if (b[0] - a[0]) * (c[1] - a[1]) == (b[1] - a[1]) * (c[0] - a[0]) {
+                        // The triangle has no area and would not be visible.
+                        continue;
+                    }
+                    let points = svg_points(triangle.iter().map(|&(p, _)| p));
+                    if triangle[1].1 == triangle[0].1 && triangle[2].1 == triangle[0].1 {
+                        writeln!(svg, "<polygon points=\"{}\" {}/>",
+                                 points, fill(triangle[0].1)).unwrap();
+                    } else if let Some((start, end, a, b)) = linear_gradient(&triangle) {
+                        writeln!(svg,
+                                 "<linearGradient id=\"gradient{}\" \
+                                  gradientUnits=\"userSpaceOnUse\" \
+                                  x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\">\
+                                  <stop offset=\"0\" {}/><stop offset=\"1\" {}/>\
+                                  </linearGradient>",
+                                 num_gradients, start[0], start[1], end[0], end[1],
+                                 stop_color(a), stop_color(b)).unwrap();
+                        writeln!(svg, "<polygon points=\"{}\" fill=\"url(#gradient{})\"/>",
+                                 points, num_gradients).unwrap();
+                        num_gradients += 1;
+                    } else {
+                        let color = render::average_color(&triangle);
+                        writeln!(svg, "<polygon points=\"{}\" {}/>", points, fill(color)).unwrap();
+                    }
+                }
+            },
+
+            Primitive::Text { color, font_size, text, rect } => {
+                // Like the other backends, the baseline lies along the bottom of the **Rect**,
+                // rounded to the nearest pixel.
+                let xy = to_svg([rect.left().round(), rect.bottom().round()]);
+                writeln!(svg,
+                         "<text x=\"{}\" y=\"{}\" font-size=\"{}\" {} \
+                          dominant-baseline=\"alphabetic\" \
+                          xml:space=\"preserve\">{}</text>",
+                         xy[0], xy[1], font_size, fill(color), escape(text)).unwrap();
+            },
+
+            Primitive::Image { texture, color, src_rect, rect } => {
+                if let Some(href) = image_href(texture) {
+                    // A nested `svg` element is used to describe the source rectangle.
+                    let r = svg_rect(rect);
+                    let (x, y, src_w, src_h) = src_rect.x_y_w_h();
+                    let (tex_w, tex_h) = texture.get_size();
+                    // The tint multiplies each channel of the image, as with the other backends.
+                    // The filter operates in sRGB so that the tint is applied to the stored values.
+                    let filter = match color {
+                        Some(color) => {
+                            let c = color.to_fsa();
+                            writeln!(svg,
+                                     "<filter id=\"tint{}\" color-interpolation-filters=\"sRGB\">\
+                                      <feColorMatrix type=\"matrix\" values=\"\
+                                      {} 0 0 0 0 0 {} 0 0 0 0 0 {} 0 0 0 0 0 {} 0\"/>\
+                                      </filter>",
+                                     num_tints, c[0], c[1], c[2], c[3]).unwrap();
+                            let filter = format!(" filter=\"url(#tint{})\"", num_tints);
+                            num_tints += 1;
+                            filter
+                        },
+                        None => String::new(),
+                    };
+                    writeln!(svg,
+                             "<svg x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" \
+                              viewBox=\"{} {} {} {}\" preserveAspectRatio=\"none\">\
+                              <image width=\"{}\" height=\"{}\"{} xlink:href=\"{}\"/>\
+                              </svg>",
+                             r[0], r[1], r[2], r[3], x, y, src_w, src_h,
+                             tex_w, tex_h, filter, escape(&href)).unwrap();
+                }
+            },
+
+            Primitive::Scissor(rect) => {
+                // Clip areas are described in window coordinates, so they must be placed outside
+                // of the transformed group.
+                if !transform::is_identity(svg_transform) {
+                    writeln!(svg, "</g>").unwrap();
+                }
+                if is_clipped {
+                    writeln!(svg, "</g>").unwrap();
+                }
+                let r = svg_rect(rect);
+                writeln!(svg,
+                         "<clipPath id=\"clip{}\">\
+                          <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"/>\
+                          </clipPath>",
+                         num_clips, r[0], r[1], r[2], r[3]).unwrap();
+                writeln!(svg, "<g clip-path=\"url(#clip{})\">", num_clips).unwrap();
+                num_clips += 1;
+                is_clipped = true;
+                if !transform::is_identity(svg_transform) {
+                    writeln!(svg, "<g transform=\"{}\">", svg_matrix(svg_transform)).unwrap();
+                }
+            },
+
+            Primitive::Transform(matrix) => {
+                if !transform::is_identity(svg_transform) {
+                    writeln!(svg, "</g>").unwrap();
+                }
+                let matrix: Matrix2d = matrix;
+                svg_transform = transform::multiply(transform::multiply(to_svg_matrix, matrix),
+                                                    from_svg_matrix);
+                if !transform::is_identity(svg_transform) {
+                    writeln!(svg, "<g transform=\"{}\">", svg_matrix(svg_transform)).unwrap();
+                }
+            },
+        }
+    }
+
+    if !transform::is_identity(svg_transform) {
+        writeln!(svg, "</g>").unwrap();
+    }
+    if is_clipped {
+        writeln!(svg, "</g>").unwrap();
+    }
+    writeln!(svg, "</svg>").unwrap();
+    svg
+}
+
+
+/// The value of a `transform` attribute describing the given matrix.
+fn svg_matrix(m: Matrix2d) -> String {
+    format!("matrix({} {} {} {} {} {})", m[0][0], m[1][0], m[0][1], m[1][1], m[0][2], m[1][2])
+}
+
+/// The `fill` and `fill-opacity` attributes for the given **Color**.
+fn fill(color: Color) -> String {
+    let c = color.to_byte_fsa();
+    let a = color.to_fsa()[3];
+    format!("fill=\"rgb({},{},{})\" fill-opacity=\"{}\"", c[0], c[1], c[2], a)
+}
+
+/// The `stop-color` and `stop-opacity` attributes for the given **Color**.
+fn stop_color(color: Color) -> String {
+    let c = color.to_byte_fsa();
+    let a = color.to_fsa()[3];
+    format!("stop-color=\"rgb({},{},{})\" stop-opacity=\"{}\"", c[0], c[1], c[2], a)
+}
+
+/// The `stroke` and `stroke-opacity` attributes for the given **Color**.
+fn stroke(color: Color) -> String {
+    let c = color.to_byte_fsa();
+    let a = color.to_fsa()[3];
+    format!("stroke=\"rgb({},{},{})\" stroke-opacity=\"{}\"", c[0], c[1], c[2], a)
+}
+
+/// The `stroke-dasharray` and `stroke-dashoffset` attributes, preceded by a space.
+fn dasharray(on: Scalar, off: Scalar, phase: Scalar) -> String {
+    format!(" stroke-dasharray=\"{} {}\" stroke-dashoffset=\"{}\"", on, off, phase)
+}
+
+/// Describe the interpolation of the given triangle's vertex colors as a linear gradient.
+///
+/// Returns the start and end points of the gradient along with the colors at each, or `None` if
+/// the colors do not vary along a single direction or the triangle has no area.
+fn linear_gradient(triangle: &[(Point, Color); 3]) -> Option<(Point, Point, Color, Color)> {
+    let colors = [triangle[0].1.to_fsa(), triangle[1].1.to_fsa(), triangle[2].1.to_fsa()];
+    let sub = |a: [f32; 4], b: [f32; 4]| [a[0] - b[0], a[1] - b[1], a[2] - b[2], a[3] - b[3]];
+    let dot = |a: [f32; 4], b: [f32; 4]| a[0] * b[0] + a[1] * b[1] + a[2] * b[2] + a[3] * b[3];
+
+    // The pair of vertices whose colors differ the most describe the ends of the gradient.
+    let distance = |i: usize, j: usize| dot(sub(colors[j], colors[i]), sub(colors[j], colors[i]));
+    let pairs = [(0, 1, 2), (1, 2, 0), (2, 0, 1)];
+    let (i, j, k) = pairs.iter().fold(pairs[0], |max, &pair| {
+        if distance(pair.0, pair.1) > distance(max.0, max.1) { pair } else { max }
+    });
+    let span = sub(colors[j], colors[i]);
+    let len_sq = dot(span, span);
+    if len_sq == 0.0 {
+        return None;
+    }
+
+    // The remaining vertex's color must lie on the line between the other two.
+    let offset = sub(colors[k], colors[i]);
+    let s = dot(offset, span) / len_sq;
+    let residual = (0..4).fold(0.0, |max: f32, c| max.max((offset[c] - span[c] * s).abs()));
+    if residual > 1.0 / 512.0 {
+        return None;
+    }
+
+    // Find the gradient `g` of the position along the gradient, such that it is `0` at `i`, `1`
+    // at `j` and `s` at `k`.
+    let (pi, pj, pk) = (triangle[i].0, triangle[j].0, triangle[k].0);
+    let (u, v) = ([pj[0] - pi[0], pj[1] - pi[1]], [pk[0] - pi[0], pk[1] - pi[1]]);
+    let det = u[0] * v[1] - u[1] * v[0];
+    if det == 0.0 {
+        return None;
+    }
+    let s = s as Scalar;
+    let g = [(v[1] - s * u[1]) / det, (s * u[0] - v[0]) / det];
+    let g_len_sq = g[0] * g[0] + g[1] * g[1];
+    let end = [pi[0] + g[0] / g_len_sq, pi[1] + g[1] / g_len_sq];
+    Some((pi, end, triangle[i].1, triangle[j].1))
+}
+
+/// A list of points in the format expected by the `points` attribute.
+fn svg_points<I: Iterator<Item=[Scalar; 2]>>(points: I) -> String {
+    let mut s = String::new();
+    for (i, p) in points.enumerate() {
+        if i > 0 {
+            s.push(' ');
+        }
+        write!(s, "{},{}", p[0], p[1]).unwrap();
+    }
+    s
+}
+
+/// Escape the characters that have special meaning within XML.
+fn escape(text: &str) -> String {
+    let mut s = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => s.push_str("&amp;"),
+            '<' => s.push_str("&lt;"),
+            '>' => s.push_str("&gt;"),
+            '"' => s.push_str("&quot;"),
+            '\'' => s.push_str("&apos;"),
+            ch => s.push(ch),
+        }
+    }
+    s
+}
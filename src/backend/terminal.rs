@@ -0,0 +1,620 @@
+//! A text-mode backend that renders a **Ui** onto a grid of character cells, allowing the same
+//! widget code to drive a terminal user interface.
+//!
+//! - The **Grid** is a plain 2D buffer of **Cell**s, each with a `char` and foreground and
+//! background **Color**s. It may be printed to a terminal by the user or inspected in tests.
+//! - The **CharacterCache** gives every character the dimensions of a single cell, so that text
+//! laid out by conrod lines up with the grid.
+//! - Terminal **Input** may be converted to conrod's **RawEvent** by pairing it with the cell
+//! dimensions via `Grid::event`, producing an **Event** that implements **ToRawEvent**.
+//!
+//! The grid is a consumer of the [**render::Primitives**](../../render/struct.Primitives.html)
+//! stream. Cells are filled if their centre lies within a primitive. Translucent colors are not
+//! blended; any color with a non-zero alpha overwrites the cell.
+//!
+//! A **Rectangle** immediately followed by another **Rectangle** that is inset by the same amount
+//! on every side (as produced by a **FramedRectangle**) is drawn as a frame using box-drawing
+//! characters.
+//!
+//! Transformed primitives are mapped onto the grid point by point. As text is always laid out
+//! along a row, only the starting position of transformed text is affected.
+
+use {Backend, Color, Dimensions, FontSize, Point, Rect, Scalar, Ui};
+use backend::event::{Input as RawInput, Key, Motion, MouseButton, RawEvent, ToRawEvent};
+use backend::graphics::{Character, ImageSize};
+use color;
+use position::transform;
+use render::{self, Primitive};
+use std;
+use widget::primitive::line::{self, Pattern};
+
+
+/// A single character cell within a **Grid**.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Cell {
+    /// The character displayed within the cell.
+    pub ch: char,
+    /// The color of the character.
+    pub fg: Color,
+    /// The color of the cell behind the character.
+    pub bg: Color,
+}
+
+/// A 2D buffer of **Cell**s in row-major order, starting with the top-left cell.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Grid {
+    columns: u16,
+    rows: u16,
+    cell_dim: Dimensions,
+    cells: Vec<Cell>,
+}
+
+/// The texture type used by the terminal backend.
+///
+/// As terminals cannot display images, only the dimensions are stored. **Image** widgets are
+/// drawn as shaded cells.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Texture {
+    /// The width of the texture in pixels.
+    pub w: u32,
+    /// The height of the texture in pixels.
+    pub h: u32,
+}
+
+/// A **CharacterCache** in which every character occupies exactly one cell.
+#[derive(Copy, Clone, Debug)]
+pub struct CharacterCache {
+    cell: Texture,
+}
+
+/// The terminal **Backend**.
+pub type TerminalBackend = (Texture, CharacterCache);
+
+/// A **Ui** that may be rendered onto a **Grid**.
+pub type TerminalUi = Ui<TerminalBackend>;
+
+/// Input received from a terminal, described in terms of cells.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Input {
+    /// The mouse moved over the cell at the given column and row.
+    MouseMove {
+        /// The column, counting from the left.
+        column: u16,
+        /// The row, counting from the top.
+        row: u16,
+    },
+    /// A mouse button was pressed.
+    MousePress(MouseButton),
+    /// A mouse button was released.
+    MouseRelease(MouseButton),
+    /// The mouse wheel was scrolled by the given number of cells, where positive `y` is upwards.
+    Scroll {
+        /// The number of columns scrolled.
+        x: Scalar,
+        /// The number of rows scrolled.
+        y: Scalar,
+    },
+    /// A key was pressed.
+    KeyPress(Key),
+    /// A key was released.
+    KeyRelease(Key),
+    /// Some text was entered.
+    Text(String),
+    /// The terminal was resized to the given number of columns and rows.
+    Resize {
+        /// The new number of columns.
+        columns: u16,
+        /// The new number of rows.
+        rows: u16,
+    },
+}
+
+/// Terminal **Input** along with the cell dimensions necessary for converting it to a
+/// **RawEvent**.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Event {
+    /// The terminal input.
+    pub input: Input,
+    /// The dimensions of a single cell.
+    pub cell_dim: Dimensions,
+}
+
+/// The default dimensions of a single cell, roughly matching the aspect ratio of a terminal
+/// character.
+pub const DEFAULT_CELL_DIMENSIONS: Dimensions = [8.0, 16.0];
+
+/// The cell that every **Grid** cell is reset to.
+pub const EMPTY_CELL: Cell = Cell { ch: ' ', fg: color::WHITE, bg: color::BLACK };
+
+/// The area of the **Grid** to which drawing is currently restricted.
+///
+/// The bounds are `[left, top, right, bottom]` where `right` and `bottom` are exclusive.
+type CellBounds = [i64; 4];
+
+
+impl ImageSize for Texture {
+    fn get_size(&self) -> (u32, u32) {
+        (self.w, self.h)
+    }
+}
+
+impl CharacterCache {
+
+    /// Construct a **CharacterCache** for cells of the given dimensions.
+    pub fn new(cell_dim: Dimensions) -> Self {
+        CharacterCache {
+            cell: Texture { w: cell_dim[0] as u32, h: cell_dim[1] as u32 },
+        }
+    }
+
+}
+
+impl ::CharacterCache for CharacterCache {
+    type Texture = Texture;
+
+    fn character<'a>(&'a mut self, _font_size: FontSize, _ch: char) -> Character<'a, Texture> {
+        Character {
+            offset: [0.0, 0.0],
+            size: [self.cell.w as Scalar, self.cell.h as Scalar],
+            texture: &self.cell,
+        }
+    }
+}
+
+
+impl Grid {
+
+    /// Construct a new **Grid** of empty cells with the default cell dimensions.
+    pub fn new(columns: u16, rows: u16) -> Self {
+        Grid::with_cell_dimensions(columns, rows, DEFAULT_CELL_DIMENSIONS)
+    }
+
+    /// Construct a new **Grid** of empty cells, where each cell covers the given dimensions of the
+    /// **Ui**'s window.
+    pub fn with_cell_dimensions(columns: u16, rows: u16, cell_dim: Dimensions) -> Self {
+        Grid {
+            columns: columns,
+            rows: rows,
+            cell_dim: cell_dim,
+            cells: vec![EMPTY_CELL; columns as usize * rows as usize],
+        }
+    }
+
+    /// Construct a **TerminalUi** whose window covers the whole **Grid**.
+    pub fn ui(&self) -> TerminalUi {
+        let mut ui = Ui::new(CharacterCache::new(self.cell_dim), ::Theme::default());
+        let dim = self.window_dim();
+        ui.win_w = dim[0];
+        ui.win_h = dim[1];
+        ui
+    }
+
+    /// The number of columns in the **Grid**.
+    pub fn columns(&self) -> u16 {
+        self.columns
+    }
+
+    /// The number of rows in the **Grid**.
+    pub fn rows(&self) -> u16 {
+        self.rows
+    }
+
+    /// The dimensions of a single cell.
+    pub fn cell_dim(&self) -> Dimensions {
+        self.cell_dim
+    }
+
+    /// The dimensions of the **Ui** window covered by the **Grid**.
+    pub fn window_dim(&self) -> Dimensions {
+        [self.columns as Scalar * self.cell_dim[0], self.rows as Scalar * self.cell_dim[1]]
+    }
+
+    /// All cells in row-major order, starting with the top-left cell.
+    pub fn cells(&self) -> &[Cell] {
+        &self.cells
+    }
+
+    /// The cell at the given column and row, if there is one.
+    pub fn cell(&self, column: u16, row: u16) -> Option<&Cell> {
+        if column < self.columns && row < self.rows {
+            Some(&self.cells[row as usize * self.columns as usize + column as usize])
+        } else {
+            None
+        }
+    }
+
+    /// The characters of the given row as a **String**.
+    pub fn row_string(&self, row: u16) -> String {
+        let start = row as usize * self.columns as usize;
+        self.cells[start..start + self.columns as usize].iter().map(|c| c.ch).collect()
+    }
+
+    /// Reset every cell to the **EMPTY_CELL**.
+    pub fn clear(&mut self) {
+        for cell in self.cells.iter_mut() {
+            *cell = EMPTY_CELL;
+        }
+    }
+
+    /// Pair the given terminal **Input** with the **Grid**'s cell dimensions so that it may be
+    /// passed to `Ui::handle_event`.
+    pub fn event(&self, input: Input) -> Event {
+        Event {
+            input: input,
+            cell_dim: self.cell_dim,
+        }
+    }
+
+    /// Draw the given **Ui** onto the **Grid**.
+    pub fn draw_ui<B: Backend>(&mut self, ui: &Ui<B>) {
+        self.draw_primitives(ui.render_primitives());
+    }
+
+    /// Draw the given **Primitive**s onto the **Grid**.
+    pub fn draw_primitives<'a, T, I>(&mut self, primitives: I)
+        where T: 'a,
+              I: IntoIterator<Item=Primitive<'a, T>>,
+    {
+        let mut scissor = self.bounds();
+        let mut matrix = transform::identity();
+        let mut primitives = primitives.into_iter().peekable();
+        while let Some(primitive) = primitives.next() {
+            match primitive {
+
+                Primitive::Rectangle { color, rect } => {
+                    if !transform::is_axis_aligned(matrix) {
+                        let corners = transform::transform_rect(matrix, rect);
+                        self.fill_polygon(scissor, &corners, color);
+                        continue;
+                    }
+                    let rect = transform::bounding_rect(matrix, rect);
+                    // Check whether or not the following rectangle is the inner area of a frame.
+                    let inner = match primitives.peek() {
+                        Some(&Primitive::Rectangle { color: inner_color, rect: inner_rect }) => {
+                            let inner_rect = transform::bounding_rect(matrix, inner_rect);
+                            if is_framed_by(inner_rect, rect) {
+                                Some((inner_color, inner_rect))
+                            } else {
+                                None
+                            }
+                        },
+                        _ => None,
+                    };
+                    match inner {
+                        Some((inner_color, _)) => {
+                            primitives.next();
+                            self.draw_frame(scissor, rect, color, inner_color);
+                        },
+                        None => self.fill_rect(scissor, rect, color),
+                    }
+                },
+
+                Primitive::Polygon { color, points } => {
+                    let points: Vec<Point> = points.iter()
+                        .map(|&p| transform::transform_point(matrix, p))
+                        .collect();
+                    self.fill_polygon(scissor, &points, color);
+                },
+
+                Primitive::Lines { color, pattern, dash, thickness, points, .. } => {
+                    let points: Vec<Point> = points.iter()
+                        .map(|&p| transform::transform_point(matrix, p))
+                        .collect();
+                    for path in line::pattern_paths(&points, pattern, dash, thickness) {
+                        if let Pattern::Dotted = pattern {
+                            let column = self.column_at(path[0][0]);
+                            let row = self.row_at(path[0][1]);
+                            self.set_cell(scissor, column, row, |cell| {
+                                cell.ch = '·';
+                                cell.fg = color;
+                            });
+                            continue;
+                        }
+                        for window in path.windows(2) {
+                            self.draw_line(scissor, window[0], window[1], color);
+                        }
+                    }
+                },
+
+                Primitive::Triangles { triangles } => {
+                    for triangle in &triangles {
+                        let points: Vec<Point> = triangle.iter()
+                            .map(|&(p, _)| transform::transform_point(matrix, p))
+                            .collect();
+                        let color = render::average_color(triangle);
+                        self.fill_polygon(scissor, &points, color);
+                    }
+                },
+
+                Primitive::Text { color, text, rect, .. } => {
+                    let start = transform::transform_point(matrix, [rect.left(), rect.y()]);
+                    let row = self.row_at(start[1]);
+                    let left = start[0] + self.cell_dim[0] / 2.0;
+                    for (i, ch) in text.chars().enumerate() {
+                        let column = self.column_at(left + i as Scalar * self.cell_dim[0]);
+                        self.set_cell(scissor, column, row, |cell| {
+                            cell.ch = ch;
+                            cell.fg = color;
+                        });
+                    }
+                },
+
+                Primitive::Image { color, rect, .. } => {
+                    let fg = color.unwrap_or(color::WHITE);
+                    if !transform::is_axis_aligned(matrix) {
+                        let corners = transform::transform_rect(matrix, rect);
+                        self.shade_polygon(scissor, &corners, fg);
+                        continue;
+                    }
+                    let rect = transform::bounding_rect(matrix, rect);
+                    let cells = self.cells_within(scissor, rect);
+                    self.for_each_cell(cells, |_, _, cell| {
+                        cell.ch = '░';
+                        cell.fg = fg;
+                    });
+                },
+
+                Primitive::Scissor(rect) => {
+                    scissor = self.cells_within(self.bounds(), rect);
+                },
+
+                Primitive::Transform(m) => matrix = m,
+            }
+        }
+    }
+
+    /// The bounds of the entire **Grid**.
+    fn bounds(&self) -> CellBounds {
+        [0, 0, self.columns as i64, self.rows as i64]
+    }
+
+    /// The column containing the given *x* position.
+    fn column_at(&self, x: Scalar) -> i64 {
+        let window_w = self.columns as Scalar * self.cell_dim[0];
+        ((x + window_w / 2.0) / self.cell_dim[0]).floor() as i64
+    }
+
+    /// The row containing the given *y* position.
+    fn row_at(&self, y: Scalar) -> i64 {
+        let window_h = self.rows as Scalar * self.cell_dim[1];
+        ((window_h / 2.0 - y) / self.cell_dim[1]).floor() as i64
+    }
+
+    /// The centre of the cell at the given column and row.
+    fn cell_centre(&self, column: i64, row: i64) -> Point {
+        let dim = self.window_dim();
+        let x = (column as Scalar + 0.5) * self.cell_dim[0] - dim[0] / 2.0;
+        let y = dim[1] / 2.0 - (row as Scalar + 0.5) * self.cell_dim[1];
+        [x, y]
+    }
+
+    /// The bounds of all cells whose centres lie within the given **Rect**, restricted to the
+    /// given scissor.
+    fn cells_within(&self, scissor: CellBounds, rect: Rect) -> CellBounds {
+        let (l, r, b, t) = rect.l_r_b_t();
+        let half_w = self.cell_dim[0] / 2.0;
+        let half_h = self.cell_dim[1] / 2.0;
+        // The first and last cells whose centres are within the rect.
+        let left = self.column_at(l + half_w);
+        let right = self.column_at(r + half_w);
+        let top = self.row_at(t - half_h);
+        let bottom = self.row_at(b - half_h);
+        [
+            std::cmp::max(scissor[0], left),
+            std::cmp::max(scissor[1], top),
+            std::cmp::min(scissor[2], right),
+            std::cmp::min(scissor[3], bottom),
+        ]
+    }
+
+    /// Apply the given function to every cell within the given bounds.
+    fn for_each_cell<F>(&mut self, bounds: CellBounds, mut f: F)
+        where F: FnMut(i64, i64, &mut Cell),
+    {
+        for row in bounds[1]..bounds[3] {
+            for column in bounds[0]..bounds[2] {
+                let idx = row as usize * self.columns as usize + column as usize;
+                f(column, row, &mut self.cells[idx]);
+            }
+        }
+    }
+
+    /// Apply the given function to the cell at the given column and row if it is within the
+    /// scissor.
+    fn set_cell<F>(&mut self, scissor: CellBounds, column: i64, row: i64, f: F)
+        where F: FnOnce(&mut Cell),
+    {
+        if column >= scissor[0] && column < scissor[2] && row >= scissor[1] && row < scissor[3] {
+            let idx = row as usize * self.columns as usize + column as usize;
+            f(&mut self.cells[idx]);
+        }
+    }
+
+    /// Fill all cells within the given **Rect** with the given color.
+    fn fill_rect(&mut self, scissor: CellBounds, rect: Rect, color: Color) {
+        if is_transparent(color) {
+            return;
+        }
+        let cells = self.cells_within(scissor, rect);
+        self.for_each_cell(cells, |_, _, cell| {
+            cell.ch = ' ';
+            cell.bg = color;
+        });
+    }
+
+    /// Draw a frame around the edge of the given **Rect** using box-drawing characters.
+    fn draw_frame(&mut self, scissor: CellBounds, rect: Rect, frame_color: Color, color: Color) {
+        let cells = self.cells_within(self.bounds(), rect);
+        let (left, top, right, bottom) = (cells[0], cells[1], cells[2] - 1, cells[3] - 1);
+
+        // If there is no room for a frame, just fill the rect.
+        if right <= left || bottom <= top {
+            return self.fill_rect(scissor, rect, color);
+        }
+
+        let clipped = [
+            std::cmp::max(scissor[0], cells[0]),
+            std::cmp::max(scissor[1], cells[1]),
+            std::cmp::min(scissor[2], cells[2]),
+            std::cmp::min(scissor[3], cells[3]),
+        ];
+        let is_transparent = is_transparent(color);
+        self.for_each_cell(clipped, |column, row, cell| {
+            let ch = match (column, row) {
+                (c, r) if c == left && r == top => '┌',
+                (c, r) if c == right && r == top => '┐',
+                (c, r) if c == left && r == bottom => '└',
+                (c, r) if c == right && r == bottom => '┘',
+                (_, r) if r == top || r == bottom => '─',
+                (c, _) if c == left || c == right => '│',
+                _ => ' ',
+            };
+            if ch != ' ' {
+                cell.ch = ch;
+                cell.fg = frame_color;
+            } else if !is_transparent {
+                cell.ch = ch;
+            }
+            if !is_transparent {
+                cell.bg = color;
+            }
+        });
+    }
+
+    /// Fill all cells whose centres lie within the given polygon using the even-odd rule.
+    fn fill_polygon(&mut self, scissor: CellBounds, points: &[Point], color: Color) {
+        if is_transparent(color) {
+            return;
+        }
+        self.for_each_cell_in_polygon(scissor, points, |cell| {
+            cell.ch = ' ';
+            cell.bg = color;
+        });
+    }
+
+    /// Shade the cells whose centres lie within the given polygon, as is done for images.
+    fn shade_polygon(&mut self, scissor: CellBounds, points: &[Point], color: Color) {
+        self.for_each_cell_in_polygon(scissor, points, |cell| {
+            cell.ch = '░';
+            cell.fg = color;
+        });
+    }
+
+    /// Apply the given function to every cell within the scissor whose centre lies within the
+    /// given polygon.
+    fn for_each_cell_in_polygon<F>(&mut self, scissor: CellBounds, points: &[Point], mut f: F)
+        where F: FnMut(&mut Cell),
+    {
+        if points.len() < 3 {
+            return;
+        }
+        let is_inside = |p: Point| {
+            let mut inside = false;
+            for i in 0..points.len() {
+                let a = points[i];
+                let b = points[(i + 1) % points.len()];
+                if (a[1] > p[1]) != (b[1] > p[1])
+                && p[0] < a[0] + (p[1] - a[1]) / (b[1] - a[1]) * (b[0] - a[0]) {
+                    inside = !inside;
+                }
+            }
+            inside
+        };
+        let bounding_rect = ::utils::bounding_box_for_points(points.iter().cloned());
+        let bounds = self.cells_within(scissor, bounding_rect);
+        for row in bounds[1]..bounds[3] {
+            for column in bounds[0]..bounds[2] {
+                if is_inside(self.cell_centre(column, row)) {
+                    self.set_cell(scissor, column, row, |cell| f(cell));
+                }
+            }
+        }
+    }
+
+    /// Draw a line between the given points using box-drawing characters.
+    fn draw_line(&mut self, scissor: CellBounds, a: Point, b: Point, color: Color) {
+        if is_transparent(color) {
+            return;
+        }
+        let (dx, dy) = (b[0] - a[0], b[1] - a[1]);
+        // Choose a character based on the angle of the line relative to the cell's aspect ratio.
+        let (cell_dx, cell_dy) = (dx / self.cell_dim[0], dy / self.cell_dim[1]);
+        let ch = if cell_dy.abs() <= cell_dx.abs() / 2.0 {
+            '─'
+        } else if cell_dx.abs() <= cell_dy.abs() / 2.0 {
+            '│'
+        } else if (cell_dx > 0.0) == (cell_dy > 0.0) {
+            '╱'
+        } else {
+            '╲'
+        };
+        // Step along the line in increments of half a cell so that no cells are skipped.
+        let steps = (cell_dx.abs().max(cell_dy.abs()) * 2.0).ceil() as usize;
+        for i in 0..steps + 1 {
+            let t = if steps == 0 { 0.0 } else { i as Scalar / steps as Scalar };
+            let column = self.column_at(a[0] + dx * t);
+            let row = self.row_at(a[1] + dy * t);
+            self.set_cell(scissor, column, row, |cell| {
+                cell.ch = ch;
+                cell.fg = color;
+            });
+        }
+    }
+
+}
+
+impl std::fmt::Display for Grid {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for row in 0..self.rows {
+            if row > 0 {
+                writeln!(f, "")?;
+            }
+            write!(f, "{}", self.row_string(row))?;
+        }
+        Ok(())
+    }
+}
+
+
+impl ToRawEvent for Event {
+    fn to_raw_event(self, win_w: Scalar, win_h: Scalar) -> Option<RawEvent> {
+        let Event { input, cell_dim } = self;
+        let event = match input {
+            Input::MouseMove { column, row } => {
+                // Move to the centre of the cell.
+                let x = (column as Scalar + 0.5) * cell_dim[0] - win_w / 2.0;
+                let y = win_h / 2.0 - (row as Scalar + 0.5) * cell_dim[1];
+                RawInput::Move(Motion::MouseCursor(x, y))
+            },
+            Input::MousePress(button) => RawInput::Press(button.into()),
+            Input::MouseRelease(button) => RawInput::Release(button.into()),
+            Input::Scroll { x, y } => RawInput::Move(Motion::MouseScroll(x * cell_dim[0],
+                                                                         y * cell_dim[1])),
+            Input::KeyPress(key) => RawInput::Press(key.into()),
+            Input::KeyRelease(key) => RawInput::Release(key.into()),
+            Input::Text(text) => RawInput::Text(text),
+            Input::Resize { columns, rows } => {
+                let w = columns as Scalar * cell_dim[0];
+                let h = rows as Scalar * cell_dim[1];
+                RawInput::Resize(w as u32, h as u32)
+            },
+        };
+        Some(event.into())
+    }
+}
+
+
+/// Whether or not the given color is fully transparent.
+fn is_transparent(color: Color) -> bool {
+    color.to_fsa()[3] <= 0.0
+}
+
+/// Whether or not the `inner` **Rect** is inset within the `outer` **Rect** by the same amount on
+/// every side.
+fn is_framed_by(inner: Rect, outer: Rect) -> bool {
+    let (il, ir, ib, it) = inner.l_r_b_t();
+    let (ol, or, ob, ot) = outer.l_r_b_t();
+    let frame = il - ol;
+    frame > 0.0 && (or - ir - frame).abs() < 1e-6
+        && (ib - ob - frame).abs() < 1e-6 && (ot - it - frame).abs() < 1e-6
+}
@@ -327,6 +327,7 @@ pub fn hsl_to_rgb(hue: f32, saturation: f32, lightness: f32) -> (f32, f32, f32)
 
 
 /// Linear or Radial Gradient.
+#[deprecated(note = "no widget draws this type. Use `FillGradient` with the `Gradientable` trait")]
 #[derive(Clone, Debug)]
 pub enum Gradient {
     /// Takes a start and end point and then a series of color stops that indicate how to
@@ -340,12 +341,16 @@ pub enum Gradient {
 
 
 /// Create a linear gradient.
+#[deprecated(note = "use `FillGradient::linear` instead")]
+#[allow(deprecated)]
 pub fn linear(start: (f64, f64), end: (f64, f64), colors: Vec<(f64, Color)>) -> Gradient {
     Gradient::Linear(start, end, colors)
 }
 
 
 /// Create a radial gradient. 
+#[deprecated(note = "use `FillGradient::radial` instead")]
+#[allow(deprecated)]
 pub fn radial(start: (f64, f64), start_r: f64,
               end: (f64, f64), end_r: f64,
               colors: Vec<(f64, Color)>) -> Gradient {
@@ -353,6 +358,136 @@ pub fn radial(start: (f64, f64), start_r: f64,
 }
 
 
+/// The maximum number of color stops that a **FillGradient** may have.
+pub const MAX_GRADIENT_STOPS: usize = 8;
+
+/// A linear or radial gradient with which the area of some shape may be filled.
+///
+/// Gradients are described relative to the bounding rectangle of the shape that they fill.
+///
+/// A **FillGradient** has a fixed capacity of `MAX_GRADIENT_STOPS` color stops, allowing it to be
+/// `Copy` so that it may be used within widget **Style**s.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct FillGradient {
+    /// Whether the gradient is linear or radial.
+    pub kind: GradientKind,
+    num_stops: usize,
+    stops: [(f32, Color); MAX_GRADIENT_STOPS],
+}
+
+/// The shape of a **FillGradient**.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum GradientKind {
+    /// Colors vary along a line at the given angle in radians, where `0.0` runs from left to
+    /// right and `PI / 2.0` runs from bottom to top.
+    ///
+    /// A position of `0.0` lies on the corner of the bounding rectangle at which the line starts
+    /// and `1.0` lies on the opposite corner.
+    Linear {
+        /// The angle of the gradient in radians.
+        angle: f64,
+    },
+    /// Colors vary with the distance from a centre point.
+    ///
+    /// A position of `0.0` lies at the centre and `1.0` lies at the given radius.
+    Radial {
+        /// The centre of the gradient relative to the centre of the bounding rectangle.
+        centre: [f64; 2],
+        /// The distance from the centre at which the last color stop is reached.
+        radius: f64,
+    },
+}
+
+
+impl FillGradient {
+
+    /// Construct a **FillGradient** of the given kind from the given `(position, color)` stops.
+    ///
+    /// Positions are typically within the range `0.0...1.0`. The stops are sorted by position.
+    ///
+    /// Panics if more than `MAX_GRADIENT_STOPS` stops are given.
+    pub fn new(kind: GradientKind, stops: &[(f32, Color)]) -> Self {
+        assert!(stops.len() <= MAX_GRADIENT_STOPS,
+                "a FillGradient may have at most {} color stops, but {} were given",
+                MAX_GRADIENT_STOPS, stops.len());
+        let mut gradient = FillGradient {
+            kind: kind,
+            num_stops: 0,
+            stops: [(0.0, TRANSPARENT); MAX_GRADIENT_STOPS],
+        };
+        for &stop in stops {
+            // Insert the stop in order of position.
+            let mut i = gradient.num_stops;
+            while i > 0 && gradient.stops[i - 1].0 > stop.0 {
+                gradient.stops[i] = gradient.stops[i - 1];
+                i -= 1;
+            }
+            gradient.stops[i] = stop;
+            gradient.num_stops += 1;
+        }
+        gradient
+    }
+
+    /// A linear gradient at the given angle in radians.
+    ///
+    /// See [**GradientKind::Linear**](./enum.GradientKind.html) for details.
+    pub fn linear(angle: f64, stops: &[(f32, Color)]) -> Self {
+        FillGradient::new(GradientKind::Linear { angle: angle }, stops)
+    }
+
+    /// A radial gradient with the given centre (relative to the centre of the bounding rectangle)
+    /// and radius.
+    pub fn radial(centre: [f64; 2], radius: f64, stops: &[(f32, Color)]) -> Self {
+        FillGradient::new(GradientKind::Radial { centre: centre, radius: radius }, stops)
+    }
+
+    /// The color stops of the gradient, sorted by position.
+    pub fn stops(&self) -> &[(f32, Color)] {
+        &self.stops[..self.num_stops]
+    }
+
+    /// The color at the given position along the gradient.
+    ///
+    /// Positions before the first stop or after the last stop take the color of that stop.
+    pub fn color_at(&self, position: f32) -> Color {
+        let stops = self.stops();
+        let (first, last) = match (stops.first(), stops.last()) {
+            (Some(&first), Some(&last)) => (first, last),
+            _ => return TRANSPARENT,
+        };
+        if position <= first.0 {
+            return first.1;
+        }
+        if position >= last.0 {
+            return last.1;
+        }
+        for window in stops.windows(2) {
+            let ((start, a), (end, b)) = (window[0], window[1]);
+            if position <= end {
+                let t = if end > start { (position - start) / (end - start) } else { 1.0 };
+                let (a, b) = (a.to_fsa(), b.to_fsa());
+                let lerp = |i: usize| a[i] + (b[i] - a[i]) * t;
+                return rgba(lerp(0), lerp(1), lerp(2), lerp(3));
+            }
+        }
+        last.1
+    }
+
+    /// The same gradient with the given function applied to the color of each stop.
+    ///
+    /// This is useful for highlighting a gradient in the same way that some **Color** would be.
+    pub fn map_colors<F>(mut self, f: F) -> Self
+        where F: Fn(Color) -> Color,
+    {
+        for stop in self.stops[..self.num_stops].iter_mut() {
+            stop.1 = f(stop.1);
+        }
+        self
+    }
+
+}
+
+
 /// Built-in colors.
 ///
 /// These colors come from the
@@ -471,3 +606,23 @@ pub trait Colorable: Sized {
 
 }
 
+
+
+/// Types that may be filled with a **FillGradient**.
+pub trait Gradientable: Sized {
+
+    /// Fill the widget with the given gradient.
+    fn gradient(self, gradient: FillGradient) -> Self;
+
+    /// Fill the widget with a linear gradient at the given angle in radians.
+    fn linear_gradient(self, angle: f64, stops: &[(f32, Color)]) -> Self {
+        self.gradient(FillGradient::linear(angle, stops))
+    }
+
+    /// Fill the widget with a radial gradient with the given centre (relative to the centre of the
+    /// widget) and radius.
+    fn radial_gradient(self, centre: [f64; 2], radius: f64, stops: &[(f32, Color)]) -> Self {
+        self.gradient(FillGradient::radial(centre, radius, stops))
+    }
+
+}
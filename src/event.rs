@@ -5,6 +5,7 @@
 
 use input;
 use position::{Dimensions, Point};
+use position::transform::{self, Matrix2d};
 use utils::vec2_sub;
 use widget;
 
@@ -64,6 +65,22 @@ pub enum Ui {
     WidgetCapturesKeyboard(widget::Index),
     /// Indicates that the given widget has released the keyboard from capturing.
     WidgetUncapturesKeyboard(widget::Index),
+    /// Indicates that the mouse has moved over the given widget, which is now the top-most widget
+    /// under the mouse.
+    MouseEnter(widget::Index),
+    /// Indicates that the given widget is no longer the top-most widget under the mouse.
+    MouseLeave(widget::Index),
+    /// A drag-and-drop payload was dragged over the given widget.
+    DragEnter(widget::Index, DragAndDrop),
+    /// A drag-and-drop payload was moved while over the given widget.
+    DragOver(widget::Index, DragAndDrop),
+    /// A drag-and-drop payload was dragged away from the given widget, or the drag was cancelled
+    /// or dropped without being accepted while over it.
+    DragLeave(widget::Index, DragAndDrop),
+    /// A drag-and-drop payload was dropped onto the given widget after being accepted by it.
+    ///
+    /// The payload itself may be taken via `UiCell::take_dropped_payload`.
+    Drop(widget::Index, DragAndDrop),
 }
 
 /// Events that apply to a specific widget.
@@ -103,6 +120,25 @@ pub enum Widget {
     CapturesKeyboard,
     /// Indicates that the given widget has released the keyboard from capturing.
     UncapturesKeyboard,
+    /// The mouse has moved over the widget.
+    ///
+    /// The moment at which this occurred is available via `input::Widget::hover_start`.
+    MouseEnter,
+    /// The mouse is no longer over the widget.
+    MouseLeave,
+    /// A drag-and-drop payload was dragged over the widget.
+    ///
+    /// The payload may be inspected via `input::Widget::drag_payload` and accepted via
+    /// `UiCell::accept_drop`.
+    DragEnter(DragAndDrop),
+    /// A drag-and-drop payload was moved while over the widget.
+    DragOver(DragAndDrop),
+    /// A drag-and-drop payload is no longer over the widget.
+    DragLeave(DragAndDrop),
+    /// An accepted drag-and-drop payload was dropped onto the widget.
+    ///
+    /// The payload itself may be taken via `UiCell::take_dropped_payload`.
+    Drop(DragAndDrop),
 }
 
 /// Contains all relevant information for a Text event.
@@ -173,6 +209,17 @@ pub struct Drag {
     pub modifiers: input::keyboard::ModifierKey,
 }
 
+/// Contains all the relevant information for an event received by the target of a drag-and-drop.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct DragAndDrop {
+    /// The widget from which the payload is being dragged.
+    pub source: widget::Index,
+    /// The position of the mouse at the time of the event.
+    pub xy: Point,
+    /// Which modifier keys are being held during the event.
+    pub modifiers: input::keyboard::ModifierKey,
+}
+
 /// Contains all the relevant information for a mouse click.
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub struct Click {
@@ -230,6 +277,15 @@ impl Button {
             button => button,
         }
     }
+
+    /// Returns a copy of the Button with its position mapped by the given matrix.
+    pub fn transformed(&self, m: Matrix2d) -> Button {
+        match *self {
+            Button::Mouse(m_button, xy) =>
+                Button::Mouse(m_button, transform::transform_point(m, xy)),
+            button => button,
+        }
+    }
 }
 
 impl Press {
@@ -240,6 +296,14 @@ impl Press {
             ..*self
         }
     }
+
+    /// Returns a copy of the Press with its position mapped by the given matrix.
+    pub fn transformed(&self, m: Matrix2d) -> Press {
+        Press {
+            button: self.button.transformed(m),
+            ..*self
+        }
+    }
 }
 
 impl Release {
@@ -250,6 +314,14 @@ impl Release {
             ..*self
         }
     }
+
+    /// Returns a copy of the Release with its position mapped by the given matrix.
+    pub fn transformed(&self, m: Matrix2d) -> Release {
+        Release {
+            button: self.button.transformed(m),
+            ..*self
+        }
+    }
 }
 
 impl Click {
@@ -260,6 +332,14 @@ impl Click {
             ..*self
         }
     }
+
+    /// Returns a copy of the Click with its position mapped by the given matrix.
+    pub fn transformed(&self, m: Matrix2d) -> Click {
+        Click {
+            xy: transform::transform_point(m, self.xy),
+            ..*self
+        }
+    }
 }
 
 impl DoubleClick {
@@ -270,6 +350,14 @@ impl DoubleClick {
             ..*self
         }
     }
+
+    /// Returns a copy of the DoubleClick with its position mapped by the given matrix.
+    pub fn transformed(&self, m: Matrix2d) -> DoubleClick {
+        DoubleClick {
+            xy: transform::transform_point(m, self.xy),
+            ..*self
+        }
+    }
 }
 
 impl Drag {
@@ -282,6 +370,51 @@ impl Drag {
             ..*self
         }
     }
+
+    /// Returns a copy of the Drag with its positions and deltas mapped by the given matrix.
+    pub fn transformed(&self, m: Matrix2d) -> Drag {
+        let origin = transform::transform_point(m, self.origin);
+        let from = transform::transform_point(m, self.from);
+        let to = transform::transform_point(m, self.to);
+        Drag {
+            origin: origin,
+            from: from,
+            to: to,
+            delta_xy: vec2_sub(to, from),
+            total_delta_xy: vec2_sub(to, origin),
+            ..*self
+        }
+    }
+}
+
+impl Scroll {
+    /// Returns a copy of the Scroll with its amounts mapped by the linear part of the given matrix.
+    pub fn transformed(&self, m: Matrix2d) -> Scroll {
+        let xy = transform::transform_vector(m, [self.x, self.y]);
+        Scroll {
+            x: xy[0],
+            y: xy[1],
+            ..*self
+        }
+    }
+}
+
+impl DragAndDrop {
+    /// Returns a copy of the DragAndDrop relative to the given `xy`
+    pub fn relative_to(&self, xy: Point) -> DragAndDrop {
+        DragAndDrop {
+            xy: vec2_sub(self.xy, xy),
+            ..*self
+        }
+    }
+
+    /// Returns a copy of the DragAndDrop with its position mapped by the given matrix.
+    pub fn transformed(&self, m: Matrix2d) -> DragAndDrop {
+        DragAndDrop {
+            xy: transform::transform_point(m, self.xy),
+            ..*self
+        }
+    }
 }
 
 
@@ -7,8 +7,10 @@
 
 use daggy::Walker;
 use position::{Point, Rect};
+use position::transform::{self, Matrix2d};
 use std;
 use super::{EdgeIndex, Graph, GraphIndex, NodeIndex};
+use theme::Theme;
 use widget;
 
 
@@ -34,18 +36,36 @@ impl PickWidgets {
 
     /// The next `NodeIndex` under the `xy` location.
     ///
+    /// A widget is only considered to be under the `xy` location if it lies within the kid area of
+    /// every parent cropping the widget and the widget's
+    /// [**IsOverFn**](../../widget/type.IsOverFn.html) agrees.
+    ///
+    /// If the widget is transformed, the `xy` location is first mapped into the widget's
+    /// un-transformed space using the inverse of its
+    /// [**transform**](./fn.transform_of_widget.html). Each cropping parent is tested within its
+    /// own transformed space in the same manner.
+    ///
     /// This is called within `PickWidgets::next`.
     pub fn next_node_index(&mut self,
                            graph: &Graph,
-                           depth_order: &[NodeIndex]) -> Option<NodeIndex>
+                           depth_order: &[NodeIndex],
+                           theme: &Theme) -> Option<NodeIndex>
     {
         while self.idx > 0 {
             self.idx -= 1;
             match depth_order.get(self.idx) {
                 None => break,
                 Some(&idx) => {
-                    if let Some(visible_rect) = cropped_area_of_widget(graph, idx) {
-                        if visible_rect.is_over(self.xy) {
+                    // Widgets whose transform collapses them onto a line or point are invisible.
+                    let xy = match transform::invert(transform_of_widget(graph, idx)) {
+                        Some(inverse) => transform::transform_point(inverse, self.xy),
+                        None => continue,
+                    };
+                    if is_point_within_cropped_area(graph, idx, self.xy) {
+                        let is_over = graph.widget(idx)
+                            .map(|container| (container.is_over)(container, xy, theme))
+                            .unwrap_or(false);
+                        if is_over {
                             return Some(idx);
                         }
                     }
@@ -61,8 +81,12 @@ impl PickWidgets {
     ///
     /// If the next widget is some graphic element of another widget, the graphic parent will be
     /// returned.
-    pub fn next(&mut self, graph: &Graph, depth_order: &[NodeIndex]) -> Option<widget::Index> {
-        self.next_node_index(graph, depth_order)
+    pub fn next(&mut self,
+                graph: &Graph,
+                depth_order: &[NodeIndex],
+                theme: &Theme) -> Option<widget::Index>
+    {
+        self.next_node_index(graph, depth_order, theme)
             .map(|idx| {
                 // Ensure that if we've picked some widget that is a **Graphic** child of some
                 // other widget, we return the **Graphic** parent.
@@ -80,8 +104,12 @@ impl PickScrollableWidgets {
     /// The `widget::Index` of the next scrollable `Widget` under the `xy` location.
     ///
     /// The `Graph` is traversed from the top down.
-    pub fn next(&mut self, graph: &Graph, depth_order: &[NodeIndex]) -> Option<widget::Index> {
-        while let Some(idx) = self.pick_widgets.next_node_index(graph, depth_order) {
+    pub fn next(&mut self,
+                graph: &Graph,
+                depth_order: &[NodeIndex],
+                theme: &Theme) -> Option<widget::Index>
+    {
+        while let Some(idx) = self.pick_widgets.next_node_index(graph, depth_order, theme) {
             if let Some(ref container) = graph.widget(idx) {
                 if container.maybe_x_scroll_state.is_some()
                 || container.maybe_y_scroll_state.is_some() {
@@ -116,6 +144,48 @@ pub fn pick_scrollable_widgets(depth_order: &[NodeIndex], xy: Point) -> PickScro
 }
 
 
+/// The matrix describing the absolute transform of the widget with the given index.
+///
+/// This is the product of the **Transform**s of the widget and all of its depth-wise parents, where
+/// the widget's own **Transform** is applied first and its outermost parent's is applied last.
+pub fn transform_of_widget<I: GraphIndex>(graph: &Graph, idx: I) -> Matrix2d {
+    let local_matrix = |idx: NodeIndex| graph.widget(idx).and_then(|container| {
+        container.maybe_transform.map(|transform| transform.matrix(container.rect.xy()))
+    });
+
+    let idx = match graph.node_index(idx) {
+        Some(idx) => idx,
+        None => return transform::identity(),
+    };
+    let mut matrix = local_matrix(idx).unwrap_or_else(transform::identity);
+    let mut depth_parents = graph.depth_parent_recursion(idx);
+    while let Some(depth_parent) = depth_parents.next_node(graph) {
+        if let Some(parent_matrix) = local_matrix(depth_parent) {
+            matrix = transform::multiply(parent_matrix, matrix);
+        }
+    }
+    matrix
+}
+
+
+/// The opacity of the widget with the given index.
+///
+/// This is the product of the opacities of the widget and all of its depth-wise parents.
+pub fn opacity_of_widget<I: GraphIndex>(graph: &Graph, idx: I) -> f32 {
+    let idx = match graph.node_index(idx) {
+        Some(idx) => idx,
+        None => return 1.0,
+    };
+    let opacity = |idx: NodeIndex| graph.widget(idx).map(|container| container.opacity);
+    let mut product = opacity(idx).unwrap_or(1.0);
+    let mut depth_parents = graph.depth_parent_recursion(idx);
+    while let Some(depth_parent) = depth_parents.next_node(graph) {
+        product *= opacity(depth_parent).unwrap_or(1.0);
+    }
+    product
+}
+
+
 /// The rectangle that represents the maximum visible area for the widget with the given index.
 ///
 /// Specifically, this considers the cropped scroll area for all parents.
@@ -126,6 +196,74 @@ pub fn cropped_area_of_widget<I: GraphIndex>(graph: &Graph, idx: I) -> Option<Re
 }
 
 
+/// Whether or not the given window-space `xy` location lies within the kid area of every parent
+/// that crops the widget with the given index.
+///
+/// The `xy` location is mapped into the un-transformed space of each cropping parent before being
+/// compared against its kid area.
+pub fn is_point_within_cropped_area<I: GraphIndex>(graph: &Graph, idx: I, xy: Point) -> bool {
+    cropping_parents(graph, idx).into_iter().all(|parent| {
+        let kid_area = match graph.widget(parent) {
+            Some(parent_widget) => parent_widget.kid_area.rect,
+            None => return true,
+        };
+        match transform::invert(transform_of_widget(graph, parent)) {
+            Some(inverse) => kid_area.is_over(transform::transform_point(inverse, xy)),
+            None => false,
+        }
+    })
+}
+
+
+/// Whether or not some part of the given `rect`, drawn by the widget with the given index, lies
+/// within the kid area of every parent that crops the widget.
+///
+/// The `rect` is given within the widget's un-transformed space and is mapped into the
+/// un-transformed space of each cropping parent before being compared against its kid area. This
+/// is useful for areas that extend beyond the widget's own `Rect`, such as its shadow.
+pub fn is_rect_within_cropped_area<I: GraphIndex>(graph: &Graph, idx: I, rect: Rect) -> bool {
+    let matrix = transform_of_widget(graph, idx);
+    cropping_parents(graph, idx).into_iter().all(|parent| {
+        let kid_area = match graph.widget(parent) {
+            Some(parent_widget) => parent_widget.kid_area.rect,
+            None => return true,
+        };
+        match transform::invert(transform_of_widget(graph, parent)) {
+            Some(inverse) => {
+                let matrix = transform::multiply(inverse, matrix);
+                transform::bounding_rect(matrix, rect).overlap(kid_area).is_some()
+            },
+            None => false,
+        }
+    })
+}
+
+
+/// The depth-wise parents of the widget with the given index that crop it to their kid area.
+///
+/// A scrollable parent crops all of its depth-wise children, except for those that are graphical
+/// elements of it.
+fn cropping_parents<I: GraphIndex>(graph: &Graph, idx: I) -> Vec<NodeIndex> {
+    let mut idx = match graph.node_index(idx) {
+        Some(idx) => idx,
+        None => return Vec::new(),
+    };
+    let mut parents = Vec::new();
+    let mut depth_parents = graph.depth_parent_recursion(idx);
+    while let Some(depth_parent) = depth_parents.next_node(graph) {
+        if let Some(depth_parent_widget) = graph.widget(depth_parent) {
+            if (depth_parent_widget.maybe_x_scroll_state.is_some()
+                || depth_parent_widget.maybe_y_scroll_state.is_some())
+            && !graph.does_graphic_edge_exist(depth_parent, idx) {
+                parents.push(depth_parent);
+            }
+        }
+        idx = depth_parent;
+    }
+    parents
+}
+
+
 /// The rectangle that represents the maximum visible area for the widget with the given index.
 ///
 /// This specifically considers the cropped scroll area for all parents until (and not including)
@@ -148,44 +286,54 @@ fn cropped_area_of_widget_maybe_within_depth<I>(graph: &Graph,
                                                 deepest_idx: Option<NodeIndex>) -> Option<Rect>
     where I: GraphIndex,
 {
-    graph.node_index(idx).and_then(|mut idx| {
+    graph.node_index(idx).and_then(|idx| {
         graph.widget(idx).and_then(|widget| {
-            let mut overlapping_rect = widget.rect;
-            let mut depth_parents = graph.depth_parent_recursion(idx);
-            while let Some(depth_parent) = depth_parents.next_node(graph) {
+            cropped_area_maybe_within_depth(graph, idx, widget.rect, deepest_idx)
+        })
+    })
+}
 
-                // If the parent's index matches that of the deepest, we're done.
-                if Some(depth_parent) == deepest_idx {
-                    break;
-                }
 
-                // Check to see if our parent is a scrollable widget and whether or not we need to
-                // update the overlap.
-                //
-                // TODO: Consider "cropped area" here once implemented instead of scrolling.
-                if let Some(depth_parent_widget) = graph.widget(depth_parent) {
-                    if depth_parent_widget.maybe_x_scroll_state.is_some()
-                    || depth_parent_widget.maybe_y_scroll_state.is_some() {
-
-                        // If the depth_parent is also a **Graphic** parent, there is no need to
-                        // calculate overlap as the child is a graphical element of the parent and
-                        // thus is not cropped to it.
-                        if !graph.does_graphic_edge_exist(depth_parent, idx) {
-                            match overlapping_rect.overlap(depth_parent_widget.kid_area.rect) {
-                                Some(overlap) => overlapping_rect = overlap,
-                                None => return None,
-                            }
-                        }
+/// Crops the given `rect` of the widget at the given index to the scroll area of its parents.
+fn cropped_area_maybe_within_depth(graph: &Graph,
+                                   mut idx: NodeIndex,
+                                   rect: Rect,
+                                   deepest_idx: Option<NodeIndex>) -> Option<Rect>
+{
+    let mut overlapping_rect = rect;
+    let mut depth_parents = graph.depth_parent_recursion(idx);
+    while let Some(depth_parent) = depth_parents.next_node(graph) {
+
+        // If the parent's index matches that of the deepest, we're done.
+        if Some(depth_parent) == deepest_idx {
+            break;
+        }
+
+        // Check to see if our parent is a scrollable widget and whether or not we need to
+        // update the overlap.
+        //
+        // TODO: Consider "cropped area" here once implemented instead of scrolling.
+        if let Some(depth_parent_widget) = graph.widget(depth_parent) {
+            if depth_parent_widget.maybe_x_scroll_state.is_some()
+            || depth_parent_widget.maybe_y_scroll_state.is_some() {
+
+                // If the depth_parent is also a **Graphic** parent, there is no need to
+                // calculate overlap as the child is a graphical element of the parent and
+                // thus is not cropped to it.
+                if !graph.does_graphic_edge_exist(depth_parent, idx) {
+                    match overlapping_rect.overlap(depth_parent_widget.kid_area.rect) {
+                        Some(overlap) => overlapping_rect = overlap,
+                        None => return None,
                     }
                 }
-
-                // Set the current parent as the new child.
-                idx = depth_parent;
             }
+        }
 
-            Some(overlapping_rect)
-        })
-    })
+        // Set the current parent as the new child.
+        idx = depth_parent;
+    }
+
+    Some(overlapping_rect)
 }
 
 
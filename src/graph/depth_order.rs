@@ -76,6 +76,14 @@ impl DepthOrder {
             _ => std::cmp::Ordering::Equal,
         });
 
+        // Overlays are always visited after all other floating widgets so that they are drawn on
+        // top. The sort is stable, so they otherwise remain in the order in which they were
+        // clicked.
+        floating.sort_by_key(|&idx| match graph[idx] {
+            Node::Widget(ref container) => container.is_overlay,
+            _ => false,
+        });
+
         // Visit all of the floating widgets last.
         while !floating.is_empty() {
             let idx = floating.remove(0);
@@ -93,11 +101,12 @@ fn visit_by_depth(graph: &Graph,
                   depth_order: &mut Vec<NodeIndex>,
                   floating_deque: &mut Vec<NodeIndex>)
 {
-    // First, if the current node is a widget and it was set in the current `set_widgets` stage,
-    // store its index.
-    match graph.widget(idx).is_some() && updated_widgets.contains(&idx) {
+    // First, if the current node is a visible widget and it was set in the current `set_widgets`
+    // stage, store its index.
+    let is_visible = graph.widget(idx).map(|w| !w.is_hidden).unwrap_or(false);
+    match is_visible && updated_widgets.contains(&idx) {
         true => depth_order.push(idx),
-        // If the current node is not an updated widget, we're done with this branch.
+        // If the current node is not an updated, visible widget, we're done with this branch.
         false => return,
     }
 
@@ -4,7 +4,7 @@
 //! The primary type of interest in this module is the [**Graph**](./struct.Graph) type.
 
 use daggy;
-use position::{Axis, Depth, Rect};
+use position::{Axis, Depth, Rect, Transform};
 use self::index_map::IndexMap;
 use std::any::Any;
 use std::iter;
@@ -87,6 +87,23 @@ pub struct Container {
     ///
     /// See the `Widget::float` docs for an explanation of what this means.
     pub maybe_floating: Option<widget::Floating>,
+    /// Whether or not the floating widget is drawn above all other floating widgets.
+    ///
+    /// See the `Widget::overlay` docs for details.
+    pub is_overlay: bool,
+    /// The shadow drawn beneath the widget if it has one.
+    pub maybe_shadow: Option<widget::Shadow>,
+    /// The transform applied to the widget and its depth-wise children if it has one.
+    pub maybe_transform: Option<Transform>,
+    /// The opacity applied to the widget and its depth-wise children.
+    pub opacity: f32,
+    /// Whether or not the widget and its depth-wise children are excluded from drawing and
+    /// picking.
+    pub is_hidden: bool,
+    /// Determines whether or not some point lies over the widget.
+    pub is_over: widget::IsOverFn,
+    /// Whether or not the widget may receive keyboard focus via `Tab` traversal.
+    pub is_focusable: bool,
     /// Whether or not children widgets should be cropped to the `kid_area`.
     pub crop_kids: bool,
     /// Scroll related state (is only `Some` if this axis is scrollable).
@@ -207,6 +224,10 @@ impl Container {
                 depth: self.depth,
                 kid_area: self.kid_area,
                 maybe_floating: self.maybe_floating,
+                maybe_shadow: self.maybe_shadow,
+                maybe_transform: self.maybe_transform,
+                opacity: self.opacity,
+                is_hidden: self.is_hidden,
                 maybe_x_scroll_state: self.maybe_x_scroll_state,
                 maybe_y_scroll_state: self.maybe_y_scroll_state,
             })
@@ -700,8 +721,9 @@ impl Graph {
     {
         let widget::PreUpdateCache {
             kind, idx, maybe_parent_idx, maybe_x_positioned_relatively_idx,
-            maybe_y_positioned_relatively_idx, rect, depth, kid_area, maybe_floating,
-            crop_kids, maybe_x_scroll_state, maybe_y_scroll_state, maybe_graphics_for,
+            maybe_y_positioned_relatively_idx, rect, depth, kid_area, maybe_floating, is_overlay,
+            maybe_shadow, maybe_transform, opacity, is_hidden, is_over, is_focusable, crop_kids,
+            maybe_x_scroll_state, maybe_y_scroll_state, maybe_graphics_for,
         } = widget;
 
         // Construct a new `Container` to place in the `Graph`.
@@ -712,6 +734,13 @@ impl Graph {
             depth: depth,
             kid_area: kid_area,
             maybe_floating: maybe_floating,
+            is_overlay: is_overlay,
+            maybe_shadow: maybe_shadow,
+            maybe_transform: maybe_transform,
+            opacity: opacity,
+            is_hidden: is_hidden,
+            is_over: is_over,
+            is_focusable: is_focusable,
             crop_kids: crop_kids,
             maybe_x_scroll_state: maybe_x_scroll_state,
             maybe_y_scroll_state: maybe_y_scroll_state,
@@ -778,6 +807,13 @@ impl Graph {
                     container.depth = depth;
                     container.kid_area = kid_area;
                     container.maybe_floating = maybe_floating;
+                    container.is_overlay = is_overlay;
+                    container.maybe_shadow = maybe_shadow;
+                    container.maybe_transform = maybe_transform;
+                    container.opacity = opacity;
+                    container.is_hidden = is_hidden;
+                    container.is_over = is_over;
+                    container.is_focusable = is_focusable;
                     container.crop_kids = crop_kids;
                     container.maybe_x_scroll_state = maybe_x_scroll_state;
                     container.maybe_y_scroll_state = maybe_y_scroll_state;
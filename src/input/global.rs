@@ -5,6 +5,8 @@
 use event;
 use input;
 use std;
+use std::any::Any;
+use widget;
 
 /// Global input event handler that also implements `input::Provider`. The `Ui` passes all events
 /// to it's `Global` instance, which aggregates and interprets the events to provide so-called
@@ -20,6 +22,33 @@ pub struct Global {
     /// Tracks the last click that occurred and the time at which it occurred in order to create
     /// double-click events.
     pub last_click: Option<(std::time::Instant, event::Click)>,
+    /// The moment at which the mouse cursor last moved, if it has moved at all.
+    ///
+    /// This is useful for effects that should only occur once the mouse has rested for some
+    /// duration, such as showing a tooltip.
+    pub last_mouse_move: Option<std::time::Instant>,
+    /// The drag-and-drop operation that is currently under way, if any.
+    dragging: Option<Dragging>,
+    /// The dropped payloads along with the widgets onto which they were dropped, in the order in
+    /// which they were dropped.
+    ///
+    /// Each remains available until it is taken or the end of the next update cycle.
+    dropped: Vec<(widget::Index, Box<Any>)>,
+}
+
+/// A drag-and-drop operation that is currently under way.
+///
+/// Started via `UiCell::start_drag`, the operation ends once the left mouse button is released
+/// or the `Escape` key is pressed.
+pub struct Dragging {
+    /// The widget from which the payload is being dragged.
+    pub source: widget::Index,
+    /// The data being dragged, which may be downcast by targets to the expected type.
+    pub payload: Box<Any>,
+    /// The widget over which the payload is currently being dragged, if any.
+    pub maybe_target: Option<widget::Index>,
+    /// Whether or not the `maybe_target` has accepted the payload.
+    pub is_accepted: bool,
 }
 
 /// Iterator over all global `event::Event`s that have occurred since the last time
@@ -45,6 +74,9 @@ impl Global {
             start: input::State::new(),
             current: input::State::new(),
             last_click: None,
+            last_mouse_move: None,
+            dragging: None,
+            dropped: Vec::new(),
         }
     }
 
@@ -59,13 +91,147 @@ impl Global {
         self.events.push(event);
     }
 
+    /// Set the widget that is currently under the mouse.
+    ///
+    /// If the widget has changed, a `MouseLeave` event is pushed for the previous widget and a
+    /// `MouseEnter` event is pushed for the new one, and the moment at which the new widget began
+    /// being hovered is recorded.
+    pub fn set_widget_under_mouse(&mut self, maybe_idx: Option<widget::Index>) {
+        if self.current.widget_under_mouse == maybe_idx {
+            return;
+        }
+        if let Some(idx) = self.current.widget_under_mouse {
+            self.push_event(event::Ui::MouseLeave(idx).into());
+        }
+        if let Some(idx) = maybe_idx {
+            self.push_event(event::Ui::MouseEnter(idx).into());
+        }
+        self.current.widget_under_mouse = maybe_idx;
+        self.current.widget_under_mouse_since = maybe_idx.map(|_| std::time::Instant::now());
+    }
+
+    /// The drag-and-drop operation that is currently under way, if any.
+    pub fn dragging(&self) -> Option<&Dragging> {
+        self.dragging.as_ref()
+    }
+
+    /// Begin dragging the given payload from the `source` widget.
+    ///
+    /// Does nothing if a drag-and-drop operation is already under way, so that a source may call
+    /// this for every `Drag` event it receives.
+    pub fn start_drag(&mut self, source: widget::Index, payload: Box<Any>) {
+        if self.dragging.is_some() {
+            return;
+        }
+        self.dragging = Some(Dragging {
+            source: source,
+            payload: payload,
+            maybe_target: None,
+            is_accepted: false,
+        });
+    }
+
+    /// Set the widget over which the current drag-and-drop payload is being dragged.
+    ///
+    /// If the target has changed, a `DragLeave` event is pushed for the previous target and a
+    /// `DragEnter` event is pushed for the new one. Otherwise a `DragOver` event is pushed for the
+    /// current target. Does nothing if there is no drag-and-drop operation under way.
+    pub fn set_drag_target(&mut self, maybe_idx: Option<widget::Index>) {
+        let (source, maybe_target) = match self.dragging {
+            Some(ref dragging) => (dragging.source, dragging.maybe_target),
+            None => return,
+        };
+        let drag_and_drop = self.drag_and_drop(source);
+        if maybe_target == maybe_idx {
+            if let Some(idx) = maybe_idx {
+                self.push_event(event::Ui::DragOver(idx, drag_and_drop).into());
+            }
+            return;
+        }
+        if let Some(idx) = maybe_target {
+            self.push_event(event::Ui::DragLeave(idx, drag_and_drop).into());
+        }
+        if let Some(idx) = maybe_idx {
+            self.push_event(event::Ui::DragEnter(idx, drag_and_drop).into());
+        }
+        if let Some(ref mut dragging) = self.dragging {
+            dragging.maybe_target = maybe_idx;
+            dragging.is_accepted = false;
+        }
+    }
+
+    /// Accept the current drag-and-drop payload on behalf of the given widget, so that it is
+    /// dropped onto the widget once released.
+    ///
+    /// Does nothing if the given widget is not the current drag target.
+    pub fn accept_drop(&mut self, idx: widget::Index) {
+        if let Some(ref mut dragging) = self.dragging {
+            if dragging.maybe_target == Some(idx) {
+                dragging.is_accepted = true;
+            }
+        }
+    }
+
+    /// End the current drag-and-drop operation, dropping the payload onto the current target.
+    ///
+    /// If the target has accepted the payload, a `Drop` event is pushed and the payload is stored
+    /// alongside any others dropped during the update cycle until it is taken or the next update
+    /// cycle ends. Otherwise the drag is cancelled.
+    pub fn drop_payload(&mut self) {
+        let dragging = match self.dragging.take() {
+            Some(dragging) => dragging,
+            None => return,
+        };
+        let drag_and_drop = self.drag_and_drop(dragging.source);
+        match dragging.maybe_target {
+            Some(idx) if dragging.is_accepted => {
+                self.push_event(event::Ui::Drop(idx, drag_and_drop).into());
+                self.dropped.push((idx, dragging.payload));
+            },
+            Some(idx) => self.push_event(event::Ui::DragLeave(idx, drag_and_drop).into()),
+            None => (),
+        }
+    }
+
+    /// Cancel the current drag-and-drop operation, discarding its payload.
+    ///
+    /// A `DragLeave` event is pushed for the current target, if there is one.
+    pub fn cancel_drag(&mut self) {
+        if let Some(dragging) = self.dragging.take() {
+            if let Some(idx) = dragging.maybe_target {
+                let drag_and_drop = self.drag_and_drop(dragging.source);
+                self.push_event(event::Ui::DragLeave(idx, drag_and_drop).into());
+            }
+        }
+    }
+
+    /// Take the earliest payload dropped onto the given widget that has not yet been taken.
+    ///
+    /// Returns `None` once all payloads dropped onto the widget since the start of the last update
+    /// cycle have been taken.
+    pub fn take_dropped_payload(&mut self, idx: widget::Index) -> Option<Box<Any>> {
+        self.dropped.iter()
+            .position(|&(target, _)| target == idx)
+            .map(|i| self.dropped.remove(i).1)
+    }
+
     /// Called at the end of every update cycle in order to prepare the `Global` to
     /// handle events for the next one.
     pub fn clear_events_and_update_start_state(&mut self) {
         self.events.clear();
+        self.dropped.clear();
         self.start = self.current.clone();
     }
 
+    /// An `event::DragAndDrop` for the given source describing the current input state.
+    fn drag_and_drop(&self, source: widget::Index) -> event::DragAndDrop {
+        event::DragAndDrop {
+            source: source,
+            xy: self.current.mouse.xy,
+            modifiers: self.current.modifiers,
+        }
+    }
+
 }
 
 impl<'a> Events<'a> {
@@ -10,6 +10,7 @@
 use backend::event::keyboard::{NO_MODIFIER, ModifierKey};
 use position::Point;
 use self::mouse::Mouse;
+use std::time::Instant;
 use utils;
 use widget;
 
@@ -33,6 +34,8 @@ pub struct State {
     /// If the mouse is currently over multiple widgets, this index will represent the top-most,
     /// non-graphic-child widget.
     pub widget_under_mouse: Option<widget::Index>,
+    /// The moment at which the mouse moved over the `widget_under_mouse`, if there is one.
+    pub widget_under_mouse_since: Option<Instant>,
     /// Which modifier keys are being held down.
     pub modifiers: ModifierKey,
 }
@@ -46,6 +49,7 @@ impl State {
             widget_capturing_keyboard: None,
             widget_capturing_mouse: None,
             widget_under_mouse: None,
+            widget_under_mouse_since: None,
             modifiers: NO_MODIFIER,
         }
     }
@@ -6,6 +6,9 @@
 use {Point, Rect};
 use event;
 use input;
+use position::transform::{self, Matrix2d};
+use std::any::Any;
+use std::time::Instant;
 use utils;
 use widget;
 
@@ -22,6 +25,7 @@ pub struct Widget<'a> {
     global: &'a input::Global,
     rect: Rect,
     idx: widget::Index,
+    inverse_transform: Matrix2d,
 }
 
 /// A view of the `input::state::Mouse` that is specific to a single widget.
@@ -29,6 +33,7 @@ pub struct Widget<'a> {
 pub struct Mouse<'a> {
     rect: Rect,
     mouse_abs_xy: Point,
+    inverse_transform: Matrix2d,
     /// The state of each `MouseButton`.
     pub buttons: &'a input::state::mouse::ButtonMap,
 }
@@ -47,6 +52,7 @@ pub struct Events<'a> {
     capturing_mouse: Option<widget::Index>,
     rect: Rect,
     idx: widget::Index,
+    inverse_transform: Matrix2d,
 }
 
 /// An `Iterator` yielding all mouse clicks occuring within the given sequence of `widget::Event`s.
@@ -107,9 +113,23 @@ impl<'a> Widget<'a> {
             global: global,
             rect: rect,
             idx: idx,
+            inverse_transform: transform::identity(),
         }
     }
 
+    /// Consider the widget to be drawn using the given absolute transform.
+    ///
+    /// All mouse coordinates are mapped through the inverse of the transform before being made
+    /// relative to the widget, so that they describe positions within the widget's un-transformed
+    /// `Rect`. Scroll amounts are mapped through the inverse's rotation and scale alone. Transforms
+    /// that cannot be inverted are ignored.
+    pub fn transformed(mut self, matrix: Matrix2d) -> Self {
+        if let Some(inverse) = transform::invert(matrix) {
+            self.inverse_transform = inverse;
+        }
+        self
+    }
+
     /// If the widget is currently capturing the mouse, this returns the state of the mouse.
     ///
     /// Returns `None` if the widget is not capturing the mouse.
@@ -119,12 +139,37 @@ impl<'a> Widget<'a> {
                 buttons: &self.global.current.mouse.buttons,
                 mouse_abs_xy: self.global.current.mouse.xy,
                 rect: self.rect,
+                inverse_transform: self.inverse_transform,
             };
             return Some(mouse);
         }
         None
     }
 
+    /// The moment at which the mouse moved over the widget.
+    ///
+    /// Returns `None` if the widget is not the top-most widget under the mouse. This is useful for
+    /// effects that should only occur once the widget has been hovered for some duration, e.g.
+    /// `hover_start().map(|start| start.elapsed() > delay)`.
+    pub fn hover_start(&self) -> Option<Instant> {
+        if self.global.current.widget_under_mouse == Some(self.idx) {
+            return self.global.current.widget_under_mouse_since;
+        }
+        None
+    }
+
+    /// The payload of the drag-and-drop operation currently under way, if it is being dragged over
+    /// the widget.
+    ///
+    /// This is useful for deciding whether or not to accept the drop via `UiCell::accept_drop`,
+    /// e.g. `drag_payload().map(|payload| payload.is::<MyItem>())`.
+    pub fn drag_payload(&self) -> Option<&'a Any> {
+        match self.global.dragging() {
+            Some(dragging) if dragging.maybe_target == Some(self.idx) => Some(&*dragging.payload),
+            _ => None,
+        }
+    }
+
     /// Produces an iterator yielding all events that are relevant to a specific widget.
     ///
     /// All events provided by this Iterator will be filtered in accordance with input capturing. For
@@ -139,6 +184,7 @@ impl<'a> Widget<'a> {
             capturing_mouse: self.global.start.widget_capturing_mouse,
             rect: self.rect,
             idx: self.idx,
+            inverse_transform: self.inverse_transform,
         }
     }
 
@@ -181,13 +227,20 @@ impl<'a> Mouse<'a> {
     }
 
     /// The position of the mouse relative to the middle of the widget's `Rect`.
+    ///
+    /// If the widget is transformed, this is the position within the un-transformed `Rect`.
     pub fn rel_xy(&self) -> Point {
-        utils::vec2_sub(self.mouse_abs_xy, self.rect.xy())
+        utils::vec2_sub(self.local_xy(), self.rect.xy())
     }
 
     /// Is the mouse currently over the widget.
     pub fn is_over(&self) -> bool {
-        self.rect.is_over(self.mouse_abs_xy)
+        self.rect.is_over(self.local_xy())
+    }
+
+    /// The absolute position of the mouse mapped into the widget's un-transformed space.
+    fn local_xy(&self) -> Point {
+        transform::transform_point(self.inverse_transform, self.mouse_abs_xy)
     }
 
 }
@@ -288,6 +341,34 @@ impl<'a> Iterator for Events<'a> {
                     }
                 },
 
+                // Hovering.
+                event::Ui::MouseEnter(idx) if idx == self.idx =>
+                    return Some(event::Widget::MouseEnter),
+                event::Ui::MouseLeave(idx) if idx == self.idx =>
+                    return Some(event::Widget::MouseLeave),
+
+                // Drag-and-drop.
+                event::Ui::DragEnter(idx, ref drag_and_drop) if idx == self.idx => {
+                    let drag_and_drop = drag_and_drop.transformed(self.inverse_transform);
+                    let drag_and_drop = drag_and_drop.relative_to(self.rect.xy());
+                    return Some(event::Widget::DragEnter(drag_and_drop));
+                },
+                event::Ui::DragOver(idx, ref drag_and_drop) if idx == self.idx => {
+                    let drag_and_drop = drag_and_drop.transformed(self.inverse_transform);
+                    let drag_and_drop = drag_and_drop.relative_to(self.rect.xy());
+                    return Some(event::Widget::DragOver(drag_and_drop));
+                },
+                event::Ui::DragLeave(idx, ref drag_and_drop) if idx == self.idx => {
+                    let drag_and_drop = drag_and_drop.transformed(self.inverse_transform);
+                    let drag_and_drop = drag_and_drop.relative_to(self.rect.xy());
+                    return Some(event::Widget::DragLeave(drag_and_drop));
+                },
+                event::Ui::Drop(idx, ref drag_and_drop) if idx == self.idx => {
+                    let drag_and_drop = drag_and_drop.transformed(self.inverse_transform);
+                    let drag_and_drop = drag_and_drop.relative_to(self.rect.xy());
+                    return Some(event::Widget::Drop(drag_and_drop));
+                },
+
                 event::Ui::WindowResized(dim) =>
                     return Some(event::Widget::WindowResized(dim)),
 
@@ -297,23 +378,33 @@ impl<'a> Iterator for Events<'a> {
                 event::Ui::Move(idx, ref move_) if idx == Some(self.idx) =>
                     return Some(move_.clone().into()),
 
-                event::Ui::Press(idx, ref press) if idx == Some(self.idx) =>
-                    return Some(press.clone().relative_to(self.rect.xy()).into()),
-                
-                event::Ui::Release(idx, ref release) if idx == Some(self.idx) =>
-                    return Some(release.clone().relative_to(self.rect.xy()).into()),
+                event::Ui::Press(idx, ref press) if idx == Some(self.idx) => {
+                    let press = press.transformed(self.inverse_transform);
+                    return Some(press.relative_to(self.rect.xy()).into());
+                },
 
-                event::Ui::Click(idx, ref click) if idx == Some(self.idx) =>
-                    return Some(click.clone().relative_to(self.rect.xy()).into()),
+                event::Ui::Release(idx, ref release) if idx == Some(self.idx) => {
+                    let release = release.transformed(self.inverse_transform);
+                    return Some(release.relative_to(self.rect.xy()).into());
+                },
 
-                event::Ui::DoubleClick(idx, ref double_click) if idx == Some(self.idx) =>
-                    return Some(double_click.clone().relative_to(self.rect.xy()).into()),
+                event::Ui::Click(idx, ref click) if idx == Some(self.idx) => {
+                    let click = click.transformed(self.inverse_transform);
+                    return Some(click.relative_to(self.rect.xy()).into());
+                },
 
-                event::Ui::Drag(idx, ref drag) if idx == Some(self.idx) =>
-                    return Some(drag.clone().relative_to(self.rect.xy()).into()),
+                event::Ui::DoubleClick(idx, ref double_click) if idx == Some(self.idx) => {
+                    let double_click = double_click.transformed(self.inverse_transform);
+                    return Some(double_click.relative_to(self.rect.xy()).into());
+                },
+
+                event::Ui::Drag(idx, ref drag) if idx == Some(self.idx) => {
+                    let drag = drag.transformed(self.inverse_transform);
+                    return Some(drag.relative_to(self.rect.xy()).into());
+                },
 
                 event::Ui::Scroll(idx, ref scroll) if idx == Some(self.idx) =>
-                    return Some(scroll.clone().into()),
+                    return Some(scroll.transformed(self.inverse_transform).into()),
 
                 _ => (),
                 
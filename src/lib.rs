@@ -16,15 +16,19 @@ extern crate input as piston_input;
 
 
 pub use widget::primitive::line::Line;
-pub use widget::primitive::image::Image;
+pub use widget::primitive::image::{Fit as ImageFit, Image, NineSlice, Skin as ImageSkin};
 pub use widget::primitive::point_path::PointPath;
+pub use widget::primitive::shape::arc::Arc;
+pub use widget::primitive::shape::bezier::{Bezier, Curve as BezierCurve};
 pub use widget::primitive::shape::circle::Circle;
 pub use widget::primitive::shape::framed_rectangle::FramedRectangle;
 pub use widget::primitive::shape::polygon::Polygon;
 pub use widget::primitive::shape::oval::Oval;
 pub use widget::primitive::shape::rectangle::Rectangle;
+pub use widget::primitive::shape::rounded_rectangle::RoundedRectangle;
 pub use widget::primitive::text::{Text, Wrap as TextWrap};
 
+pub use widget::animated_image::AnimatedImage;
 pub use widget::button::Button;
 pub use widget::canvas::Canvas;
 pub use widget::drop_down_list::DropDownList;
@@ -40,6 +44,7 @@ pub use widget::text_box::TextBox;
 pub use widget::text_edit::TextEdit;
 pub use widget::title_bar::TitleBar;
 pub use widget::toggle::Toggle;
+pub use widget::tooltip::Tooltip;
 pub use widget::xy_pad::XYPad;
 
 
@@ -49,6 +54,7 @@ pub use widget::primitive::shape::Style as ShapeStyle;
 pub use widget::primitive::shape::framed_rectangle::Style as FramedRectangleStyle;
 pub use widget::primitive::text::Style as TextStyle;
 
+pub use widget::animated_image::Style as AnimatedImageStyle;
 pub use widget::button::Style as ButtonStyle;
 pub use widget::canvas::Style as CanvasStyle;
 pub use widget::drop_down_list::Style as DropDownListStyle;
@@ -62,25 +68,29 @@ pub use widget::text_box::Style as TextBoxStyle;
 pub use widget::text_edit::Style as TextEditStyle;
 pub use widget::title_bar::Style as TitleBarStyle;
 pub use widget::toggle::Style as ToggleStyle;
+pub use widget::tooltip::Style as TooltipStyle;
 pub use widget::xy_pad::Style as XYPadStyle;
 
 
 pub use backend::{Backend, CharacterCache, Graphics};
 pub use background::Background;
-pub use color::{Color, Colorable};
+#[allow(deprecated)]
+pub use color::{Color, Colorable, Gradient, Gradientable};
+pub use color::{FillGradient, GradientKind};
 pub use frame::{Framing, Frameable};
 pub use glyph_cache::GlyphCache;
 pub use graph::NodeIndex;
 pub use label::{FontSize, Labelable};
 pub use position::{Align, Axis, Corner, Depth, Direction, Dimension, Dimensions, Edge, Margin,
-                   Padding, Place, Point, Position, Positionable, Range, Rect, Scalar, Sizeable};
+                   Padding, Place, Point, Position, Positionable, Range, Rect, Scalar, Sizeable,
+                   Transform};
 //pub use position::Matrix as PositionMatrix;
-pub use theme::Theme;
+pub use theme::{FocusRing, Theme};
 pub use ui::{Ui, UiCell};
 pub use widget::{default_x_dimension, default_y_dimension};
 pub use widget::scroll;
 pub use widget::{CommonBuilder, CommonState, CommonStyle, Floating, IndexSlot, MaybeParent,
-                 UpdateArgs, Widget};
+                 Shadow, UpdateArgs, Widget};
 pub use widget::{KidArea, KidAreaArgs};
 pub use widget::CommonState as WidgetCommonState;
 pub use widget::Id as WidgetId;
@@ -100,6 +110,8 @@ pub mod guide;
 pub mod input;
 mod label;
 mod position;
+pub mod render;
+pub mod testing;
 pub mod text;
 pub mod theme;
 mod ui;
@@ -4,12 +4,14 @@ use widget;
 
 pub use self::range::{Edge, Range};
 pub use self::rect::{Corner, Rect};
+pub use self::transform::Transform;
 //pub use self::matrix::Matrix;
 
 
 //pub mod matrix;
 pub mod range;
 pub mod rect;
+pub mod transform;
 
 
 /// An alias over the Scalar type used throughout Conrod.
@@ -0,0 +1,177 @@
+//! Types and functions for describing the rotation, scale and translation of widgets.
+
+
+use piston_graphics::math;
+use super::{Point, Rect, Scalar};
+
+
+/// A 2D affine transformation matrix, stored as two rows of three columns.
+///
+/// A point `[x, y]` is mapped to `[m[0][0] * x + m[0][1] * y + m[0][2], m[1][0] * x + m[1][1] *
+/// y + m[1][2]]`. This is the same layout used by piston's `graphics::math::Matrix2d`.
+pub type Matrix2d = math::Matrix2d;
+
+/// A rotation, scale and translation applied to a widget along with all of its depth-wise
+/// children.
+///
+/// The rotation and scale are applied about the `pivot`, which is given relative to the centre of
+/// the widget's **Rect**. The `translation` is applied afterwards.
+///
+/// Widgets are still laid out using their un-transformed **Rect**s. The **Transform** only affects
+/// where the widget is drawn and where it receives mouse input.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Transform {
+    /// The anti-clockwise rotation in radians.
+    pub rotation: Scalar,
+    /// The scale along the *x* and *y* axes.
+    pub scale: [Scalar; 2],
+    /// The translation applied after rotating and scaling.
+    pub translation: Point,
+    /// The point about which the rotation and scale are applied, relative to the centre of the
+    /// widget's **Rect**.
+    pub pivot: Point,
+}
+
+
+impl Transform {
+
+    /// The **Transform** that leaves a widget unchanged.
+    pub fn identity() -> Self {
+        Transform {
+            rotation: 0.0,
+            scale: [1.0, 1.0],
+            translation: [0.0, 0.0],
+            pivot: [0.0, 0.0],
+        }
+    }
+
+    /// A **Transform** rotating the widget anti-clockwise by the given angle in radians.
+    pub fn rotation(radians: Scalar) -> Self {
+        Transform::identity().rotate(radians)
+    }
+
+    /// A **Transform** scaling the widget uniformly by the given factor.
+    pub fn scale(scale: Scalar) -> Self {
+        Transform::identity().scale_xy(scale, scale)
+    }
+
+    /// A **Transform** translating the widget by the given amount.
+    pub fn translation(xy: Point) -> Self {
+        Transform::identity().translate(xy)
+    }
+
+    /// Set the anti-clockwise rotation in radians.
+    pub fn rotate(mut self, radians: Scalar) -> Self {
+        self.rotation = radians;
+        self
+    }
+
+    /// Set the scale along each axis.
+    pub fn scale_xy(mut self, x: Scalar, y: Scalar) -> Self {
+        self.scale = [x, y];
+        self
+    }
+
+    /// Set the translation applied after rotating and scaling.
+    pub fn translate(mut self, xy: Point) -> Self {
+        self.translation = xy;
+        self
+    }
+
+    /// Set the point about which the rotation and scale are applied, relative to the centre of
+    /// the widget's **Rect**.
+    pub fn pivot(mut self, pivot: Point) -> Self {
+        self.pivot = pivot;
+        self
+    }
+
+    /// The matrix describing the **Transform** of a widget whose **Rect** is centred at the given
+    /// position.
+    pub fn matrix(&self, centre: Point) -> Matrix2d {
+        let pivot = [centre[0] + self.pivot[0], centre[1] + self.pivot[1]];
+        let to_pivot = math::translate([pivot[0] + self.translation[0],
+                                        pivot[1] + self.translation[1]]);
+        let rotate = math::rotate_radians(self.rotation);
+        let scale = math::scale(self.scale[0], self.scale[1]);
+        let from_pivot = math::translate([-pivot[0], -pivot[1]]);
+        multiply(multiply(multiply(to_pivot, rotate), scale), from_pivot)
+    }
+
+}
+
+
+/// The matrix that leaves all points unchanged.
+pub fn identity() -> Matrix2d {
+    math::identity()
+}
+
+/// Whether or not the given matrix leaves all points unchanged.
+pub fn is_identity(m: Matrix2d) -> bool {
+    m == identity()
+}
+
+/// Whether or not the given matrix maps axis-aligned rectangles to axis-aligned rectangles,
+/// i.e. it describes no rotation or shear.
+pub fn is_axis_aligned(m: Matrix2d) -> bool {
+    m[0][1] == 0.0 && m[1][0] == 0.0
+}
+
+/// The matrix that applies `b` followed by `a`.
+pub fn multiply(a: Matrix2d, b: Matrix2d) -> Matrix2d {
+    math::multiply(a, b)
+}
+
+/// Map the given point using the given matrix.
+pub fn transform_point(m: Matrix2d, p: Point) -> Point {
+    math::transform_pos(m, p)
+}
+
+/// Map the given vector (e.g. a scroll or drag delta) using only the rotation, scale and shear of
+/// the given matrix, ignoring its translation.
+pub fn transform_vector(m: Matrix2d, v: Point) -> Point {
+    math::transform_vec(m, v)
+}
+
+/// The greatest factor by which the given matrix stretches any vector.
+///
+/// This is `1.0` for the identity matrix or any rotation.
+pub fn max_scale(m: Matrix2d) -> Scalar {
+    let (a, b, c, d) = (m[0][0], m[0][1], m[1][0], m[1][1]);
+    let sum_sq = a * a + b * b + c * c + d * d;
+    let det = a * d - b * c;
+    ((sum_sq + (sum_sq * sum_sq - 4.0 * det * det).max(0.0).sqrt()) / 2.0).sqrt()
+}
+
+/// The inverse of the given matrix, or `None` if the matrix collapses space onto a line or point
+/// (e.g. a scale of zero).
+pub fn invert(m: Matrix2d) -> Option<Matrix2d> {
+    let det = m[0][0] * m[1][1] - m[0][1] * m[1][0];
+    if det == 0.0 {
+        return None;
+    }
+    let (a, b, c, d) = (m[1][1] / det, -m[0][1] / det, -m[1][0] / det, m[0][0] / det);
+    Some([
+        [a, b, -(a * m[0][2] + b * m[1][2])],
+        [c, d, -(c * m[0][2] + d * m[1][2])],
+    ])
+}
+
+/// The corners of the given **Rect** mapped by the given matrix, in the order bottom-left,
+/// top-left, top-right, bottom-right.
+pub fn transform_rect(m: Matrix2d, rect: Rect) -> [Point; 4] {
+    let (l, r, b, t) = rect.l_r_b_t();
+    [
+        transform_point(m, [l, b]),
+        transform_point(m, [l, t]),
+        transform_point(m, [r, t]),
+        transform_point(m, [r, b]),
+    ]
+}
+
+/// The axis-aligned **Rect** bounding the given **Rect** once mapped by the given matrix.
+pub fn bounding_rect(m: Matrix2d, rect: Rect) -> Rect {
+    let corners = transform_rect(m, rect);
+    corners[1..].iter().fold(Rect::from_corners(corners[0], corners[0]), |rect, &p| {
+        rect.stretch_to_point(p)
+    })
+}
@@ -0,0 +1,849 @@
+//! Conrod's backend-agnostic rendering API.
+//!
+//! The **Primitives** iterator yields a stream of simple graphical **Primitive**s (rectangles,
+//! polygons, lines, shaded triangles, text, images, scissor and transform changes) in the order in
+//! which they should be drawn. This allows users to render a **Ui** using any graphics backend
+//! without having to implement the piston **Graphics** trait.
+//!
+//! All geometry is described using conrod's coordinate system, where the origin is at the centre
+//! of the window and the *y* axis points upwards.
+
+
+use {Color, FillGradient, FontSize, Point, Rect, Scalar};
+use backend::graphics::ImageSize;
+use color::{self, GradientKind};
+use graph::{self, Container, Graph, NodeIndex};
+use position::transform::{self, Matrix2d};
+use std::any::Any;
+use std::collections::VecDeque;
+use theme::Theme;
+use widget::{self, primitive};
+use widget::primitive::line::{Cap, Dash, Join, Pattern};
+
+
+/// A single graphical primitive that may be drawn by some renderer.
+#[derive(Clone, Debug)]
+pub enum Primitive<'a, T: 'a> {
+    /// A filled rectangle.
+    Rectangle {
+        /// The color with which the rectangle is filled.
+        color: Color,
+        /// The location and dimensions of the rectangle.
+        rect: Rect,
+    },
+    /// A filled, convex polygon described by its vertices.
+    Polygon {
+        /// The color with which the polygon is filled.
+        color: Color,
+        /// The vertices of the polygon.
+        points: Vec<Point>,
+    },
+    /// A series of connected line segments, stroked as a single path.
+    Lines {
+        /// The color of the lines.
+        color: Color,
+        /// The pattern with which the lines should be drawn.
+        pattern: Pattern,
+        /// The lengths describing a **Dashed** or **Dotted** pattern.
+        ///
+        /// See [**line::pattern_paths**](../widget/primitive/line/fn.pattern_paths.html).
+        dash: Dash,
+        /// The full thickness of the lines.
+        thickness: Scalar,
+        /// The style with which the ends of the path are drawn.
+        cap: Cap,
+        /// The style with which the corners between segments are drawn.
+        join: Join,
+        /// The points joined by the lines.
+        points: Vec<Point>,
+    },
+    /// A list of triangles, each of whose vertices has its own color.
+    ///
+    /// The colors should be interpolated across the area of each triangle. Renderers that are
+    /// unable to do so may fill each triangle with the average of its vertex colors.
+    Triangles {
+        /// The position and color of each vertex of each triangle.
+        triangles: Vec<[(Point, Color); 3]>,
+    },
+    /// A single line of text.
+    Text {
+        /// The color of the text.
+        color: Color,
+        /// The font size of the text.
+        font_size: FontSize,
+        /// The string slice to be drawn.
+        text: &'a str,
+        /// The bounding **Rect** of the line of text.
+        rect: Rect,
+    },
+    /// An image described by some backend texture.
+    Image {
+        /// The texture from which the image is sampled.
+        texture: &'a T,
+        /// An optional color with which the image is multiplied.
+        color: Option<Color>,
+        /// The area of the texture that should be drawn, in pixel coordinates.
+        src_rect: Rect,
+        /// The location and dimensions of the image.
+        rect: Rect,
+    },
+    /// All following primitives should be cropped to the given **Rect**.
+    ///
+    /// The **Rect** may have zero area, in which case nothing should be drawn until the next
+    /// **Scissor** is yielded.
+    ///
+    /// The **Rect** is always described in window coordinates and is unaffected by any
+    /// **Transform**.
+    Scissor(Rect),
+    /// All following primitives should be mapped by the given matrix before being drawn, until the
+    /// next **Transform** is yielded.
+    ///
+    /// Widgets are drawn without any transform until the first **Transform** is yielded.
+    Transform(Matrix2d),
+}
+
+
+/// An iterator yielding every **Primitive** necessary for rendering a **Graph** in depth order.
+pub struct Primitives<'a, T: 'a> {
+    graph: &'a Graph,
+    depth_order: ::std::slice::Iter<'a, NodeIndex>,
+    theme: &'a Theme,
+    /// The rect of the window widget, used for filtering out invisible widgets.
+    window_rect: Rect,
+    /// The scissor rect most recently yielded.
+    scissor: Rect,
+    /// The transform matrix most recently yielded.
+    transform: Matrix2d,
+    /// A stack of scissor rects, one for each widget that crops its kids.
+    ///
+    /// FIXME: This allocation every time the iterator is created is unnecessary. We should re-use
+    /// a buffer (perhaps owned by the Ui) for this.
+    crop_stack: Vec<(NodeIndex, Rect)>,
+    /// Primitives that have been produced but not yet yielded.
+    buffer: VecDeque<Primitive<'a, T>>,
+    /// The widget around which a focus ring should be drawn, if any.
+    maybe_focused: Option<NodeIndex>,
+    /// The focus ring of the focused widget along with the scissor rect and transform with which it
+    /// should be drawn. It is yielded once all of the widget's depth-wise children have been drawn.
+    pending_focus_ring: Option<(NodeIndex, Rect, Matrix2d, Primitive<'a, T>)>,
+}
+
+
+/// Produce an iterator yielding all **Primitive**s for the given **Graph** in depth order.
+pub fn primitives<'a, T>(graph: &'a Graph,
+                         depth_order: &'a [NodeIndex],
+                         theme: &'a Theme) -> Primitives<'a, T>
+    where T: ImageSize + Any,
+{
+    primitives_with_focus(graph, depth_order, theme, None)
+}
+
+
+/// Produce an iterator yielding all **Primitive**s for the given **Graph** in depth order.
+///
+/// If some `maybe_focused` widget is given, the `Theme`'s focus ring is drawn around it.
+pub fn primitives_with_focus<'a, T>(graph: &'a Graph,
+                                    depth_order: &'a [NodeIndex],
+                                    theme: &'a Theme,
+                                    maybe_focused: Option<NodeIndex>) -> Primitives<'a, T>
+    where T: ImageSize + Any,
+{
+    // Retrieve the core window widget so that we can use it to filter visible widgets. If we
+    // don't yet have the window widget, we won't have *any* widgets.
+    let window_idx = NodeIndex::new(0);
+    let (window_rect, depth_order) = match graph.widget(window_idx) {
+        Some(window) => (window.rect, depth_order),
+        None => (Rect::from_xy_dim([0.0, 0.0], [0.0, 0.0]), &depth_order[..0]),
+    };
+
+    Primitives {
+        graph: graph,
+        depth_order: depth_order.iter(),
+        theme: theme,
+        window_rect: window_rect,
+        scissor: window_rect,
+        transform: transform::identity(),
+        crop_stack: Vec::new(),
+        buffer: VecDeque::new(),
+        maybe_focused: maybe_focused,
+        pending_focus_ring: None,
+    }
+}
+
+
+impl<'a, T> Primitives<'a, T>
+    where T: ImageSize + Any,
+{
+
+    /// Produce the primitives for the next widget in the depth order and store them in the buffer.
+    ///
+    /// Returns `false` if there are no more widgets.
+    fn next_widget(&mut self) -> bool {
+        let Primitives {
+            graph,
+            ref mut depth_order,
+            theme,
+            window_rect,
+            ref mut scissor,
+            transform: ref mut current_transform,
+            ref mut crop_stack,
+            ref mut buffer,
+            maybe_focused,
+            ref mut pending_focus_ring,
+        } = *self;
+
+        let idx = match depth_order.next() {
+            Some(&idx) => idx,
+            // If the focused widget was among the last to be drawn, its ring is drawn last.
+            None => return match pending_focus_ring.take() {
+                Some(ring) => {
+                    push_focus_ring(ring, scissor, current_transform, buffer);
+                    true
+                },
+                None => false,
+            },
+        };
+
+        // Once we reach a widget that is not a depth-wise child of the focused widget, the focused
+        // widget and all of its children have been drawn, so we can draw the focus ring above them.
+        let is_focus_ring_due = pending_focus_ring.as_ref()
+            .map(|&(focused_idx, _, _, _)| !graph.does_recursive_depth_edge_exist(focused_idx, idx))
+            .unwrap_or(false);
+        if is_focus_ring_due {
+            if let Some(ring) = pending_focus_ring.take() {
+                push_focus_ring(ring, scissor, current_transform, buffer);
+            }
+        }
+
+        if let Some(container) = graph.widget(idx) {
+
+            // If we're currently cropping and the current `crop_parent_idx` is *not* a depth-wise
+            // parent of the widget at the current `idx`, we should pop that crop rect from the
+            // stack as we are done with it.
+            while let Some(&(crop_parent_idx, _)) = crop_stack.last() {
+                if graph.does_recursive_depth_edge_exist(crop_parent_idx, idx) {
+                    break;
+                } else {
+                    crop_stack.pop();
+                }
+            }
+
+            // Check the stack for the current scissor rect.
+            let crop_rect = crop_stack.last().map(|&(_, rect)| rect).unwrap_or(window_rect);
+
+            // The transform and opacity of the widget along with all of its depth-wise parents.
+            let matrix = graph::algo::transform_of_widget(graph, idx);
+            let opacity = graph::algo::opacity_of_widget(graph, idx);
+
+            // Only produce primitives for the widget if it would actually be visible.
+            //
+            // TODO: Refactor this into a `visible_area_of_widget` graph algo. Also, consider
+            // calculating the visible area during the `set_widgets` stage, as it might be more
+            // optimal than doing so here.
+            //
+            // A widget's shadow may reach into view even when the widget itself does not.
+            let drawn_rect = container.maybe_shadow
+                .map(|shadow| container.rect.max(shadow.bounding_rect(container.rect)))
+                .unwrap_or(container.rect);
+            let is_visible = transform::bounding_rect(matrix, drawn_rect)
+                .overlap(window_rect).is_some()
+                && graph::algo::is_rect_within_cropped_area(graph, idx, drawn_rect);
+            if is_visible {
+                if crop_rect != *scissor {
+                    *scissor = crop_rect;
+                    buffer.push_back(Primitive::Scissor(crop_rect));
+                }
+                if matrix != *current_transform {
+                    *current_transform = matrix;
+                    buffer.push_back(Primitive::Transform(matrix));
+                }
+                let start = buffer.len();
+                let scale = transform::max_scale(matrix);
+                push_container_primitives_with_scale(container, theme, scale, buffer);
+                if opacity < 1.0 {
+                    for primitive in buffer.iter_mut().skip(start) {
+                        primitive.multiply_opacity(opacity);
+                    }
+                }
+                if maybe_focused == Some(idx) {
+                    if let Some(ring) = theme.focus_ring {
+                        let (l, r, b, t) = container.rect.pad(-ring.padding).l_r_b_t();
+                        let points = vec![[l, b], [l, t], [r, t], [r, b], [l, b]];
+                        let mut primitive = lines(theme, ring.line_style, points);
+                        primitive.multiply_opacity(opacity);
+                        *pending_focus_ring = Some((idx, crop_rect, matrix, primitive));
+                    }
+                }
+            }
+
+            // If the current widget should crop its children, add its kid area to the stack.
+            //
+            // Scissor rects are axis-aligned, so a transformed kid area is cropped to its bounds.
+            if container.crop_kids {
+                let kid_area = transform::bounding_rect(matrix, container.kid_area.rect);
+                let rect = crop_rect.overlap(kid_area)
+                    .unwrap_or_else(|| Rect::from_xy_dim(crop_rect.xy(), [0.0, 0.0]));
+                crop_stack.push((idx, rect));
+            }
+        }
+
+        true
+    }
+
+}
+
+
+impl<'a, T> Iterator for Primitives<'a, T>
+    where T: ImageSize + Any,
+{
+    type Item = Primitive<'a, T>;
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(primitive) = self.buffer.pop_front() {
+                return Some(primitive);
+            }
+            if !self.next_widget() {
+                return None;
+            }
+        }
+    }
+}
+
+
+impl<'a, T> Primitive<'a, T> {
+
+    /// Multiply the alpha of every color used by the **Primitive** by the given opacity.
+    ///
+    /// Un-tinted **Image**s are given a white tint so that they may be faded.
+    fn multiply_opacity(&mut self, opacity: f32) {
+        match *self {
+            Primitive::Rectangle { ref mut color, .. } |
+            Primitive::Polygon { ref mut color, .. } |
+            Primitive::Lines { ref mut color, .. } |
+            Primitive::Text { ref mut color, .. } => *color = color.alpha(opacity),
+            Primitive::Triangles { ref mut triangles } => {
+                for triangle in triangles.iter_mut() {
+                    for vertex in triangle.iter_mut() {
+                        vertex.1 = vertex.1.alpha(opacity);
+                    }
+                }
+            },
+            Primitive::Image { ref mut color, .. } => {
+                *color = Some(color.unwrap_or(color::WHITE).alpha(opacity));
+            },
+            Primitive::Scissor(_) | Primitive::Transform(_) => (),
+        }
+    }
+
+}
+
+
+/// Push the given focus ring onto the back of the buffer, preceded by any change in scissor rect
+/// or transform necessary for drawing it.
+fn push_focus_ring<'a, T>(ring: (NodeIndex, Rect, Matrix2d, Primitive<'a, T>),
+                          scissor: &mut Rect,
+                          transform: &mut Matrix2d,
+                          buffer: &mut VecDeque<Primitive<'a, T>>)
+{
+    let (_, crop_rect, matrix, primitive) = ring;
+    if crop_rect != *scissor {
+        *scissor = crop_rect;
+        buffer.push_back(Primitive::Scissor(crop_rect));
+    }
+    if matrix != *transform {
+        *transform = matrix;
+        buffer.push_back(Primitive::Transform(matrix));
+    }
+    buffer.push_back(primitive);
+}
+
+
+/// Push all **Primitive**s necessary for drawing the widget within the given **Container** onto
+/// the back of the given buffer.
+///
+/// `T` is the backend texture type used by any **Image** widgets.
+pub fn push_container_primitives<'a, T>(container: &'a Container,
+                                        theme: &Theme,
+                                        buffer: &mut VecDeque<Primitive<'a, T>>)
+    where T: ImageSize + Any,
+{
+    push_container_primitives_with_scale(container, theme, 1.0, buffer)
+}
+
+
+/// The same as **push_container_primitives**, but for a widget whose transform enlarges it by up
+/// to the given `scale`.
+///
+/// Curves flattened while drawing (e.g. those of an **Arc**) are flattened to within
+/// `FLATTENING_TOLERANCE / scale` so that they remain smooth once enlarged.
+pub fn push_container_primitives_with_scale<'a, T>(container: &'a Container,
+                                                   theme: &Theme,
+                                                   scale: Scalar,
+                                                   buffer: &mut VecDeque<Primitive<'a, T>>)
+    where T: ImageSize + Any,
+{
+    use widget::primitive::shape::Style as ShapeStyle;
+    use widget::primitive::shape::FLATTENING_TOLERANCE;
+
+    let tolerance = if scale > 0.0 { FLATTENING_TOLERANCE / scale } else { FLATTENING_TOLERANCE };
+
+    // Shadows are drawn beneath everything else belonging to the widget.
+    if let Some(shadow) = container.maybe_shadow {
+        push_shadow_primitives(container.rect, shadow, buffer);
+    }
+
+    match container.kind {
+
+        primitive::shape::rectangle::KIND => {
+            if let Some(rectangle) = container.unique_widget_state::<::Rectangle>() {
+                match rectangle.style {
+                    ShapeStyle::Fill(_) => {
+                        let color = rectangle.style.get_color(theme);
+                        let rect = container.rect;
+                        buffer.push_back(Primitive::Rectangle { color: color, rect: rect });
+                    },
+                    ShapeStyle::Gradient(ref gradient) => {
+                        let triangles = rect_triangles(container.rect);
+                        buffer.push_back(gradient_fill(triangles, container.rect, gradient));
+                    },
+                    ShapeStyle::Outline(line_style) => {
+                        let (l, r, b, t) = container.rect.l_r_b_t();
+                        let points = vec![[l, b], [l, t], [r, t], [r, b], [l, b]];
+                        buffer.push_back(lines(theme, line_style, points));
+                    },
+                }
+            }
+        },
+
+        primitive::shape::framed_rectangle::KIND => {
+            if let Some(framed_rectangle) = container.unique_widget_state::<::FramedRectangle>() {
+                use widget::primitive::shape::rounded_rectangle::{self, Radii};
+
+                let frame = framed_rectangle.style.frame(theme);
+                let corner_radius = framed_rectangle.style.corner_radius(theme);
+                let frame_color = framed_rectangle.style.frame_color(theme);
+                let frame_rect = container.rect;
+                let color = framed_rectangle.style.color(theme);
+                let maybe_gradient = framed_rectangle.style.maybe_gradient(theme);
+                let rect = container.rect.pad(frame);
+                if corner_radius > 0.0 {
+                    let radii = Radii::all(corner_radius);
+                    if frame > 0.0 {
+                        let points = rounded_rectangle::points(frame_rect, radii);
+                        buffer.push_back(Primitive::Polygon { color: frame_color, points: points });
+                    }
+                    let points = rounded_rectangle::points(rect, radii.pad(frame));
+                    match maybe_gradient {
+                        Some(ref gradient) =>
+                            buffer.push_back(gradient_fill(convex_fan(&points), rect, gradient)),
+                        None =>
+                            buffer.push_back(Primitive::Polygon { color: color, points: points }),
+                    }
+                } else {
+                    if frame > 0.0 {
+                        buffer.push_back(Primitive::Rectangle {
+                            color: frame_color,
+                            rect: frame_rect,
+                        });
+                    }
+                    match maybe_gradient {
+                        Some(ref gradient) => {
+                            let triangles = rect_triangles(rect);
+                            buffer.push_back(gradient_fill(triangles, rect, gradient));
+                        },
+                        None => buffer.push_back(Primitive::Rectangle { color: color, rect: rect }),
+                    }
+                }
+            }
+        },
+
+        primitive::shape::rounded_rectangle::KIND => {
+            use widget::primitive::shape::rounded_rectangle;
+            if let Some(rounded) = container.unique_widget_state::<::RoundedRectangle>() {
+                let mut points = rounded_rectangle::points(container.rect, rounded.state.radii);
+                match rounded.style {
+                    ShapeStyle::Fill(_) => {
+                        let color = rounded.style.get_color(theme);
+                        buffer.push_back(Primitive::Polygon { color: color, points: points });
+                    },
+                    ShapeStyle::Gradient(ref gradient) => {
+                        let triangles = convex_fan(&points);
+                        buffer.push_back(gradient_fill(triangles, container.rect, gradient));
+                    },
+                    ShapeStyle::Outline(line_style) => {
+                        let first = points[0];
+                        points.push(first);
+                        buffer.push_back(lines(theme, line_style, points));
+                    },
+                }
+            }
+        },
+
+        primitive::shape::oval::KIND => {
+            if let Some(oval) = container.unique_widget_state::<::Oval>() {
+                let points = primitive::shape::oval::circumference(container.rect);
+
+                match oval.style {
+                    ShapeStyle::Fill(_) => {
+                        let color = oval.style.get_color(theme);
+                        buffer.push_back(Primitive::Polygon { color: color, points: points });
+                    },
+                    ShapeStyle::Gradient(ref gradient) => {
+                        let triangles = convex_fan(&points);
+                        buffer.push_back(gradient_fill(triangles, container.rect, gradient));
+                    },
+                    ShapeStyle::Outline(line_style) => {
+                        buffer.push_back(lines(theme, line_style, points));
+                    },
+                }
+            }
+        },
+
+        primitive::shape::arc::KIND => {
+            if let Some(arc) = container.unique_widget_state::<::Arc>() {
+                let (x, y, w, h) = container.rect.x_y_w_h();
+                let radius = w.min(h) / 2.0;
+                let section = arc.state.section;
+                match arc.style {
+                    ShapeStyle::Fill(_) => {
+                        let color = arc.style.get_color(theme);
+                        let triangles = section.triangles_within([x, y], radius, tolerance);
+                        buffer.push_back(solid_fill(triangles, color));
+                    },
+                    ShapeStyle::Gradient(ref gradient) => {
+                        let triangles = section.triangles_within([x, y], radius, tolerance);
+                        buffer.push_back(gradient_fill(triangles, container.rect, gradient));
+                    },
+                    ShapeStyle::Outline(line_style) => {
+                        for points in section.outlines_within([x, y], radius, tolerance) {
+                            buffer.push_back(lines(theme, line_style, points));
+                        }
+                    },
+                }
+            }
+        },
+
+        primitive::shape::bezier::KIND => {
+            if let Some(bezier) = container.unique_widget_state::<::Bezier>() {
+                let triangles = bezier.state.triangles.clone();
+                match bezier.style {
+                    ShapeStyle::Fill(_) => {
+                        let color = bezier.style.get_color(theme);
+                        buffer.push_back(solid_fill(triangles, color));
+                    },
+                    ShapeStyle::Gradient(ref gradient) => {
+                        buffer.push_back(gradient_fill(triangles, container.rect, gradient));
+                    },
+                    ShapeStyle::Outline(line_style) => {
+                        let points = bezier.state.points.clone();
+                        buffer.push_back(lines(theme, line_style, points));
+                    },
+                }
+            }
+        },
+
+        primitive::shape::polygon::KIND => {
+            use widget::primitive::shape::Style;
+            use widget::primitive::shape::polygon::State;
+
+            if let Some(polygon) = container.state_and_style::<State, Style>() {
+                match polygon.style {
+                    ShapeStyle::Fill(_) => {
+                        let color = polygon.style.get_color(theme);
+                        let triangles = polygon.state.triangles.clone();
+                        buffer.push_back(solid_fill(triangles, color));
+                    },
+                    ShapeStyle::Gradient(ref gradient) => {
+                        let triangles = polygon.state.triangles.clone();
+                        buffer.push_back(gradient_fill(triangles, container.rect, gradient));
+                    },
+                    ShapeStyle::Outline(line_style) => {
+                        let mut points = polygon.state.points.iter().cloned();
+                        let first = points.next();
+                        let points = first.into_iter().chain(points).chain(first).collect();
+                        buffer.push_back(lines(theme, line_style, points));
+                    },
+                }
+            }
+        },
+
+        primitive::line::KIND => {
+            if let Some(line) = container.unique_widget_state::<::Line>() {
+                let points = vec![line.state.start, line.state.end];
+                buffer.push_back(lines(theme, line.style, points));
+            }
+        },
+
+        primitive::point_path::KIND => {
+            use widget::primitive::point_path::{State, Style};
+            if let Some(point_path) = container.state_and_style::<State, Style>() {
+                let points = point_path.state.points.clone();
+                buffer.push_back(lines(theme, point_path.style, points));
+            }
+        },
+
+        primitive::text::KIND => {
+            if let Some(text) = container.unique_widget_state::<::Text>() {
+                use {Align, text};
+
+                let graph::UniqueWidgetState { ref state, ref style } = *text;
+
+                let font_size = style.font_size(theme);
+                let line_spacing = style.line_spacing(theme);
+                let color = style.color(theme);
+                let x_align = style.text_align(theme);
+                let y_align = Align::End; // Always align text to top of Text's Rect.
+                let rect = container.rect;
+                let line_infos = state.line_infos.iter().cloned();
+                let string = &state.string;
+
+                let lines = line_infos.clone().map(|info| &string[info.byte_range()]);
+                let line_rects =
+                    text::line::rects(line_infos, font_size, rect, x_align, y_align, line_spacing);
+
+                for (line, line_rect) in lines.zip(line_rects) {
+                    buffer.push_back(Primitive::Text {
+                        color: color,
+                        font_size: font_size,
+                        text: line,
+                        rect: line_rect,
+                    });
+                }
+            }
+        },
+
+        primitive::image::KIND => {
+            use widget::primitive::image::{State, Style};
+            if let Some(image) = container.state_and_style::<State<T>, Style>() {
+                let graph::UniqueWidgetState { ref state, ref style } = *image;
+                if let Some(texture) = state.texture.as_ref() {
+                    let color = style.maybe_color.and_then(|c| c);
+                    let slices = match state.nine_slice {
+                        Some(nine_slice) => nine_slice.slices(texture.src_rect, container.rect),
+                        None => state.fit.slices(texture.src_rect, container.rect),
+                    };
+                    for (src_rect, rect) in slices {
+                        buffer.push_back(Primitive::Image {
+                            texture: texture.arc.as_ref(),
+                            color: color,
+                            src_rect: src_rect,
+                            rect: rect,
+                        });
+                    }
+                }
+            }
+        },
+
+        _ => (),
+    }
+}
+
+
+/// The number of translucent layers used to approximate the blurred edge of a **Shadow**.
+const SHADOW_LAYERS: usize = 8;
+
+/// Push the **Primitive**s describing the given shadow of the given **Rect** onto the buffer.
+///
+/// The blurred edge is approximated by layering increasingly small translucent rounded
+/// rectangles. The alpha of each layer is chosen so that the accumulated opacity falls linearly
+/// from the shadow's color at the inner edge of the blur to nothing at its outer edge.
+fn push_shadow_primitives<'a, T>(rect: Rect,
+                                 shadow: widget::Shadow,
+                                 buffer: &mut VecDeque<Primitive<'a, T>>)
+{
+    use widget::primitive::shape::rounded_rectangle::{self, Radii};
+
+    let rect = rect.shift(shadow.offset);
+    let blur = shadow.blur.max(0.0);
+    let alpha = shadow.color.to_fsa()[3];
+    let num_layers = if blur > 0.0 { SHADOW_LAYERS } else { 1 };
+    // The accumulated opacity wherever the given number of layers overlap.
+    let opacity = |n: usize| alpha * n as f32 / num_layers as f32;
+    for i in 0..num_layers {
+        // The distance by which the layer extends beyond the rect.
+        let extent = blur / 2.0 - blur * i as Scalar / num_layers as Scalar;
+        let layer_rect = rect.pad(-extent);
+        if layer_rect.w() <= 0.0 || layer_rect.h() <= 0.0 {
+            break;
+        }
+        let layer_alpha = 1.0 - (1.0 - opacity(i + 1)) / (1.0 - opacity(i));
+        let color = shadow.color.alpha(layer_alpha);
+        // Round each layer as though it were offset from the fully opaque area of the shadow.
+        let radii = Radii::all(extent + blur / 2.0);
+        let points = rounded_rectangle::points(layer_rect, radii);
+        buffer.push_back(Primitive::Polygon { color: color, points: points });
+    }
+}
+
+
+/// Produce a **Primitive::Lines** from the given line style and points.
+fn lines<'a, T>(theme: &Theme,
+                style: primitive::line::Style,
+                points: Vec<Point>) -> Primitive<'a, T>
+{
+    Primitive::Lines {
+        color: style.get_color(theme),
+        pattern: style.get_pattern(theme),
+        dash: style.get_dash(theme),
+        thickness: style.get_thickness(theme),
+        cap: style.get_cap(theme),
+        join: style.get_join(theme),
+        points: points,
+    }
+}
+
+
+/// The average of the vertex colors of the given triangle.
+///
+/// Useful for renderers that are unable to interpolate the vertex colors of a
+/// **Primitive::Triangles**.
+pub fn average_color(triangle: &[(Point, Color); 3]) -> Color {
+    let (a, b, c) = (triangle[0].1.to_fsa(), triangle[1].1.to_fsa(), triangle[2].1.to_fsa());
+    let avg = |i: usize| (a[i] + b[i] + c[i]) / 3.0;
+    ::color::rgba(avg(0), avg(1), avg(2), avg(3))
+}
+
+
+/// The number of bands into which the range `0.0...1.0` of a linear gradient is divided, in
+/// addition to the bands between color stops.
+///
+/// This keeps the banding subtle for renderers that fill each triangle with a single color.
+const LINEAR_GRADIENT_RESOLUTION: usize = 32;
+
+/// Radial gradients are subdivided until no triangle edge is longer than the gradient's radius
+/// divided by this amount.
+const RADIAL_GRADIENT_RESOLUTION: Scalar = 8.0;
+
+/// Produce a **Primitive::Triangles** filling the given triangles with the given gradient.
+///
+/// `rect` is the bounding rectangle relative to which the gradient is described.
+fn gradient_fill<'a, T>(triangles: Vec<[Point; 3]>,
+                        rect: Rect,
+                        gradient: &FillGradient) -> Primitive<'a, T>
+{
+    Primitive::Triangles { triangles: gradient_triangles(triangles, rect, gradient) }
+}
+
+/// Produce a **Primitive::Triangles** filling the given triangles with a single color.
+fn solid_fill<'a, T>(triangles: Vec<[Point; 3]>, color: Color) -> Primitive<'a, T> {
+    let triangles = triangles.into_iter()
+        .map(|t| [(t[0], color), (t[1], color), (t[2], color)])
+        .collect();
+    Primitive::Triangles { triangles: triangles }
+}
+
+/// The two triangles filling the given **Rect**.
+fn rect_triangles(rect: Rect) -> Vec<[Point; 3]> {
+    let (l, r, b, t) = rect.l_r_b_t();
+    vec![[[l, b], [l, t], [r, t]], [[l, b], [r, t], [r, b]]]
+}
+
+/// Triangulate the convex polygon described by the given points by fanning from their average.
+fn convex_fan(points: &[Point]) -> Vec<[Point; 3]> {
+    if points.len() < 3 {
+        return Vec::new();
+    }
+    let n = points.len() as Scalar;
+    let centroid = points.iter().fold([0.0, 0.0], |c, p| [c[0] + p[0] / n, c[1] + p[1] / n]);
+    (0..points.len()).map(|i| [centroid, points[i], points[(i + 1) % points.len()]]).collect()
+}
+
+/// Shade the given triangles with vertex colors describing the given gradient.
+///
+/// Linear gradients are sliced at each color stop so that interpolating the vertex colors
+/// reproduces the gradient exactly, while radial gradients are subdivided until their edges are
+/// short relative to the gradient's radius.
+fn gradient_triangles(triangles: Vec<[Point; 3]>, rect: Rect, gradient: &FillGradient)
+    -> Vec<[(Point, Color); 3]>
+{
+    let mut shaded = Vec::new();
+    let (x, y, w, h) = rect.x_y_w_h();
+    match gradient.kind {
+
+        GradientKind::Linear { angle } => {
+            let (sin, cos) = angle.sin_cos();
+            let len = (w * cos).abs() + (h * sin).abs();
+            let position = |p: Point| if len > 0.0 {
+                ((p[0] - x) * cos + (p[1] - y) * sin) / len + 0.5
+            } else {
+                0.0
+            };
+            let mut cuts: Vec<Scalar> = (1..LINEAR_GRADIENT_RESOLUTION)
+                .map(|i| i as Scalar / LINEAR_GRADIENT_RESOLUTION as Scalar)
+                .chain(gradient.stops().iter().map(|&(pos, _)| pos as Scalar))
+                .collect();
+            cuts.sort_by(|a, b| a.partial_cmp(b).unwrap_or(::std::cmp::Ordering::Equal));
+            cuts.dedup();
+
+            let vertex = |p: Point| (p, gradient.color_at(position(p) as f32));
+            for triangle in triangles {
+                // Slice the triangle into convex bands between each consecutive pair of cuts.
+                let mut remaining = triangle.to_vec();
+                for &cut in &cuts {
+                    let (below, above) = split_convex(&remaining, |p| position(p) - cut);
+                    for i in 1..below.len().saturating_sub(1) {
+                        shaded.push([vertex(below[0]), vertex(below[i]), vertex(below[i + 1])]);
+                    }
+                    remaining = above;
+                }
+                for i in 1..remaining.len().saturating_sub(1) {
+                    let (a, b, c) = (remaining[0], remaining[i], remaining[i + 1]);
+                    shaded.push([vertex(a), vertex(b), vertex(c)]);
+                }
+            }
+        },
+
+        GradientKind::Radial { centre, radius } => {
+            let centre = [x + centre[0], y + centre[1]];
+            let position = |p: Point| if radius > 0.0 {
+                (p[0] - centre[0]).hypot(p[1] - centre[1]) / radius
+            } else {
+                1.0
+            };
+            // Limit the subdivision of shapes that are far larger than the gradient itself.
+            let max_edge = (radius / RADIAL_GRADIENT_RESOLUTION)
+                .max(w.hypot(h) / (2.0 * RADIAL_GRADIENT_RESOLUTION));
+            let vertex = |p: Point| (p, gradient.color_at(position(p) as f32));
+
+            let mut stack = triangles;
+            while let Some(tri) = stack.pop() {
+                // Bisect the longest edge until all edges are short enough.
+                let edge_len = |i: usize| {
+                    let (a, b) = (tri[i], tri[(i + 1) % 3]);
+                    (b[0] - a[0]).hypot(b[1] - a[1])
+                };
+                let longest = (0..3).fold(0, |j, i| if edge_len(i) > edge_len(j) { i } else { j });
+                if edge_len(longest) > max_edge {
+                    let (a, b, c) = (tri[longest], tri[(longest + 1) % 3], tri[(longest + 2) % 3]);
+                    let mid = [(a[0] + b[0]) / 2.0, (a[1] + b[1]) / 2.0];
+                    stack.push([a, mid, c]);
+                    stack.push([mid, b, c]);
+                } else {
+                    shaded.push([vertex(tri[0]), vertex(tri[1]), vertex(tri[2])]);
+                }
+            }
+        },
+    }
+
+    shaded
+}
+
+/// Split the convex polygon described by the given points along the line at which `side` is zero,
+/// returning the parts at which `side` is negative and positive respectively.
+fn split_convex<F>(points: &[Point], side: F) -> (Vec<Point>, Vec<Point>)
+    where F: Fn(Point) -> Scalar,
+{
+    let (mut below, mut above) = (Vec::new(), Vec::new());
+    for i in 0..points.len() {
+        let (a, b) = (points[i], points[(i + 1) % points.len()]);
+        let (sa, sb) = (side(a), side(b));
+        if sa < 0.0 { below.push(a) } else { above.push(a) }
+        if (sa < 0.0) != (sb < 0.0) {
+            let t = sa / (sa - sb);
+            let p = [a[0] + (b[0] - a[0]) * t, a[1] + (b[1] - a[1]) * t];
+            below.push(p);
+            above.push(p);
+        }
+    }
+    (below, above)
+}
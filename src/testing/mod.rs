@@ -0,0 +1,278 @@
+//! Tools for unit testing widgets without a window, graphics or font backend.
+//!
+//! This module provides:
+//!
+//! - A **MockBackend** whose **MockCharacterCache** yields fixed-size characters, allowing a **Ui**
+//! to be constructed with [**windowless_ui**](./fn.windowless_ui.html).
+//! - A scripted event driver for moving the mouse, clicking, dragging, typing and scrolling upon
+//! widgets.
+//! - Frame stepping via [**step**](./fn.step.html).
+//! - Assertions upon the events received by the **Ui** and the **Rect**s of widgets.
+//! - Golden-image snapshot testing of rendered **Ui**s via the
+//! [**snapshot**](./snapshot/index.html) module.
+//!
+//! Note that, just like when running a real application, events are only delivered to widgets
+//! during the following call to `Ui::set_widgets` (or **step**). Events are cleared once the
+//! widgets have been set.
+//!
+//! ```
+//! extern crate conrod;
+//!
+//! use conrod::{Button, Labelable, Positionable, Sizeable, Widget, WidgetId};
+//! use conrod::testing;
+//!
+//! fn main() {
+//!     const BUTTON: WidgetId = WidgetId(0);
+//!     let mut ui = testing::windowless_ui([200.0, 200.0]);
+//!     let mut clicks = 0;
+//!
+//!     // Set the widgets once so that the button's position is known.
+//!     testing::step(&mut ui, |ref mut ui| {
+//!         Button::new().w_h(80.0, 40.0).middle().label("Click").react(|| {}).set(BUTTON, ui);
+//!     });
+//!
+//!     // Click the button, then step a frame so that the button may react.
+//!     testing::click_widget(&mut ui, BUTTON);
+//!     testing::step(&mut ui, |ref mut ui| {
+//!         Button::new()
+//!             .w_h(80.0, 40.0)
+//!             .middle()
+//!             .label("Click")
+//!             .react(|| clicks += 1)
+//!             .set(BUTTON, ui);
+//!     });
+//!
+//!     assert_eq!(clicks, 1);
+//!     let expected_rect = conrod::Rect::from_xy_dim([0.0, 0.0], [80.0, 40.0]);
+//!     testing::assert_widget_rect(&ui, BUTTON, expected_rect);
+//! }
+//! ```
+
+use {Backend, CharacterCache, Dimensions, FontSize, Point, Rect, Scalar, Theme, Ui, UiCell};
+use backend::graphics::{Character, ImageSize};
+use event::{self, Input, Motion};
+use input::{Button, Key, MouseButton};
+use widget;
+
+pub mod snapshot;
+
+
+/// A **Backend** that requires no window, graphics or font context.
+#[derive(Copy, Clone, Debug)]
+pub struct MockBackend;
+
+/// A texture type that only stores its dimensions.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct MockImageSize {
+    /// The width of the texture in pixels.
+    pub w: u32,
+    /// The height of the texture in pixels.
+    pub h: u32,
+}
+
+/// A **CharacterCache** that yields the same fixed-size character for every `char`.
+#[derive(Copy, Clone, Debug)]
+pub struct MockCharacterCache {
+    texture: MockImageSize,
+}
+
+/// The dimensions of every character yielded by the **MockCharacterCache::new**.
+pub const DEFAULT_CHARACTER_DIMENSIONS: Dimensions = [14.0, 22.0];
+
+/// The **Ui** type used throughout the testing module.
+pub type MockUi = Ui<MockBackend>;
+
+
+impl Backend for MockBackend {
+    type Texture = MockImageSize;
+    type CharacterCache = MockCharacterCache;
+}
+
+impl ImageSize for MockImageSize {
+    fn get_size(&self) -> (u32, u32) {
+        (self.w, self.h)
+    }
+}
+
+impl MockCharacterCache {
+
+    /// Construct a **MockCharacterCache** yielding characters with the default dimensions.
+    pub fn new() -> Self {
+        let dim = DEFAULT_CHARACTER_DIMENSIONS;
+        MockCharacterCache::with_character_dimensions(dim[0] as u32, dim[1] as u32)
+    }
+
+    /// Construct a **MockCharacterCache** yielding characters with the given dimensions.
+    pub fn with_character_dimensions(w: u32, h: u32) -> Self {
+        MockCharacterCache {
+            texture: MockImageSize { w: w, h: h },
+        }
+    }
+
+}
+
+impl CharacterCache for MockCharacterCache {
+    type Texture = MockImageSize;
+
+    fn character<'a>(&'a mut self,
+                     _font_size: FontSize,
+                     _ch: char) -> Character<'a, MockImageSize>
+    {
+        Character {
+            offset: [0.0, 0.0],
+            size: [self.texture.w as Scalar, self.texture.h as Scalar],
+            texture: &self.texture,
+        }
+    }
+}
+
+
+/// Construct a **Ui** using the **MockBackend** and default **Theme** with a window of the given
+/// dimensions.
+pub fn windowless_ui(window_dim: Dimensions) -> MockUi {
+    windowless_ui_with_character_cache(MockCharacterCache::new(), window_dim)
+}
+
+/// Construct a **Ui** using the given **CharacterCache** and default **Theme** with a window of
+/// the given dimensions.
+pub fn windowless_ui_with_character_cache<B>(character_cache: B::CharacterCache,
+                                             window_dim: Dimensions) -> Ui<B>
+    where B: Backend,
+{
+    let mut ui = Ui::new(character_cache, Theme::default());
+    ui.win_w = window_dim[0];
+    ui.win_h = window_dim[1];
+    ui
+}
+
+
+/// Step a single frame by setting the widgets using the given function.
+///
+/// All events received since the previous frame are delivered to the widgets and then cleared.
+pub fn step<B, F>(ui: &mut Ui<B>, set_widgets: F)
+    where B: Backend,
+          F: FnOnce(UiCell<B>),
+{
+    ui.set_widgets(set_widgets);
+}
+
+
+/// Convert the given **Point** from conrod's coordinate system (origin at the centre, *y* up) to
+/// the window's coordinate system (origin at the top left, *y* down).
+pub fn to_window_coordinates<B: Backend>(ui: &Ui<B>, xy: Point) -> Point {
+    let x = (ui.win_w / 2.0) + xy[0];
+    let y = (ui.win_h / 2.0) - xy[1];
+    [x, y]
+}
+
+
+/// Move the mouse to the given **Point** in conrod's coordinate system.
+pub fn move_mouse_to<B: Backend>(ui: &mut Ui<B>, xy: Point) {
+    let window_xy = to_window_coordinates(ui, xy);
+    ui.handle_event(Input::Move(Motion::MouseCursor(window_xy[0], window_xy[1])));
+}
+
+/// Move the mouse to the centre of the widget with the given index.
+///
+/// If the widget does not yet exist within the **Ui**, the mouse is not moved.
+pub fn move_mouse_to_widget<B, I>(ui: &mut Ui<B>, idx: I)
+    where B: Backend,
+          I: Into<widget::Index>,
+{
+    if let Some(xy) = ui.xy_of(idx) {
+        move_mouse_to(ui, xy);
+    }
+}
+
+/// Press the given mouse button.
+pub fn press_mouse_button<B: Backend>(ui: &mut Ui<B>, button: MouseButton) {
+    ui.handle_event(Input::Press(Button::Mouse(button)));
+}
+
+/// Release the given mouse button.
+pub fn release_mouse_button<B: Backend>(ui: &mut Ui<B>, button: MouseButton) {
+    ui.handle_event(Input::Release(Button::Mouse(button)));
+}
+
+/// Press and release the left mouse button at the current mouse position.
+pub fn left_click_mouse<B: Backend>(ui: &mut Ui<B>) {
+    press_mouse_button(ui, MouseButton::Left);
+    release_mouse_button(ui, MouseButton::Left);
+}
+
+/// Move the mouse to the centre of the widget with the given index and click the left button.
+pub fn click_widget<B, I>(ui: &mut Ui<B>, idx: I)
+    where B: Backend,
+          I: Into<widget::Index>,
+{
+    move_mouse_to_widget(ui, idx);
+    left_click_mouse(ui);
+}
+
+/// Press the left mouse button over the centre of the widget with the given index, drag the mouse
+/// to the given **Point** (in conrod's coordinate system) and release it.
+pub fn drag_widget<B, I>(ui: &mut Ui<B>, idx: I, to: Point)
+    where B: Backend,
+          I: Into<widget::Index>,
+{
+    move_mouse_to_widget(ui, idx);
+    press_mouse_button(ui, MouseButton::Left);
+    move_mouse_to(ui, to);
+    release_mouse_button(ui, MouseButton::Left);
+}
+
+/// Press and release the given key.
+pub fn press_and_release_key<B: Backend>(ui: &mut Ui<B>, key: Key) {
+    ui.handle_event(Input::Press(Button::Keyboard(key)));
+    ui.handle_event(Input::Release(Button::Keyboard(key)));
+}
+
+/// Enter the given text, as though it were typed on the keyboard.
+pub fn type_text<B: Backend>(ui: &mut Ui<B>, text: &str) {
+    ui.handle_event(Input::Text(text.to_string()));
+}
+
+/// Scroll the mouse wheel by the given amount at the current mouse position.
+pub fn scroll<B: Backend>(ui: &mut Ui<B>, x: Scalar, y: Scalar) {
+    ui.handle_event(Input::Move(Motion::MouseScroll(x, y)));
+}
+
+/// Move the mouse to the centre of the widget with the given index and scroll the mouse wheel by
+/// the given amount.
+pub fn scroll_widget<B, I>(ui: &mut Ui<B>, idx: I, x: Scalar, y: Scalar)
+    where B: Backend,
+          I: Into<widget::Index>,
+{
+    move_mouse_to_widget(ui, idx);
+    scroll(ui, x, y);
+}
+
+
+/// Assert that the given event has been received by the **Ui** since the last frame.
+pub fn assert_event_was_pushed<B: Backend>(ui: &Ui<B>, event: event::Event) {
+    let found = ui.global_input.events().find(|evt| **evt == event);
+    assert!(found.is_some(),
+            "expected to find event: {:?} in: \nevents: {:?}",
+            event,
+            ui.global_input.events().collect::<Vec<&event::Event>>());
+}
+
+/// Assert that the given event has *not* been received by the **Ui** since the last frame.
+pub fn assert_event_was_not_pushed<B: Backend>(ui: &Ui<B>, event: event::Event) {
+    let found = ui.global_input.events().find(|evt| **evt == event);
+    assert!(found.is_none(), "expected not to find event: {:?}", event);
+}
+
+/// Assert that the widget with the given index occupies the given **Rect**.
+pub fn assert_widget_rect<B, I>(ui: &Ui<B>, idx: I, expected: Rect)
+    where B: Backend,
+          I: Into<widget::Index>,
+{
+    let idx: widget::Index = idx.into();
+    match ui.rect_of(idx) {
+        Some(rect) => assert!(rect == expected,
+                              "expected {:?} to have rect {:?}, found {:?}",
+                              idx, expected, rect),
+        None => panic!("no widget found for {:?}", idx),
+    }
+}
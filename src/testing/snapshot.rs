@@ -0,0 +1,237 @@
+//! Golden-image snapshot testing of rendered **Ui**s.
+//!
+//! A **Ui** using the software backend is rendered into an **Image** and compared against a
+//! "golden" image stored on disk in the PAM format (see `Image::write_pam`).
+//!
+//! - If the `CONROD_UPDATE_SNAPSHOTS` environment variable is set, the golden image is written
+//! (or overwritten) with the newly rendered image and the assertion passes.
+//! - Otherwise, if the golden image does not exist, the assertion panics. This ensures that a
+//! missing or misnamed golden image cannot silently pass, e.g. on CI.
+//! - Otherwise, if more than the allowed number of pixels differ by more than the given
+//! per-channel tolerance, the rendered image and a diff image are written alongside the golden
+//! image (with the `.actual.pam` and `.diff.pam` extensions) and the assertion panics.
+//!
+//! ```
+//! extern crate conrod;
+//!
+//! use conrod::{Canvas, Colorable, Widget, WidgetId};
+//! use conrod::testing::snapshot;
+//!
+//! fn main() {
+//!     const CANVAS: WidgetId = WidgetId(0);
+//!     let mut ui = snapshot::windowless_ui([64.0, 32.0]);
+//!     ui.set_widgets(|ref mut ui| {
+//!         Canvas::new().color(conrod::color::BLUE).set(CANVAS, ui);
+//!     });
+//!
+//!     // Golden images are usually written by running the tests with `CONROD_UPDATE_SNAPSHOTS`
+//!     // set, but here we write it ourselves.
+//!     let path = std::env::temp_dir().join("conrod_snapshot_doc_example.pam");
+//!     snapshot::render(&ui).save_pam(&path).unwrap();
+//!     snapshot::assert_ui_snapshot(&ui, &path, snapshot::Tolerance::exact());
+//! }
+//! ```
+
+use {CharacterCache, Dimensions, FontSize, Scalar, Ui};
+use backend::graphics::Character;
+use backend::software::{self, Image};
+use std::path::Path;
+
+
+/// The environment variable that, when set, causes all golden images to be overwritten.
+pub const UPDATE_ENV_VAR: &'static str = "CONROD_UPDATE_SNAPSHOTS";
+
+/// A **CharacterCache** rendering every visible character as a solid block and every whitespace
+/// character as empty space.
+///
+/// This allows for deterministic text layout and rendering that does not depend on any fonts.
+#[derive(Clone, Debug)]
+pub struct MockGlyphCache {
+    glyph: Image,
+    space: Image,
+}
+
+/// A **Ui** that may be rendered using the software backend.
+pub type SnapshotUi = Ui<(Image, MockGlyphCache)>;
+
+/// Describes how much a rendered image may differ from its golden image.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Tolerance {
+    /// The maximum amount by which any channel of a pixel may differ before the pixel is
+    /// considered different.
+    pub per_channel: u8,
+    /// The maximum number of differing pixels before the images are considered different.
+    pub max_differing_pixels: usize,
+}
+
+/// The result of comparing two **Image**s.
+#[derive(Clone, Debug)]
+pub struct Diff {
+    /// The number of pixels that differ by more than the tolerance.
+    pub num_differing_pixels: usize,
+    /// An image highlighting differing pixels in opaque red over a faded copy of the expected
+    /// image.
+    pub image: Image,
+}
+
+
+impl MockGlyphCache {
+
+    /// Construct a **MockGlyphCache** with glyphs of the default character dimensions.
+    pub fn new() -> Self {
+        let dim = super::DEFAULT_CHARACTER_DIMENSIONS;
+        MockGlyphCache::with_character_dimensions(dim[0] as u32, dim[1] as u32)
+    }
+
+    /// Construct a **MockGlyphCache** with glyphs of the given dimensions.
+    pub fn with_character_dimensions(w: u32, h: u32) -> Self {
+        let n = w as usize * h as usize;
+        MockGlyphCache {
+            glyph: Image::from_pixels(w, h, vec![[255; 4]; n]),
+            space: Image::new(w, h),
+        }
+    }
+
+}
+
+impl CharacterCache for MockGlyphCache {
+    type Texture = Image;
+
+    fn character<'a>(&'a mut self, _font_size: FontSize, ch: char) -> Character<'a, Image> {
+        let texture = if ch.is_whitespace() { &self.space } else { &self.glyph };
+        Character {
+            offset: [0.0, 0.0],
+            size: [texture.width() as Scalar, texture.height() as Scalar],
+            texture: texture,
+        }
+    }
+}
+
+
+impl Tolerance {
+
+    /// No difference is allowed whatsoever.
+    pub fn exact() -> Self {
+        Tolerance {
+            per_channel: 0,
+            max_differing_pixels: 0,
+        }
+    }
+
+    /// Allow each channel of each pixel to differ by the given amount.
+    pub fn per_channel(per_channel: u8) -> Self {
+        Tolerance {
+            per_channel: per_channel,
+            max_differing_pixels: 0,
+        }
+    }
+
+    /// Allow up to the given number of pixels to differ.
+    pub fn max_differing_pixels(self, max_differing_pixels: usize) -> Self {
+        Tolerance { max_differing_pixels: max_differing_pixels, ..self }
+    }
+
+}
+
+
+/// Construct a **SnapshotUi** using the **MockGlyphCache** and default **Theme** with a window of
+/// the given dimensions.
+pub fn windowless_ui(window_dim: Dimensions) -> SnapshotUi {
+    super::windowless_ui_with_character_cache(MockGlyphCache::new(), window_dim)
+}
+
+
+/// Render the given **Ui** into a new **Image** with one pixel per unit of the window's
+/// dimensions.
+pub fn render<C>(ui: &Ui<(Image, C)>) -> Image
+    where C: CharacterCache<Texture=Image>,
+{
+    let mut image = Image::new(ui.win_w.round() as u32, ui.win_h.round() as u32);
+    software::draw_ui(ui, &mut image);
+    image
+}
+
+
+/// Compare the `actual` image against the `expected` image, considering pixels different if any
+/// of their channels differ by more than `per_channel`.
+///
+/// If the dimensions differ, the diff covers the maximum of both and all pixels that do not exist
+/// in both images are considered different.
+pub fn diff(expected: &Image, actual: &Image, per_channel: u8) -> Diff {
+    let width = ::std::cmp::max(expected.width(), actual.width());
+    let height = ::std::cmp::max(expected.height(), actual.height());
+    let mut image = Image::new(width, height);
+    let mut num_differing_pixels = 0;
+    for y in 0..height {
+        for x in 0..width {
+            let is_different = match (expected.pixel(x, y), actual.pixel(x, y)) {
+                (Some(e), Some(a)) => e.iter().zip(a.iter()).any(|(&e, &a)| {
+                    (e as i16 - a as i16).abs() > per_channel as i16
+                }),
+                _ => true,
+            };
+            let pixel = if is_different {
+                num_differing_pixels += 1;
+                [255, 0, 0, 255]
+            } else {
+                let e = expected.pixel(x, y).unwrap_or([0; 4]);
+                [e[0], e[1], e[2], e[3] / 4]
+            };
+            image.set_pixel(x, y, pixel);
+        }
+    }
+    Diff {
+        num_differing_pixels: num_differing_pixels,
+        image: image,
+    }
+}
+
+
+/// Render the given **Ui** and compare it against the golden image at the given path.
+///
+/// See the [module documentation](./index.html) for details.
+pub fn assert_ui_snapshot<C, P>(ui: &Ui<(Image, C)>, path: P, tolerance: Tolerance)
+    where C: CharacterCache<Texture=Image>,
+          P: AsRef<Path>,
+{
+    assert_snapshot(&render(ui), path, tolerance);
+}
+
+
+/// Compare the given image against the golden image at the given path.
+///
+/// See the [module documentation](./index.html) for details.
+pub fn assert_snapshot<P: AsRef<Path>>(actual: &Image, path: P, tolerance: Tolerance) {
+    let path = path.as_ref();
+
+    if ::std::env::var_os(UPDATE_ENV_VAR).is_some() {
+        if let Err(err) = actual.save_pam(path) {
+            panic!("failed to write snapshot {:?}: {}", path, err);
+        }
+        return;
+    }
+
+    if !path.exists() {
+        panic!("snapshot {:?} does not exist. Run the tests with the `{}` environment variable \
+                set to write it.", path, UPDATE_ENV_VAR);
+    }
+
+    let expected = match Image::open_pam(path) {
+        Ok(expected) => expected,
+        Err(err) => panic!("failed to read snapshot {:?}: {}", path, err),
+    };
+
+    let diff = diff(&expected, actual, tolerance.per_channel);
+    if diff.num_differing_pixels > tolerance.max_differing_pixels {
+        // e.g. `button.pam` becomes `button.actual.pam` and `button.diff.pam`.
+        let actual_path = path.with_extension("actual.pam");
+        let diff_path = path.with_extension("diff.pam");
+        let _ = actual.save_pam(&actual_path);
+        let _ = diff.image.save_pam(&diff_path);
+        panic!("snapshot {:?} differs in {} pixels (tolerance: {:?}).\n\
+                actual image written to {:?}\n\
+                diff image written to {:?}",
+               path, diff.num_differing_pixels, tolerance, actual_path, diff_path);
+    }
+}
+
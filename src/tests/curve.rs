@@ -0,0 +1,88 @@
+use {Arc, Point, Positionable, Scalar, Transform, Widget};
+use render::Primitive;
+use std::f64::consts::PI;
+use testing;
+use widget;
+use widget::primitive::shape::FLATTENING_TOLERANCE;
+use widget::primitive::shape::arc::{self, Section};
+use widget::primitive::shape::bezier::Curve;
+
+
+fn triangle_area(t: &[Point; 3]) -> Scalar {
+    ((t[1][0] - t[0][0]) * (t[2][1] - t[0][1]) - (t[2][0] - t[0][0]) * (t[1][1] - t[0][1])).abs()
+        / 2.0
+}
+
+
+#[test]
+fn arcs_should_be_flattened_according_to_their_size() {
+    assert!(arc::num_segments(100.0, PI) > arc::num_segments(10.0, PI));
+    assert!(arc::num_segments(100.0, 2.0 * PI) > arc::num_segments(100.0, PI));
+
+    // Every segment's midpoint lies within the tolerance of the true arc.
+    let points = arc::points([0.0, 0.0], 50.0, 0.0, PI);
+    for w in points.windows(2) {
+        let mid = [(w[0][0] + w[1][0]) / 2.0, (w[0][1] + w[1][1]) / 2.0];
+        assert!(50.0 - mid[0].hypot(mid[1]) <= FLATTENING_TOLERANCE);
+    }
+}
+
+#[test]
+fn ring_section_triangles_should_cover_the_section() {
+    let section = Section { start: 0.0, end: PI / 2.0, inner_radius: 50.0 };
+    let triangles = section.triangles([0.0, 0.0], 100.0);
+    let area: Scalar = triangles.iter().map(triangle_area).sum();
+    let expected = (100.0 * 100.0 - 50.0 * 50.0) * PI / 4.0;
+    assert!((area - expected).abs() / expected < 0.01);
+
+    // A pie slice's outline returns to the centre and is closed.
+    let pie = Section { start: 0.0, end: PI / 2.0, inner_radius: 0.0 };
+    let outlines = pie.outlines([0.0, 0.0], 10.0);
+    assert_eq!(outlines.len(), 1);
+    assert_eq!(outlines[0].first(), outlines[0].last());
+    assert!(outlines[0].contains(&[0.0, 0.0]));
+
+    // A full ring is outlined by two circles.
+    let ring = Section { start: 0.0, end: 2.0 * PI, inner_radius: 5.0 };
+    assert_eq!(ring.outlines([0.0, 0.0], 10.0).len(), 2);
+}
+
+#[test]
+fn bezier_curves_should_be_flattened_adaptively() {
+    let small = Curve::cubic([0.0, 0.0], [0.0, 10.0], [10.0, 10.0], [10.0, 0.0]);
+    let large = Curve::cubic([0.0, 0.0], [0.0, 100.0], [100.0, 100.0], [100.0, 0.0]);
+    let points = large.flatten();
+
+    assert_eq!(points.first(), Some(&[0.0, 0.0]));
+    assert_eq!(points.last(), Some(&[100.0, 0.0]));
+    assert!(points.len() > small.flatten().len());
+    // The curve's apex lies upon the flattened path.
+    assert!(points.contains(&large.point_at(0.5)));
+
+    // A straight curve needs no subdivision.
+    let straight = Curve::quadratic([0.0, 0.0], [5.0, 0.0], [10.0, 0.0]);
+    assert_eq!(straight.flatten(), vec![[0.0, 0.0], [10.0, 0.0]]);
+}
+
+#[test]
+fn arcs_should_be_flattened_more_finely_when_enlarged_by_their_transform() {
+    let num_triangles = |scale: Scalar| {
+        const ARC_ID: widget::Id = widget::Id(0);
+        let mut ui = testing::windowless_ui([400.0, 400.0]);
+        ui.set_widgets(|ref mut ui| {
+            Arc::fill(10.0, 0.0, PI)
+                .middle()
+                .transform(Transform::scale(scale))
+                .set(ARC_ID, ui);
+        });
+        let num = ui.render_primitives().map(|primitive| match primitive {
+            Primitive::Triangles { triangles } => triangles.len(),
+            _ => 0,
+        }).sum::<usize>();
+        num
+    };
+
+    // An arc enlarged ten times is flattened like an arc of ten times the radius.
+    assert_eq!(num_triangles(1.0), arc::num_segments(10.0, PI));
+    assert_eq!(num_triangles(10.0), arc::num_segments(100.0, PI));
+}
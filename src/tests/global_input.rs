@@ -64,3 +64,24 @@ fn events_should_return_all_inputs_in_order() {
     assert_eq!(evt1, *results[0]);
     assert_eq!(evt2, *results[1]);
 }
+
+#[test]
+fn every_payload_dropped_onto_a_widget_should_be_taken_in_order() {
+    use widget::{Id, Index};
+
+    let source = Index::Public(Id(0));
+    let (target, other) = (Index::Public(Id(1)), Index::Public(Id(2)));
+    let mut input = input::Global::new();
+    for &(n, idx) in &[(1u32, target), (2, other), (3, target)] {
+        input.start_drag(source, Box::new(n));
+        input.set_drag_target(Some(idx));
+        input.accept_drop(idx);
+        input.drop_payload();
+    }
+
+    let mut take = |idx| input.take_dropped_payload(idx).map(|p| *p.downcast::<u32>().unwrap());
+    assert_eq!(take(target), Some(1));
+    assert_eq!(take(target), Some(3));
+    assert_eq!(take(target), None);
+    assert_eq!(take(other), Some(2));
+}
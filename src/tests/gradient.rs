@@ -0,0 +1,61 @@
+use {Canvas, Frameable, Gradientable, Oval, Positionable, Widget};
+use color;
+use testing::snapshot;
+use widget;
+
+
+#[test]
+fn software_backend_should_interpolate_a_linear_gradient_across_the_canvas() {
+    let mut ui = snapshot::windowless_ui([100.0, 10.0]);
+
+    const CANVAS_ID: widget::Id = widget::Id(0);
+    ui.set_widgets(|ref mut ui| {
+        Canvas::new()
+            .frame(0.0)
+            .linear_gradient(0.0, &[(0.0, color::rgb(1.0, 0.0, 0.0)),
+                                    (1.0, color::rgb(0.0, 0.0, 1.0))])
+            .set(CANVAS_ID, ui);
+    });
+
+    let image = snapshot::render(&ui);
+
+    // Red on the left, blue on the right and an even mix in the middle.
+    let left = image.pixel(0, 5).unwrap();
+    let middle = image.pixel(50, 5).unwrap();
+    let right = image.pixel(99, 5).unwrap();
+    assert!(left[0] > 250 && left[2] < 5);
+    assert!(right[0] < 5 && right[2] > 250);
+    assert!((middle[0] as i32 - middle[2] as i32).abs() <= 6);
+    assert_eq!(middle[3], 255);
+}
+
+#[test]
+fn software_backend_should_shade_a_radial_gradient_by_distance_from_its_centre() {
+    let mut ui = snapshot::windowless_ui([40.0, 40.0]);
+
+    const OVAL_ID: widget::Id = widget::Id(0);
+    ui.set_widgets(|ref mut ui| {
+        Oval::fill([40.0, 40.0])
+            .middle()
+            .radial_gradient([0.0, 0.0], 20.0, &[(0.0, color::WHITE), (1.0, color::BLACK)])
+            .set(OVAL_ID, ui);
+    });
+
+    let image = snapshot::render(&ui);
+
+    // The gradient darkens towards the edge of the oval, outside of which nothing is drawn.
+    let brightness = |x: u32| image.pixel(x, 20).unwrap()[0];
+    assert!(brightness(20) > 240);
+    assert!(brightness(20) > brightness(10) && brightness(10) > brightness(2));
+    assert!((brightness(10) as i32 - 128).abs() < 16);
+    assert_eq!(image.pixel(0, 0), Some([0; 4]));
+}
+
+#[test]
+#[should_panic(expected = "at most")]
+fn fill_gradient_should_reject_more_than_the_maximum_number_of_stops() {
+    let stops: Vec<_> = (0..color::MAX_GRADIENT_STOPS + 1)
+        .map(|i| (i as f32, color::WHITE))
+        .collect();
+    color::FillGradient::linear(0.0, &stops);
+}
@@ -0,0 +1,173 @@
+use {Button, Canvas, Positionable, Rect, Sizeable, Widget};
+use backend::software::Image;
+use testing::snapshot::{self, SnapshotUi};
+use widget;
+use widget::primitive::image::Fit;
+
+
+#[test]
+fn contain_and_cover_should_preserve_the_aspect_ratio_of_the_source() {
+    // A source twice as wide as it is tall, fitted to a square.
+    let src_rect = Rect::from_xy_dim([0.0, 0.0], [40.0, 20.0]);
+    let rect = Rect::from_xy_dim([0.0, 0.0], [20.0, 20.0]);
+
+    // The whole source is scaled down to fit within the square.
+    let contain = Fit::Contain.slices(src_rect, rect);
+    assert_eq!(contain, vec![(src_rect, Rect::from_xy_dim([0.0, 0.0], [20.0, 10.0]))]);
+
+    // The centre of the source covers the whole square.
+    let cover = Fit::Cover.slices(src_rect, rect);
+    assert_eq!(cover, vec![(Rect::from_xy_dim([10.0, 0.0], [20.0, 20.0]), rect)]);
+
+    // The source is drawn at its native size in the middle of a larger area.
+    let large_rect = Rect::from_xy_dim([5.0, 5.0], [60.0, 60.0]);
+    let center = Fit::Center.slices(src_rect, large_rect);
+    assert_eq!(center, vec![(src_rect, Rect::from_xy_dim([5.0, 5.0], [40.0, 20.0]))]);
+}
+
+#[test]
+fn tile_should_repeat_the_source_from_the_top_left_and_crop_the_last_tiles() {
+    let src_rect = Rect::from_xy_dim([0.0, 0.0], [8.0, 8.0]);
+    let rect = Rect::from_corners([0.0, 0.0], [20.0, 20.0]);
+    let tiles = Fit::Tile.slices(src_rect, rect);
+
+    assert_eq!(tiles.len(), 9);
+    assert_eq!(tiles[0], (src_rect, Rect::from_corners([0.0, 12.0], [8.0, 20.0])));
+    // The tile in the bottom-right corner is cropped along both axes.
+    let cropped = Rect::from_xy_dim([0.0, 0.0], [4.0, 4.0]);
+    assert_eq!(tiles[8], (cropped, Rect::from_corners([16.0, 0.0], [20.0, 4.0])));
+    let area: f64 = tiles.iter().map(|&(_, dst)| dst.w() * dst.h()).sum();
+    assert_eq!(area, 400.0);
+}
+
+#[test]
+fn software_backend_should_keep_the_corners_of_a_nine_slice_image_unscaled() {
+    use std::sync::Arc;
+    use widget::primitive::image::{self, NineSlice};
+
+    let mut ui = snapshot::windowless_ui([20.0, 20.0]);
+
+    // A 4x4 texture with red corners, green edges and a blue centre.
+    let (red, green, blue) = ([255, 0, 0, 255], [0, 255, 0, 255], [0, 0, 255, 255]);
+    let pixels = (0..16).map(|i| match (i % 4 == 0 || i % 4 == 3, i / 4 == 0 || i / 4 == 3) {
+        (true, true) => red,
+        (true, false) | (false, true) => green,
+        (false, false) => blue,
+    }).collect();
+    let texture = Arc::new(Image::from_pixels(4, 4, pixels));
+
+    const CANVAS_ID: widget::Id = widget::Id(0);
+    const IMAGE_ID: widget::Id = widget::Id(1);
+    ui.set_widgets(|ref mut ui| {
+        Canvas::new().set(CANVAS_ID, ui);
+        image::Image::from_texture(texture.clone())
+            .nine_slice(NineSlice::uniform(1.0))
+            .w_h(20.0, 20.0)
+            .middle_of(CANVAS_ID)
+            .set(IMAGE_ID, ui);
+    });
+
+    let image = snapshot::render(&ui);
+
+    assert_eq!(image.pixel(0, 0), Some(red));
+    assert_eq!(image.pixel(19, 19), Some(red));
+    assert_eq!(image.pixel(10, 0), Some(green));
+    assert_eq!(image.pixel(0, 10), Some(green));
+    // Were the image simply stretched, its corners would span five pixels.
+    assert_eq!(image.pixel(3, 3), Some(blue));
+    assert_eq!(image.pixel(10, 10), Some(blue));
+}
+
+#[test]
+fn software_backend_should_draw_a_button_skin_in_place_of_its_rectangle() {
+    use std::sync::Arc;
+    use widget::primitive::image::Skin;
+
+    let mut ui = snapshot::windowless_ui([20.0, 20.0]);
+
+    let magenta = [255, 0, 255, 255];
+    let skin = Skin::new(Arc::new(Image::from_pixels(1, 1, vec![magenta])));
+
+    const CANVAS_ID: widget::Id = widget::Id(0);
+    const BUTTON_ID: widget::Id = widget::Id(1);
+    ui.set_widgets(|ref mut ui| {
+        Canvas::new().set(CANVAS_ID, ui);
+        Button::new()
+            .skin(&skin)
+            .w_h(10.0, 10.0)
+            .middle_of(CANVAS_ID)
+            .react(|| ())
+            .set(BUTTON_ID, ui);
+    });
+
+    let image = snapshot::render(&ui);
+
+    assert_eq!(image.pixel(6, 6), Some(magenta));
+    assert_eq!(image.pixel(10, 10), Some(magenta));
+    assert_eq!(image.pixel(2, 2), image.pixel(2, 17));
+    assert!(image.pixel(2, 2) != Some(magenta));
+}
+
+#[test]
+#[should_panic(expected = "skin texture type differs")]
+fn buttons_should_panic_when_skinned_with_a_texture_unknown_to_the_backend() {
+    use std::sync::Arc;
+    use widget::primitive::image::Skin;
+
+    let mut ui = snapshot::windowless_ui([100.0, 100.0]);
+    let skin = Skin::new(Arc::new(0u8));
+
+    const BUTTON_ID: widget::Id = widget::Id(0);
+    ui.set_widgets(|ref mut ui| {
+        Button::new()
+            .skin(&skin)
+            .react(|| ())
+            .set(BUTTON_ID, ui);
+    });
+}
+
+#[test]
+fn software_backend_should_advance_an_animated_image_to_its_last_frame() {
+    use std::sync::Arc;
+    use AnimatedImage;
+
+    let mut ui = snapshot::windowless_ui([20.0, 20.0]);
+
+    // A sprite sheet of two frames side by side: red then blue.
+    let (red, blue) = ([255, 0, 0, 255], [0, 0, 255, 255]);
+    let texture = Arc::new(Image::from_pixels(2, 1, vec![red, blue]));
+
+    const CANVAS_ID: widget::Id = widget::Id(0);
+    const ANIMATION_ID: widget::Id = widget::Id(1);
+    let set_widgets = |ui: &mut SnapshotUi, is_playing: bool| ui.set_widgets(|ref mut ui| {
+        Canvas::new().set(CANVAS_ID, ui);
+        AnimatedImage::grid(texture.clone(), 2, 1)
+            .fps(1000.0)
+            .playing(is_playing)
+            .looping(false)
+            .w_h(10.0, 10.0)
+            .middle_of(CANVAS_ID)
+            .set(ANIMATION_ID, ui);
+    });
+    let draw = |ui: &SnapshotUi| snapshot::render(ui).pixel(10, 10);
+
+    set_widgets(&mut ui, true);
+    assert_eq!(draw(&ui), Some(red));
+
+    // At a thousand frames per second, the animation soon reaches its last frame and never
+    // returns to its first.
+    while draw(&ui) != Some(blue) {
+        assert_eq!(draw(&ui), Some(red));
+        set_widgets(&mut ui, true);
+    }
+    for _ in 0..10 {
+        set_widgets(&mut ui, true);
+        assert_eq!(draw(&ui), Some(blue));
+    }
+
+    // Pausing leaves the animation on its last frame, while playing it again restarts it.
+    set_widgets(&mut ui, false);
+    assert_eq!(draw(&ui), Some(blue));
+    set_widgets(&mut ui, true);
+    assert_eq!(draw(&ui), Some(red));
+}
@@ -0,0 +1,104 @@
+use Point;
+use backend::software::{self, Image};
+use color;
+use render::Primitive;
+use testing::snapshot;
+use widget::primitive::line::{pattern_paths, stroke_triangles, Cap, Dash, Join, Pattern};
+
+
+#[test]
+fn dashed_pattern_should_continue_across_path_vertices() {
+    // An "L" shaped path with segments of length 10 and 10.
+    let points = [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0]];
+    let dash = Dash { length: 6.0, gap: 2.0, phase: 0.0 };
+    let paths = pattern_paths(&points, Pattern::Dashed, dash, 1.0);
+
+    // Dashes cover [0, 6], [8, 14] and [16, 20] along the path.
+    assert_eq!(paths, vec![
+        vec![[0.0, 0.0], [6.0, 0.0]],
+        vec![[8.0, 0.0], [10.0, 0.0], [10.0, 4.0]],
+        vec![[10.0, 6.0], [10.0, 10.0]],
+    ]);
+
+    // A phase of 4 shifts the pattern along the path by 4.
+    let dash = Dash { phase: 4.0, ..dash };
+    let paths = pattern_paths(&points, Pattern::Dashed, dash, 1.0);
+    assert_eq!(paths, vec![
+        vec![[0.0, 0.0], [2.0, 0.0]],
+        vec![[4.0, 0.0], [10.0, 0.0]],
+        vec![[10.0, 2.0], [10.0, 8.0]],
+    ]);
+}
+
+#[test]
+fn dotted_pattern_should_produce_evenly_spaced_dots() {
+    let points = [[0.0, 0.0], [5.0, 0.0], [5.0, 7.0]];
+    let dash = Dash { length: 6.0, gap: 2.0, phase: 0.0 };
+    let paths = pattern_paths(&points, Pattern::Dotted, dash, 2.0);
+
+    // Each dot is one thickness plus one gap apart.
+    assert_eq!(paths, vec![
+        vec![[0.0, 0.0], [0.0, 0.0]],
+        vec![[4.0, 0.0], [4.0, 0.0]],
+        vec![[5.0, 3.0], [5.0, 3.0]],
+        vec![[5.0, 7.0], [5.0, 7.0]],
+    ]);
+
+    let solid = pattern_paths(&points, Pattern::Solid, dash, 2.0);
+    assert_eq!(solid, vec![points.to_vec()]);
+}
+
+#[test]
+fn stroke_should_join_corners_without_gaps_or_overlap() {
+    fn area(triangles: &[[Point; 3]]) -> f64 {
+        triangles.iter().map(|t| {
+            let (a, b, c) = (t[0], t[1], t[2]);
+            ((b[0] - a[0]) * (c[1] - a[1]) - (c[0] - a[0]) * (b[1] - a[1])).abs() / 2.0
+        }).sum()
+    }
+
+    // An "L" shaped path with a thickness of 2. The miter fills the outer corner exactly, giving
+    // an area of 11 * 2 for the horizontal stroke plus 9 * 2 for the vertical stroke.
+    let points = [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0]];
+    let miter = stroke_triangles(&points, 2.0, Cap::Flat, Join::Miter(4.0));
+    assert!((area(&miter) - 40.0).abs() < 1e-9);
+
+    // A bevel cuts half of the outer corner's unit square.
+    let bevel = stroke_triangles(&points, 2.0, Cap::Flat, Join::Bevel);
+    assert!((area(&bevel) - 39.5).abs() < 1e-9);
+
+    // A right angle has a miter ratio of sqrt(2), so a limit of 1 falls back to a bevel.
+    let limited = stroke_triangles(&points, 2.0, Cap::Flat, Join::Miter(1.0));
+    assert!((area(&limited) - 39.5).abs() < 1e-9);
+
+    // A round join lies between the two.
+    let round = area(&stroke_triangles(&points, 2.0, Cap::Flat, Join::Round));
+    assert!(round > 39.5 && round < 40.0);
+
+    // Square caps extend both ends by half of the thickness.
+    let square = stroke_triangles(&points, 2.0, Cap::Square, Join::Miter(4.0));
+    assert!((area(&square) - 44.0).abs() < 1e-9);
+}
+
+#[test]
+fn software_backend_should_blend_each_pixel_of_a_stroke_once() {
+    let mut image = Image::new(40, 40);
+    let mut cc = snapshot::MockGlyphCache::new();
+    // A thick, translucent zig-zag whose short segments cause the stroke to overlap itself.
+    let primitives = vec![
+        Primitive::Lines {
+            color: color::WHITE.alpha(0.5),
+            pattern: Pattern::Solid,
+            dash: Dash { length: 0.0, gap: 0.0, phase: 0.0 },
+            thickness: 10.0,
+            cap: Cap::Round,
+            join: Join::Round,
+            points: vec![[-15.0, 0.0], [-12.0, 3.0], [-9.0, 0.0], [-6.0, 3.0], [15.0, 0.0]],
+        },
+    ];
+    software::draw_primitives(primitives, [40.0, 40.0], &mut cc, &mut image);
+
+    let mut alphas: Vec<u8> = image.pixels().iter().map(|p| p[3]).filter(|&a| a > 0).collect();
+    alphas.dedup();
+    assert_eq!(alphas.len(), 1);
+}
@@ -1,3 +1,14 @@
+mod curve;
 mod global_input;
+mod gradient;
+mod image;
+mod line;
+mod polygon;
+mod rounded_rectangle;
+mod shadow;
+mod snapshot;
+mod software;
+mod svg;
+mod terminal;
 mod widget_input;
 mod ui;
@@ -0,0 +1,85 @@
+use {Colorable, Point, Polygon, Scalar, Widget};
+use color;
+use testing::snapshot;
+use widget;
+use widget::primitive::shape::polygon::triangulate;
+
+
+fn area(triangles: &[[Point; 3]]) -> Scalar {
+    triangles.iter().map(|t| {
+        ((t[1][0] - t[0][0]) * (t[2][1] - t[0][1]) - (t[2][0] - t[0][0]) * (t[1][1] - t[0][1]))
+            .abs() / 2.0
+    }).sum()
+}
+
+fn centroid(t: &[Point; 3]) -> Point {
+    [(t[0][0] + t[1][0] + t[2][0]) / 3.0, (t[0][1] + t[1][1] + t[2][1]) / 3.0]
+}
+
+
+#[test]
+fn concave_polygons_should_be_triangulated_within_their_outline() {
+    // An "L" shape, wound clockwise, with the top right quadrant cut away.
+    let points = [[0.0, 0.0], [0.0, 20.0], [10.0, 20.0], [10.0, 10.0], [20.0, 10.0], [20.0, 0.0]];
+    let triangles = triangulate(&points);
+    assert_eq!(triangles.len(), points.len() - 2);
+    assert!((area(&triangles) - 300.0).abs() < 1e-9);
+    for t in &triangles {
+        let c = centroid(t);
+        assert!(!(c[0] > 10.0 && c[1] > 10.0));
+    }
+}
+
+#[test]
+fn polygons_with_a_reflex_vertex_on_a_diagonal_should_be_fully_triangulated() {
+    // A square with a notch cut into its top, whose reflex vertex lies on both of the square's
+    // diagonals.
+    let points = [[0.0, 0.0], [20.0, 0.0], [20.0, 20.0], [10.0, 10.0], [0.0, 20.0]];
+    let triangles = triangulate(&points);
+    assert!((area(&triangles) - 300.0).abs() < 1e-9);
+    for t in &triangles {
+        let c = centroid(t);
+        assert!(!(c[1] > 10.0 && (c[0] - 10.0).abs() < c[1] - 10.0));
+    }
+}
+
+#[test]
+fn self_intersecting_polygons_should_be_filled_using_the_even_odd_rule() {
+    // A bowtie, whose two halves meet at the origin.
+    let bowtie = [[-10.0, -10.0], [10.0, 10.0], [10.0, -10.0], [-10.0, 10.0]];
+    assert!((area(&triangulate(&bowtie)) - 200.0).abs() < 1e-9);
+
+    // A pentagram, whose central pentagon is crossed twice and so is left empty.
+    let star: Vec<Point> = (0..5).map(|i| {
+        let angle = ::std::f64::consts::PI * (0.5 + 0.8 * i as Scalar);
+        [100.0 * angle.cos(), 100.0 * angle.sin()]
+    }).collect();
+    let triangles = triangulate(&star);
+    assert!(!triangles.is_empty());
+    for t in &triangles {
+        let c = centroid(t);
+        assert!(c[0].hypot(c[1]) > 30.0);
+    }
+}
+
+#[test]
+fn software_backend_should_leave_the_notch_of_a_concave_polygon_empty() {
+    let mut ui = snapshot::windowless_ui([40.0, 40.0]);
+
+    // A "U" shape whose notch is open to the top of the window.
+    const POLYGON_ID: widget::Id = widget::Id(0);
+    let points = [[-20.0, -20.0], [20.0, -20.0], [20.0, 20.0], [10.0, 20.0],
+                  [10.0, -10.0], [-10.0, -10.0], [-10.0, 20.0], [-20.0, 20.0]];
+    ui.set_widgets(|ref mut ui| {
+        Polygon::abs_fill(points.iter().cloned())
+            .color(color::WHITE)
+            .set(POLYGON_ID, ui);
+    });
+
+    let image = snapshot::render(&ui);
+
+    assert_eq!(image.pixel(20, 10), Some([0; 4]));
+    assert_eq!(image.pixel(5, 10), Some([255; 4]));
+    assert_eq!(image.pixel(35, 10), Some([255; 4]));
+    assert_eq!(image.pixel(20, 35), Some([255; 4]));
+}
@@ -0,0 +1,31 @@
+use {Button, Colorable, Frameable, Positionable, Sizeable, Widget};
+use color;
+use testing::snapshot;
+use widget;
+
+
+#[test]
+fn software_backend_should_draw_rounded_button_corners() {
+    let mut ui = snapshot::windowless_ui([40.0, 20.0]);
+
+    const BUTTON_ID: widget::Id = widget::Id(0);
+    ui.set_widgets(|ref mut ui| {
+        Button::new()
+            .w_h(40.0, 20.0)
+            .middle()
+            .color(color::RED)
+            .frame(0.0)
+            .corner_radius(8.0)
+            .react(|| {})
+            .set(BUTTON_ID, ui);
+    });
+
+    let image = snapshot::render(&ui);
+
+    // The corners are cut away while the edges and centre are filled.
+    assert_eq!(image.pixel(0, 0), Some([0; 4]));
+    assert_eq!(image.pixel(39, 19), Some([0; 4]));
+    assert_eq!(image.pixel(20, 0), Some(color::RED.to_byte_fsa()));
+    assert_eq!(image.pixel(0, 10), Some(color::RED.to_byte_fsa()));
+    assert_eq!(image.pixel(20, 10), Some(color::RED.to_byte_fsa()));
+}
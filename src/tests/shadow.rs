@@ -0,0 +1,64 @@
+use {Canvas, Colorable, Frameable, Positionable, Shadow, Sizeable, Widget};
+use color;
+use testing::snapshot;
+use widget;
+
+
+#[test]
+fn software_backend_should_draw_a_blurred_shadow_beneath_a_floating_widget() {
+    let mut ui = snapshot::windowless_ui([40.0, 40.0]);
+
+    const BACKGROUND_ID: widget::Id = widget::Id(0);
+    const POPUP_ID: widget::Id = widget::Id(1);
+    ui.set_widgets(|ref mut ui| {
+        Canvas::new()
+            .color(color::WHITE)
+            .set(BACKGROUND_ID, ui);
+        Canvas::new()
+            .w_h(10.0, 10.0)
+            .middle_of(BACKGROUND_ID)
+            .parent(BACKGROUND_ID)
+            .floating(true)
+            .color(color::RED)
+            .frame(0.0)
+            .shadow(Shadow::new([5.0, -5.0], 4.0, color::BLACK))
+            .set(POPUP_ID, ui);
+    });
+
+    let image = snapshot::render(&ui);
+
+    // The popup is drawn over its shadow.
+    assert_eq!(image.pixel(20, 20), Some(color::RED.to_byte_fsa()));
+    // Within the blur the shadow is fully opaque, fading out over the blur distance.
+    assert_eq!(image.pixel(26, 26), Some([0, 0, 0, 255]));
+    let edge = image.pixel(30, 24).unwrap();
+    assert!(edge[0] > 64 && edge[0] < 192);
+    assert_eq!(image.pixel(35, 5), Some([255; 4]));
+}
+
+#[test]
+fn software_backend_should_draw_the_shadow_of_a_widget_lying_just_outside_the_window() {
+    let mut ui = snapshot::windowless_ui([40.0, 40.0]);
+
+    const BACKGROUND_ID: widget::Id = widget::Id(0);
+    const POPUP_ID: widget::Id = widget::Id(1);
+    ui.set_widgets(|ref mut ui| {
+        Canvas::new()
+            .color(color::WHITE)
+            .set(BACKGROUND_ID, ui);
+        // The popup spans -31..-21 along *x*, while its shadow reaches -21..-11.
+        Canvas::new()
+            .w_h(10.0, 10.0)
+            .x_y(-26.0, 0.0)
+            .parent(BACKGROUND_ID)
+            .floating(true)
+            .color(color::RED)
+            .frame(0.0)
+            .shadow(Shadow::new([10.0, 0.0], 0.0, color::BLACK))
+            .set(POPUP_ID, ui);
+    });
+
+    let image = snapshot::render(&ui);
+    assert_eq!(image.pixel(3, 20), Some([0, 0, 0, 255]));
+    assert_eq!(image.pixel(15, 20), Some([255; 4]));
+}
@@ -0,0 +1,81 @@
+use {Canvas, Colorable, Widget};
+use backend::software::Image;
+use color;
+use std;
+use testing::snapshot::{self, Tolerance};
+use widget;
+
+
+///// Test assist code.
+
+
+fn set_canvas(ui: &mut snapshot::SnapshotUi, color: color::Color) {
+    const CANVAS_ID: widget::Id = widget::Id(0);
+    ui.set_widgets(|ref mut ui| {
+        Canvas::new()
+            .color(color)
+            .set(CANVAS_ID, ui);
+    });
+}
+
+fn temp_snapshot_path(name: &str) -> std::path::PathBuf {
+    let path = std::env::temp_dir().join(format!("conrod_test_{}.pam", name));
+    let _ = std::fs::remove_file(&path);
+    let _ = std::fs::remove_file(path.with_extension("actual.pam"));
+    let _ = std::fs::remove_file(path.with_extension("diff.pam"));
+    path
+}
+
+
+///// Actual tests.
+
+
+#[test]
+fn image_should_round_trip_through_pam() {
+    let mut image = Image::new(3, 2);
+    image.set_pixel(1, 1, [1, 2, 3, 4]);
+    let mut bytes = Vec::new();
+    image.write_pam(&mut bytes).unwrap();
+    assert_eq!(Image::read_pam(&bytes[..]).unwrap(), image);
+}
+
+#[test]
+fn diff_should_only_count_pixels_beyond_the_tolerance() {
+    let expected = Image::from_pixels(2, 1, vec![[100; 4], [100; 4]]);
+    let actual = Image::from_pixels(2, 1, vec![[102; 4], [110; 4]]);
+    assert_eq!(snapshot::diff(&expected, &actual, 0).num_differing_pixels, 2);
+    assert_eq!(snapshot::diff(&expected, &actual, 2).num_differing_pixels, 1);
+    assert_eq!(snapshot::diff(&expected, &actual, 10).num_differing_pixels, 0);
+}
+
+#[test]
+fn snapshot_should_write_a_diff_image_when_the_ui_changes() {
+    let path = temp_snapshot_path("snapshot_should_write_a_diff_image_when_the_ui_changes");
+    let mut ui = snapshot::windowless_ui([20.0, 10.0]);
+
+    set_canvas(&mut ui, color::RED);
+    snapshot::render(&ui).save_pam(&path).unwrap();
+    snapshot::assert_ui_snapshot(&ui, &path, Tolerance::exact());
+
+    set_canvas(&mut ui, color::BLUE);
+    let image = snapshot::render(&ui);
+    let result = std::panic::catch_unwind(|| {
+        snapshot::assert_snapshot(&image, &path, Tolerance::per_channel(8));
+    });
+    assert!(result.is_err());
+
+    let diff = Image::open_pam(path.with_extension("diff.pam")).unwrap();
+    assert_eq!(diff.pixel(10, 5), Some([255, 0, 0, 255]));
+}
+
+#[test]
+fn snapshot_should_panic_rather_than_write_a_missing_golden_image() {
+    let path = temp_snapshot_path("snapshot_should_panic_rather_than_write_a_missing_golden_image");
+    let image = Image::new(2, 2);
+    let result = std::panic::catch_unwind(|| {
+        snapshot::assert_snapshot(&image, &path, Tolerance::exact());
+    });
+    let message = result.unwrap_err().downcast::<String>().unwrap();
+    assert!(message.contains(snapshot::UPDATE_ENV_VAR));
+    assert!(!path.exists());
+}
@@ -0,0 +1,43 @@
+use {Canvas, Colorable, Rect, Widget};
+use backend::software::{self, Image};
+use color;
+use render::Primitive;
+use testing::snapshot;
+use widget;
+
+
+#[test]
+fn software_backend_should_fill_the_image_with_the_canvas_color() {
+    let mut ui = snapshot::windowless_ui([100.0, 50.0]);
+
+    const CANVAS_ID: widget::Id = widget::Id(0);
+    ui.set_widgets(|ref mut ui| {
+        Canvas::new()
+            .color(color::RED)
+            .set(CANVAS_ID, ui);
+    });
+
+    // Rasterize at twice the window resolution.
+    let mut image = Image::new(200, 100);
+    software::draw_ui(&ui, &mut image);
+
+    assert_eq!(image.pixel(100, 50), Some(color::RED.to_byte_fsa()));
+}
+
+#[test]
+fn software_backend_should_crop_primitives_to_the_scissor() {
+    let mut image = Image::new(10, 10);
+    let mut cc = snapshot::MockGlyphCache::new();
+    let primitives = vec![
+        // Crop to the left half of the view.
+        Primitive::Scissor(Rect::from_corners([-5.0, -5.0], [0.0, 5.0])),
+        Primitive::Rectangle {
+            color: color::WHITE,
+            rect: Rect::from_corners([-5.0, -5.0], [5.0, 5.0]),
+        },
+    ];
+    software::draw_primitives(primitives, [10.0, 10.0], &mut cc, &mut image);
+
+    assert_eq!(image.pixel(2, 5), Some([255; 4]));
+    assert_eq!(image.pixel(7, 5), Some([0; 4]));
+}
@@ -0,0 +1,80 @@
+use {Canvas, Colorable, Frameable, Gradientable, Positionable, Rect, Sizeable, Text, Widget};
+use backend::software::Image;
+use backend::svg;
+use color;
+use render::Primitive;
+use testing;
+use widget;
+
+
+#[test]
+fn svg_should_contain_elements_in_depth_order_and_clip_cropped_kids() {
+    let mut ui = testing::windowless_ui([200.0, 100.0]);
+
+    const CANVAS_ID: widget::Id = widget::Id(0);
+    const TEXT_ID: widget::Id = widget::Id(1);
+    ui.set_widgets(|ref mut ui| {
+        Canvas::new()
+            .w_h(150.0, 80.0)
+            .middle()
+            .color(color::RED)
+            .crop_kids()
+            .set(CANVAS_ID, ui);
+        Text::new("<A & B>")
+            .w(100.0)
+            .middle_of(CANVAS_ID)
+            .parent(CANVAS_ID)
+            .set(TEXT_ID, ui);
+    });
+
+    let svg = svg::from_ui(&ui, |_| None);
+
+    let rect = svg.find("<rect x=\"25\" y=\"10\" width=\"150\" height=\"80\" fill")
+        .expect("expected the canvas frame rect");
+    let clip_rect = "<rect x=\"25\" y=\"10\" width=\"150\" height=\"80\"/>";
+    let clip = svg.find(&format!("<clipPath id=\"clip0\">{}", clip_rect))
+        .expect("expected a clip path for the canvas");
+    let text = svg.find("&lt;A &amp; B&gt;</text>").expect("expected the escaped text");
+    assert!(clip < rect && rect < text);
+    assert!(svg.trim_end().ends_with("</g>\n</svg>"));
+}
+
+#[test]
+fn svg_should_describe_gradients_with_linear_gradient_elements() {
+    let mut ui = testing::windowless_ui([100.0, 10.0]);
+
+    const CANVAS_ID: widget::Id = widget::Id(0);
+    ui.set_widgets(|ref mut ui| {
+        Canvas::new()
+            .frame(0.0)
+            .linear_gradient(0.0, &[(0.0, color::rgb(1.0, 0.0, 0.0)),
+                                    (1.0, color::rgb(0.0, 0.0, 1.0))])
+            .set(CANVAS_ID, ui);
+    });
+
+    let svg = svg::from_ui(&ui, |_| None);
+
+    // Every triangle is shaded by a gradient, whose ends are pure red and blue.
+    assert!(svg.contains("<linearGradient id=\"gradient0\""));
+    assert_eq!(svg.matches("<polygon").count(), svg.matches("fill=\"url(#gradient").count());
+    assert!(svg.contains("stop-color=\"rgb(255,0,0)\""));
+    assert!(svg.contains("stop-color=\"rgb(0,0,255)\""));
+}
+
+#[test]
+fn svg_should_tint_each_channel_of_an_image() {
+    let texture = Image::new(4, 4);
+    let rect = Rect::from_xy_dim([0.0, 0.0], [4.0, 4.0]);
+    let primitive = Primitive::Image {
+        texture: &texture,
+        color: Some(color::rgba(1.0, 0.5, 0.0, 0.25)),
+        src_rect: rect,
+        rect: rect,
+    };
+
+    let svg = svg::from_primitives(Some(primitive), [4.0, 4.0], |_| Some("tex.png".to_owned()));
+
+    assert!(svg.contains("<feColorMatrix type=\"matrix\" \
+                          values=\"1 0 0 0 0 0 0.5 0 0 0 0 0 0 0 0 0 0 0 0.25 0\"/>"));
+    assert!(svg.contains("<image width=\"4\" height=\"4\" filter=\"url(#tint0)\""));
+}
@@ -0,0 +1,49 @@
+use {Button, Colorable, Labelable, Positionable, Sizeable, Widget};
+use backend::event::{Input as RawInput, Motion, MouseButton};
+use backend::terminal::{Grid, Input};
+use backend::ToRawEvent;
+use color;
+use widget;
+
+
+#[test]
+fn terminal_backend_should_draw_framed_button_with_label() {
+    let mut grid = Grid::new(20, 5);
+    let mut ui = grid.ui();
+
+    const BUTTON_ID: widget::Id = widget::Id(0);
+    ui.set_widgets(|ref mut ui| {
+        Button::new()
+            .w_h(80.0, 48.0)
+            .middle()
+            .color(color::BLUE)
+            .label("Hi")
+            .react(|| {})
+            .set(BUTTON_ID, ui);
+    });
+
+    grid.draw_ui(&ui);
+
+    assert_eq!(grid.row_string(1), "     ┌────────┐     ");
+    assert_eq!(grid.row_string(3), "     └────────┘     ");
+    let label_row = grid.row_string(2);
+    assert!(label_row.starts_with("     │"));
+    assert!(label_row.contains("Hi"));
+    assert_eq!(grid.cell(6, 2).unwrap().bg, color::BLUE);
+}
+
+#[test]
+fn terminal_input_should_convert_to_raw_events_at_cell_centres() {
+    let grid = Grid::new(20, 5);
+    let dim = grid.window_dim();
+
+    let event = grid.event(Input::MouseMove { column: 0, row: 0 });
+    let raw = event.to_raw_event(dim[0], dim[1]).unwrap();
+    let expected: ::backend::RawEvent = RawInput::Move(Motion::MouseCursor(-76.0, 32.0)).into();
+    assert_eq!(raw, expected);
+
+    let event = grid.event(Input::MousePress(MouseButton::Left));
+    let raw = event.to_raw_event(dim[0], dim[1]).unwrap();
+    let expected: ::backend::RawEvent = RawInput::Press(MouseButton::Left.into()).into();
+    assert_eq!(raw, expected);
+}
@@ -1,51 +1,41 @@
 use {
-    Theme,
+    Arc,
     Canvas,
-    CharacterCache,
+    Circle,
     Color,
-    FontSize,
+    Frameable,
     Labelable,
+    Polygon,
     Positionable,
     Colorable,
+    Rect,
+    Rectangle,
+    RoundedRectangle,
     Sizeable,
+    Transform,
     Widget
 };
-use backend::graphics::{Character, ImageSize};
+use color;
 use event::{self, Input, Motion};
-use input::{self, Button, Key, MouseButton};
+use input::{self, Key, MouseButton};
 use input::keyboard::ModifierKey;
+use testing::{
+    assert_event_was_pushed,
+    left_click_mouse,
+    move_mouse_to,
+    move_mouse_to_widget,
+    press_mouse_button,
+    release_mouse_button,
+    snapshot,
+    MockUi as Ui,
+};
 use widget::{Index, self};
 use widget::button::Button as ButtonWidget;
-use position::Point;
 
 
 ///// Test assist code.
 
 
-type Ui = ::Ui<MockBackend>;
-
-fn left_click_mouse(ui: &mut Ui) {
-    press_mouse_button(MouseButton::Left, ui);
-    release_mouse_button(MouseButton::Left, ui);
-}
-
-fn release_mouse_button(button: MouseButton, ui: &mut Ui) {
-    let event = Input::Release(Button::Mouse(button));
-    ui.handle_event(event);
-}
-
-fn press_mouse_button(button: MouseButton, ui: &mut Ui) {
-    let event = Input::Press(Button::Mouse(button));
-    ui.handle_event(event);
-}
-
-fn move_mouse_to_widget(widget_idx: Index, ui: &mut Ui) {
-    ui.xy_of(widget_idx).map(|point| {
-        let abs_xy = to_window_coordinates(point, ui);
-        move_mouse_to_abs_coordinates(abs_xy[0], abs_xy[1], ui);
-    });
-}
-
 fn move_mouse_to_abs_coordinates(x: f64, y: f64, ui: &mut Ui) {
     ui.handle_event(Input::Move(Motion::MouseCursor(x, y)));
 }
@@ -55,71 +45,8 @@ fn test_handling_basic_input_event(ui: &mut Ui, event: Input) {
     assert_event_was_pushed(ui, event::Event::Raw(event));
 }
 
-fn assert_event_was_pushed(ui: &Ui, event: event::Event) {
-    let found = ui.global_input.events().find(|evt| **evt == event);
-    assert!(found.is_some(),
-            format!("expected to find event: {:?} in: \nevents: {:?}",
-                    event,
-                    ui.global_input.events().collect::<Vec<&event::Event>>()));
-}
-
-fn to_window_coordinates(xy: Point, ui: &Ui) -> Point {
-    let x = (ui.win_w / 2.0) + xy[0];
-    let y = (ui.win_h / 2.0) - xy[1];
-    [x, y]
-}
-
 fn windowless_ui() -> Ui {
-    let theme = Theme::default();
-    let cc = MockCharacterCache::new();
-    Ui::new(cc, theme)
-}
-
-#[derive(Copy, Clone)]
-struct MockBackend;
-
-impl ::Backend for MockBackend {
-    type Texture = MockImageSize;
-    type CharacterCache = MockCharacterCache;
-}
-
-#[derive(Clone)]
-struct MockImageSize {
-    w: u32,
-    h: u32,
-}
-
-impl ImageSize for MockImageSize {
-    fn get_size(&self) -> (u32, u32) {
-        (self.w, self.h)
-    }
-}
-
-#[derive(Clone)]
-struct MockCharacterCache{
-    my_char: Character<'static, MockImageSize>
-}
-
-impl MockCharacterCache {
-    fn new() -> MockCharacterCache {
-        const MOCK_IMAGE_SIZE: &'static MockImageSize = &MockImageSize{ w: 14, h: 22 };
-        MockCharacterCache {
-            my_char: Character{
-                offset: [0.0, 0.0],
-                size: [14.0, 22.0],
-                texture: MOCK_IMAGE_SIZE,
-            }
-        }
-    }
-}
-
-impl CharacterCache for MockCharacterCache {
-    type Texture = MockImageSize;
-
-    fn character(&mut self, _font_size: FontSize, _ch: char) -> Character<'static, MockImageSize> {
-        self.my_char.clone()
-    }
-
+    ::testing::windowless_ui([0.0, 0.0])
 }
 
 
@@ -135,7 +62,7 @@ fn ui_should_reset_global_input_after_widget_are_set() {
     const CANVAS_ID: widget::Id = widget::Id(0);
     const BUTTON_ID: widget::Id = widget::Id(1);
 
-    move_mouse_to_widget(Index::Public(BUTTON_ID), &mut ui);
+    move_mouse_to_widget(&mut ui, Index::Public(BUTTON_ID));
     left_click_mouse(&mut ui);
 
     assert!(ui.global_input.events().next().is_some());
@@ -177,21 +104,458 @@ fn ui_should_push_capturing_event_when_mouse_button_is_pressed_over_a_widget() {
     });
 
     let button_idx = Index::Public(BUTTON_ID);
-    move_mouse_to_widget(button_idx, &mut ui);
-    press_mouse_button(MouseButton::Left, &mut ui);
+    move_mouse_to_widget(&mut ui, button_idx);
+    press_mouse_button(&mut ui, MouseButton::Left);
 
     let expected_capture_event = event::Ui::WidgetCapturesKeyboard(button_idx);
     assert_event_was_pushed(&ui, expected_capture_event.into());
 
     // Now click somewhere on the background and widget should uncapture
-    release_mouse_button(MouseButton::Left, &mut ui);
+    release_mouse_button(&mut ui, MouseButton::Left);
     move_mouse_to_abs_coordinates(1.0, 1.0, &mut ui);
-    press_mouse_button(MouseButton::Left, &mut ui);
+    press_mouse_button(&mut ui, MouseButton::Left);
 
     let expected_uncapture_event = event::Ui::WidgetUncapturesKeyboard(button_idx);
     assert_event_was_pushed(&ui, expected_uncapture_event.into());
 }
 
+#[test]
+fn ui_should_cycle_keyboard_focus_through_focusable_widgets_with_tab() {
+    use render::Primitive;
+    use testing::press_and_release_key;
+
+    let mut ui = windowless_ui();
+    ui.win_w = 250.0;
+    ui.win_h = 300.0;
+
+    const CANVAS_ID: widget::Id = widget::Id(0);
+    const BUTTON_A_ID: widget::Id = widget::Id(1);
+    const BUTTON_B_ID: widget::Id = widget::Id(2);
+    const BUTTON_C_ID: widget::Id = widget::Id(3);
+    ui.set_widgets(|ref mut ui| {
+        Canvas::new().set(CANVAS_ID, ui);
+        for (i, &id) in [BUTTON_A_ID, BUTTON_B_ID, BUTTON_C_ID].iter().enumerate() {
+            ButtonWidget::new()
+                .w_h(50.0, 50.0)
+                .mid_top_with_margin_on(CANVAS_ID, 10.0 + 60.0 * i as f64)
+                .react(|| {})
+                .set(id, ui);
+        }
+    });
+
+    let capturing = |ui: &Ui| ui.global_input.current.widget_capturing_keyboard;
+    let shift_tab = |ui: &mut Ui| {
+        ui.handle_event(Input::Press(input::Button::Keyboard(Key::LShift)));
+        press_and_release_key(ui, Key::Tab);
+        ui.handle_event(Input::Release(input::Button::Keyboard(Key::LShift)));
+    };
+
+    // Focus follows the order in which the buttons were instantiated.
+    press_and_release_key(&mut ui, Key::Tab);
+    assert_eq!(capturing(&ui), Some(Index::Public(BUTTON_A_ID)));
+    let capture_event = event::Ui::WidgetCapturesKeyboard(Index::Public(BUTTON_A_ID));
+    assert_event_was_pushed(&ui, capture_event.into());
+    press_and_release_key(&mut ui, Key::Tab);
+    assert_eq!(capturing(&ui), Some(Index::Public(BUTTON_B_ID)));
+    let uncapture_event = event::Ui::WidgetUncapturesKeyboard(Index::Public(BUTTON_A_ID));
+    assert_event_was_pushed(&ui, uncapture_event.into());
+
+    // Shift+Tab moves backwards, wrapping around from the first widget to the last.
+    shift_tab(&mut ui);
+    assert_eq!(capturing(&ui), Some(Index::Public(BUTTON_A_ID)));
+    shift_tab(&mut ui);
+    assert_eq!(capturing(&ui), Some(Index::Public(BUTTON_C_ID)));
+
+    // The focus ring is drawn around the focused widget, but not once focus moves via the mouse.
+    let ring = ui.theme.focus_ring.unwrap();
+    let is_ring_drawn = |ui: &Ui| ui.render_primitives().any(|primitive| match primitive {
+        Primitive::Lines { color, .. } => color == ring.line_style.get_color(&ui.theme),
+        _ => false,
+    });
+    assert!(is_ring_drawn(&ui));
+    move_mouse_to_widget(&mut ui, Index::Public(BUTTON_A_ID));
+    press_mouse_button(&mut ui, MouseButton::Left);
+    assert_eq!(capturing(&ui), Some(Index::Public(BUTTON_A_ID)));
+    assert!(!is_ring_drawn(&ui));
+}
+
+#[test]
+fn ui_should_not_send_the_text_of_a_focus_cycling_tab_to_the_focused_widget() {
+    use TextEdit;
+    use testing::{press_and_release_key, type_text};
+
+    let mut ui = windowless_ui();
+    ui.win_w = 250.0;
+    ui.win_h = 300.0;
+
+    const CANVAS_ID: widget::Id = widget::Id(0);
+    const TEXT_EDIT_A_ID: widget::Id = widget::Id(1);
+    const TEXT_EDIT_B_ID: widget::Id = widget::Id(2);
+    let (mut a, mut b) = (String::new(), String::new());
+    let mut set_widgets = |ui: &mut Ui| ui.set_widgets(|ref mut ui| {
+        Canvas::new().set(CANVAS_ID, ui);
+        TextEdit::new(&mut a)
+            .w_h(100.0, 20.0)
+            .mid_top_of(CANVAS_ID)
+            .react(|_: &mut String| {})
+            .set(TEXT_EDIT_A_ID, ui);
+        TextEdit::new(&mut b)
+            .w_h(100.0, 20.0)
+            .middle_of(CANVAS_ID)
+            .react(|_: &mut String| {})
+            .set(TEXT_EDIT_B_ID, ui);
+    });
+    set_widgets(&mut ui);
+
+    press_and_release_key(&mut ui, Key::Tab);
+    type_text(&mut ui, "a");
+    set_widgets(&mut ui);
+
+    // A windowing backend produces a `"\t"` text input alongside the `Tab` press.
+    ui.handle_event(Input::Press(input::Button::Keyboard(Key::Tab)));
+    let tab_press = event::Ui::Press(None, event::Press {
+        button: event::Button::Keyboard(Key::Tab),
+        modifiers: input::keyboard::NO_MODIFIER,
+    });
+    assert_event_was_pushed(&ui, tab_press.into());
+    type_text(&mut ui, "\t");
+    ui.handle_event(Input::Release(input::Button::Keyboard(Key::Tab)));
+    let capturing = ui.global_input.current.widget_capturing_keyboard;
+    assert_eq!(capturing, Some(Index::Public(TEXT_EDIT_B_ID)));
+    set_widgets(&mut ui);
+
+    drop(set_widgets);
+    assert_eq!(a, "a");
+    assert_eq!(b, "");
+}
+
+#[test]
+fn ui_should_send_a_tab_to_the_focused_widget_when_focus_cannot_move() {
+    use TextEdit;
+    use testing::{press_and_release_key, type_text};
+
+    let mut ui = windowless_ui();
+    ui.win_w = 250.0;
+    ui.win_h = 300.0;
+
+    const CANVAS_ID: widget::Id = widget::Id(0);
+    const TEXT_EDIT_ID: widget::Id = widget::Id(1);
+    let mut text = String::new();
+    let mut set_widgets = |ui: &mut Ui| ui.set_widgets(|ref mut ui| {
+        Canvas::new().set(CANVAS_ID, ui);
+        TextEdit::new(&mut text)
+            .w_h(100.0, 20.0)
+            .middle_of(CANVAS_ID)
+            .react(|_: &mut String| {})
+            .set(TEXT_EDIT_ID, ui);
+    });
+    set_widgets(&mut ui);
+
+    press_and_release_key(&mut ui, Key::Tab);
+    set_widgets(&mut ui);
+
+    // The only focusable widget is already focused, so it receives the `Tab` and its text.
+    ui.handle_event(Input::Press(input::Button::Keyboard(Key::Tab)));
+    let tab_press = event::Ui::Press(Some(Index::Public(TEXT_EDIT_ID)), event::Press {
+        button: event::Button::Keyboard(Key::Tab),
+        modifiers: input::keyboard::NO_MODIFIER,
+    });
+    assert_event_was_pushed(&ui, tab_press.into());
+    type_text(&mut ui, "\t");
+    ui.handle_event(Input::Release(input::Button::Keyboard(Key::Tab)));
+    set_widgets(&mut ui);
+
+    drop(set_widgets);
+    assert_eq!(text, "\t");
+}
+
+#[test]
+fn ui_should_capture_and_uncapture_input_for_widgets_on_request() {
+    let mut ui = windowless_ui();
+    ui.win_w = 250.0;
+    ui.win_h = 300.0;
+
+    const CANVAS_ID: widget::Id = widget::Id(0);
+    const BUTTON_ID: widget::Id = widget::Id(1);
+    let button_idx = Index::Public(BUTTON_ID);
+    let set_widgets = |ui: &mut Ui, focus: bool| ui.set_widgets(|ref mut ui| {
+        Canvas::new().set(CANVAS_ID, ui);
+        ButtonWidget::new()
+            .w_h(50.0, 50.0)
+            .middle_of(CANVAS_ID)
+            .react(|| {})
+            .set(BUTTON_ID, ui);
+        if focus {
+            ui.capture_keyboard(BUTTON_ID);
+        }
+    });
+
+    // Captures requested during `set_widgets` are received by widgets during the next update.
+    set_widgets(&mut ui, true);
+    assert_eq!(ui.global_input.current.widget_capturing_keyboard, Some(button_idx));
+    assert_event_was_pushed(&ui, event::Ui::WidgetCapturesKeyboard(button_idx).into());
+
+    // Releasing a widget that is not capturing the keyboard has no effect.
+    set_widgets(&mut ui, false);
+    ui.uncapture_keyboard(CANVAS_ID);
+    assert_eq!(ui.global_input.current.widget_capturing_keyboard, Some(button_idx));
+    ui.uncapture_keyboard(BUTTON_ID);
+    assert_eq!(ui.global_input.current.widget_capturing_keyboard, None);
+    assert_event_was_pushed(&ui, event::Ui::WidgetUncapturesKeyboard(button_idx).into());
+
+    // Capturing the mouse for one widget releases it from another.
+    ui.capture_mouse(CANVAS_ID);
+    ui.capture_mouse(BUTTON_ID);
+    assert_eq!(ui.global_input.current.widget_capturing_mouse, Some(button_idx));
+    let uncapture_event = event::Ui::WidgetUncapturesMouse(Index::Public(CANVAS_ID));
+    assert_event_was_pushed(&ui, uncapture_event.into());
+    ui.uncapture_mouse(BUTTON_ID);
+    assert_eq!(ui.global_input.current.widget_capturing_mouse, None);
+}
+
+#[test]
+fn ui_should_only_pick_non_rectangular_widgets_over_their_shape() {
+    let mut ui = windowless_ui();
+    ui.win_w = 200.0;
+    ui.win_h = 200.0;
+
+    const CANVAS_ID: widget::Id = widget::Id(0);
+    const CIRCLE_ID: widget::Id = widget::Id(1);
+    ui.set_widgets(|ref mut ui| {
+        Canvas::new().set(CANVAS_ID, ui);
+        Circle::fill(50.0)
+            .middle_of(CANVAS_ID)
+            .set(CIRCLE_ID, ui);
+    });
+
+    let under_mouse = |ui: &mut Ui, xy| {
+        move_mouse_to(ui, xy);
+        ui.global_input.current.widget_under_mouse
+    };
+
+    assert_eq!(under_mouse(&mut ui, [0.0, 0.0]), Some(Index::Public(CIRCLE_ID)));
+    assert_eq!(under_mouse(&mut ui, [0.0, 49.0]), Some(Index::Public(CIRCLE_ID)));
+    // The corner of the circle's `Rect` lies outside of the circle itself.
+    assert_eq!(under_mouse(&mut ui, [45.0, 45.0]), Some(Index::Public(CANVAS_ID)));
+}
+
+#[test]
+fn ui_should_not_pick_transformed_widgets_where_cropped_by_their_parent() {
+    let mut ui = windowless_ui();
+    ui.win_w = 200.0;
+    ui.win_h = 200.0;
+
+    const CANVAS_ID: widget::Id = widget::Id(0);
+    const RECTANGLE_ID: widget::Id = widget::Id(1);
+    ui.set_widgets(|ref mut ui| {
+        Canvas::new()
+            .w_h(40.0, 40.0)
+            .middle()
+            .scroll_kids()
+            .set(CANVAS_ID, ui);
+        Rectangle::fill([20.0, 20.0])
+            .middle_of(CANVAS_ID)
+            .transform(Transform::translation([20.0, 0.0]))
+            .set(RECTANGLE_ID, ui);
+    });
+
+    let under_mouse = |ui: &mut Ui, xy| {
+        move_mouse_to(ui, xy);
+        ui.global_input.current.widget_under_mouse
+    };
+
+    // The rectangle covers the area from 10 to 30 along the *x* axis, though the canvas crops it
+    // at 20.
+    assert_eq!(under_mouse(&mut ui, [15.0, 0.0]), Some(Index::Public(RECTANGLE_ID)));
+    assert!(under_mouse(&mut ui, [25.0, 0.0]) != Some(Index::Public(RECTANGLE_ID)));
+}
+
+#[test]
+fn ui_should_only_pick_outlined_shapes_over_their_outline() {
+    let mut ui = windowless_ui();
+    ui.win_w = 200.0;
+    ui.win_h = 200.0;
+
+    const CANVAS_ID: widget::Id = widget::Id(0);
+    const CIRCLE_ID: widget::Id = widget::Id(1);
+    const POLYGON_ID: widget::Id = widget::Id(2);
+    const ARC_ID: widget::Id = widget::Id(3);
+    const ROUNDED_RECTANGLE_ID: widget::Id = widget::Id(4);
+    ui.set_widgets(|ref mut ui| {
+        Canvas::new().set(CANVAS_ID, ui);
+        Circle::outline(40.0)
+            .x_y(-50.0, 0.0)
+            .set(CIRCLE_ID, ui);
+        Polygon::outline(vec![[0.0, 0.0], [40.0, 0.0], [40.0, 40.0], [0.0, 40.0]])
+            .x_y(50.0, 0.0)
+            .set(POLYGON_ID, ui);
+        Arc::outline(30.0, 0.0, ::std::f64::consts::PI)
+            .x_y(-50.0, -60.0)
+            .set(ARC_ID, ui);
+        RoundedRectangle::outline([40.0, 40.0], 10.0)
+            .x_y(50.0, -60.0)
+            .set(ROUNDED_RECTANGLE_ID, ui);
+    });
+
+    let under_mouse = |ui: &mut Ui, xy| {
+        move_mouse_to(ui, xy);
+        ui.global_input.current.widget_under_mouse
+    };
+
+    // The stroke of each shape may be picked, but not the area that it encloses.
+    assert_eq!(under_mouse(&mut ui, [-50.0, 40.0]), Some(Index::Public(CIRCLE_ID)));
+    assert_eq!(under_mouse(&mut ui, [-50.0, 0.0]), Some(Index::Public(CANVAS_ID)));
+    assert_eq!(under_mouse(&mut ui, [30.0, 0.0]), Some(Index::Public(POLYGON_ID)));
+    assert_eq!(under_mouse(&mut ui, [50.0, 0.0]), Some(Index::Public(CANVAS_ID)));
+    assert_eq!(under_mouse(&mut ui, [-50.0, -30.0]), Some(Index::Public(ARC_ID)));
+    assert_eq!(under_mouse(&mut ui, [-50.0, -45.0]), Some(Index::Public(CANVAS_ID)));
+    assert_eq!(under_mouse(&mut ui, [30.0, -60.0]), Some(Index::Public(ROUNDED_RECTANGLE_ID)));
+    assert_eq!(under_mouse(&mut ui, [50.0, -60.0]), Some(Index::Public(CANVAS_ID)));
+}
+
+#[test]
+fn ui_should_pick_transformed_widgets_at_their_transformed_position() {
+    let mut ui = windowless_ui();
+    ui.win_w = 200.0;
+    ui.win_h = 200.0;
+
+    const CANVAS_ID: widget::Id = widget::Id(0);
+    const RECTANGLE_ID: widget::Id = widget::Id(1);
+    const CHILD_ID: widget::Id = widget::Id(2);
+    ui.set_widgets(|ref mut ui| {
+        Canvas::new().set(CANVAS_ID, ui);
+        Rectangle::fill([20.0, 20.0])
+            .middle_of(CANVAS_ID)
+            .transform(Transform::scale(2.0).translate([50.0, 0.0]))
+            .set(RECTANGLE_ID, ui);
+        Rectangle::fill([4.0, 4.0])
+            .top_right_of(RECTANGLE_ID)
+            .set(CHILD_ID, ui);
+    });
+
+    let under_mouse = |ui: &mut Ui, xy| {
+        move_mouse_to(ui, xy);
+        ui.global_input.current.widget_under_mouse
+    };
+
+    // The rectangle now covers the area from 30 to 70 along the *x* axis.
+    assert_eq!(under_mouse(&mut ui, [60.0, 0.0]), Some(Index::Public(RECTANGLE_ID)));
+    assert_eq!(under_mouse(&mut ui, [0.0, 0.0]), Some(Index::Public(CANVAS_ID)));
+    // The child inherits the transform of its parent.
+    assert_eq!(under_mouse(&mut ui, [66.0, 16.0]), Some(Index::Public(CHILD_ID)));
+    assert_eq!(under_mouse(&mut ui, [8.0, 8.0]), Some(Index::Public(CANVAS_ID)));
+}
+
+#[test]
+fn software_backend_should_rotate_a_transformed_widget_about_its_centre() {
+    let mut ui = snapshot::windowless_ui([40.0, 40.0]);
+
+    // A square rotated by 45 degrees, producing a diamond.
+    const CANVAS_ID: widget::Id = widget::Id(0);
+    const RECTANGLE_ID: widget::Id = widget::Id(1);
+    ui.set_widgets(|ref mut ui| {
+        Canvas::new()
+            .color(color::BLACK)
+            .set(CANVAS_ID, ui);
+        Rectangle::fill([20.0, 20.0])
+            .middle_of(CANVAS_ID)
+            .color(color::WHITE)
+            .transform(Transform::rotation(::std::f64::consts::PI / 4.0))
+            .set(RECTANGLE_ID, ui);
+    });
+
+    let image = snapshot::render(&ui);
+
+    // The top corner of the diamond extends beyond the un-rotated square.
+    assert_eq!(image.pixel(20, 7), Some([255; 4]));
+    // The corners of the un-rotated square show the canvas beneath.
+    assert_eq!(image.pixel(29, 10), Some(color::BLACK.to_byte_fsa()));
+    assert_eq!(image.pixel(10, 29), Some(color::BLACK.to_byte_fsa()));
+}
+
+#[test]
+fn software_backend_should_fade_a_widget_and_its_children_by_their_composed_opacity() {
+    let mut ui = snapshot::windowless_ui([40.0, 40.0]);
+
+    const BACKGROUND_ID: widget::Id = widget::Id(0);
+    const PANEL_ID: widget::Id = widget::Id(1);
+    const RECTANGLE_ID: widget::Id = widget::Id(2);
+    ui.set_widgets(|ref mut ui| {
+        Canvas::new()
+            .frame(0.0)
+            .color(color::WHITE)
+            .set(BACKGROUND_ID, ui);
+        Canvas::new()
+            .frame(0.0)
+            .w_h(20.0, 20.0)
+            .middle_of(BACKGROUND_ID)
+            .color(color::BLACK)
+            .opacity(0.5)
+            .set(PANEL_ID, ui);
+        Rectangle::fill([10.0, 10.0])
+            .middle_of(PANEL_ID)
+            .color(color::rgb(1.0, 0.0, 0.0))
+            .opacity(0.5)
+            .set(RECTANGLE_ID, ui);
+    });
+
+    let image = snapshot::render(&ui);
+
+    assert_eq!(image.pixel(2, 20), Some([255; 4]));
+    // The panel is drawn at half opacity.
+    assert_eq!(image.pixel(12, 20), Some([128, 128, 128, 255]));
+    // The rectangle is drawn at a quarter of its opacity over the faded panel.
+    assert_eq!(image.pixel(20, 20), Some([160, 96, 96, 255]));
+}
+
+#[test]
+fn ui_should_neither_pick_nor_draw_hidden_widgets_but_keep_their_state() {
+    use render::Primitive;
+
+    let mut ui = windowless_ui();
+    ui.win_w = 200.0;
+    ui.win_h = 200.0;
+
+    const CANVAS_ID: widget::Id = widget::Id(0);
+    const PANEL_ID: widget::Id = widget::Id(1);
+    const RECTANGLE_ID: widget::Id = widget::Id(2);
+    let set_widgets = |ui: &mut Ui, hidden: bool| ui.set_widgets(|ref mut ui| {
+        Canvas::new().set(CANVAS_ID, ui);
+        Canvas::new()
+            .w_h(100.0, 100.0)
+            .middle_of(CANVAS_ID)
+            .hidden(hidden)
+            .set(PANEL_ID, ui);
+        Rectangle::fill([20.0, 20.0])
+            .middle_of(PANEL_ID)
+            .set(RECTANGLE_ID, ui);
+    });
+
+    let under_mouse = |ui: &mut Ui, xy| {
+        move_mouse_to(ui, xy);
+        ui.global_input.current.widget_under_mouse
+    };
+
+    set_widgets(&mut ui, true);
+    assert_eq!(under_mouse(&mut ui, [0.0, 0.0]), Some(Index::Public(CANVAS_ID)));
+    assert_eq!(under_mouse(&mut ui, [40.0, 40.0]), Some(Index::Public(CANVAS_ID)));
+    let is_rectangle_drawn = |ui: &Ui| ui.render_primitives().any(|p| match p {
+        Primitive::Rectangle { rect, .. } => rect == Rect::from_xy_dim([0.0, 0.0], [20.0, 20.0]),
+        _ => false,
+    });
+    assert!(!is_rectangle_drawn(&ui));
+    // The hidden child retains its unique state.
+    let has_state = ui.widget_graph().widget(RECTANGLE_ID)
+        .and_then(|container| container.unique_widget_state::<Rectangle>())
+        .is_some();
+    assert!(has_state);
+
+    set_widgets(&mut ui, false);
+    assert_eq!(under_mouse(&mut ui, [0.0, 0.0]), Some(Index::Public(RECTANGLE_ID)));
+    assert_eq!(under_mouse(&mut ui, [40.0, 40.0]), Some(Index::Public(PANEL_ID)));
+    assert!(is_rectangle_drawn(&ui));
+}
+
 #[test]
 fn ui_should_push_input_events_to_aggregator() {
     let mut ui = windowless_ui();
@@ -219,3 +583,247 @@ fn high_level_scroll_event_should_be_created_from_a_raw_mouse_scroll() {
         assert_eq!(expected_scroll, scroll);
     }
 }
+
+#[test]
+fn ui_should_render_primitives_in_depth_order() {
+    use render::Primitive;
+
+    let mut ui = windowless_ui();
+    ui.win_w = 250.0;
+    ui.win_h = 300.0;
+
+    const CANVAS_ID: widget::Id = widget::Id(0);
+    const BUTTON_ID: widget::Id = widget::Id(1);
+    ui.set_widgets(|ref mut ui| {
+
+        Canvas::new()
+            .color(Color::Rgba(1.0, 1.0, 1.0, 1.0))
+            .set(CANVAS_ID, ui);
+        ButtonWidget::new()
+            .w_h(100.0, 200.0)
+            .label("MyButton")
+            .react(|| {})
+            .bottom_right_of(CANVAS_ID)
+            .set(BUTTON_ID, ui);
+    });
+
+    let primitives: Vec<_> = ui.render_primitives().collect();
+
+    // The canvas is the deepest widget, so its rectangle should be drawn first.
+    match primitives.first() {
+        Some(&Primitive::Rectangle { .. }) => (),
+        _ => panic!("expected the canvas rectangle to be the first primitive"),
+    }
+
+    // The button's label should be drawn last, on top of the button's rectangle.
+    match primitives.last() {
+        Some(&Primitive::Text { text, .. }) => assert_eq!(text, "MyButton"),
+        _ => panic!("expected the button label to be the last primitive"),
+    }
+}
+
+#[test]
+fn ui_should_show_a_tooltip_above_other_widgets_once_its_target_is_hovered() {
+    use render::Primitive;
+    use std::time::Duration;
+    use Tooltip;
+
+    let mut ui = windowless_ui();
+    ui.win_w = 250.0;
+    ui.win_h = 300.0;
+    ui.theme.tooltip_delay = Duration::from_millis(0);
+
+    const CANVAS_ID: widget::Id = widget::Id(0);
+    const BUTTON_ID: widget::Id = widget::Id(1);
+    const TOOLTIP_ID: widget::Id = widget::Id(2);
+    const FLOATING_ID: widget::Id = widget::Id(3);
+    let set_widgets = |ui: &mut Ui| ui.set_widgets(|ref mut ui| {
+        Canvas::new().set(CANVAS_ID, ui);
+        ButtonWidget::new()
+            .w_h(50.0, 50.0)
+            .middle_of(CANVAS_ID)
+            .react(|| {})
+            .set(BUTTON_ID, ui);
+        Tooltip::new(BUTTON_ID, "Tip").set(TOOLTIP_ID, ui);
+        // Neither instantiation order nor depth place other floating widgets above the popup.
+        Rectangle::fill([20.0, 20.0])
+            .top_left_of(CANVAS_ID)
+            .floating(true)
+            .depth(::std::f32::MIN)
+            .set(FLOATING_ID, ui);
+    });
+    let is_tooltip_last = |ui: &Ui| match ui.render_primitives().last() {
+        Some(Primitive::Text { text, .. }) => text == "Tip",
+        _ => false,
+    };
+
+    set_widgets(&mut ui);
+    assert!(!is_tooltip_last(&ui));
+
+    // Once the mouse rests over the button, the popup is drawn on top of everything else.
+    move_mouse_to_widget(&mut ui, Index::Public(BUTTON_ID));
+    set_widgets(&mut ui);
+    assert!(is_tooltip_last(&ui));
+
+    // The popup appears just below and to the right of the cursor, but does not steal the hover
+    // from its target when the mouse moves over it.
+    move_mouse_to(&mut ui, [16.0, -24.0]);
+    set_widgets(&mut ui);
+    assert_eq!(ui.global_input.current.widget_under_mouse, Some(Index::Public(BUTTON_ID)));
+    assert!(is_tooltip_last(&ui));
+}
+
+#[test]
+fn ui_should_only_show_a_tooltip_once_the_mouse_has_rested_for_the_delay() {
+    use render::Primitive;
+    use std::time::{Duration, Instant};
+    use Tooltip;
+
+    let mut ui = windowless_ui();
+    ui.win_w = 250.0;
+    ui.win_h = 300.0;
+    let delay = Duration::from_secs(60);
+    ui.theme.tooltip_delay = delay;
+
+    const CANVAS_ID: widget::Id = widget::Id(0);
+    const BUTTON_ID: widget::Id = widget::Id(1);
+    const TOOLTIP_ID: widget::Id = widget::Id(2);
+    let set_widgets = |ui: &mut Ui| ui.set_widgets(|ref mut ui| {
+        Canvas::new().set(CANVAS_ID, ui);
+        ButtonWidget::new()
+            .w_h(50.0, 50.0)
+            .middle_of(CANVAS_ID)
+            .react(|| {})
+            .set(BUTTON_ID, ui);
+        Tooltip::new(BUTTON_ID, "Tip").set(TOOLTIP_ID, ui);
+    });
+    let is_tooltip_drawn = |ui: &Ui| ui.render_primitives().any(|primitive| match primitive {
+        Primitive::Text { text, .. } => text == "Tip",
+        _ => false,
+    });
+
+    set_widgets(&mut ui);
+    move_mouse_to_widget(&mut ui, Index::Public(BUTTON_ID));
+    set_widgets(&mut ui);
+    assert!(!is_tooltip_drawn(&ui));
+
+    // Hovering the target for the delay is not enough while the mouse keeps moving.
+    let long_ago = Instant::now() - delay;
+    ui.global_input.current.widget_under_mouse_since = Some(long_ago);
+    move_mouse_to(&mut ui, [5.0, 5.0]);
+    set_widgets(&mut ui);
+    assert!(!is_tooltip_drawn(&ui));
+
+    // Once the mouse has rested for the delay, the popup is shown and remains so while the mouse
+    // moves within the target.
+    ui.global_input.last_mouse_move = Some(long_ago);
+    set_widgets(&mut ui);
+    assert!(is_tooltip_drawn(&ui));
+    move_mouse_to(&mut ui, [0.0, 0.0]);
+    set_widgets(&mut ui);
+    assert!(is_tooltip_drawn(&ui));
+}
+
+#[test]
+fn ui_should_drop_an_accepted_drag_and_drop_payload_onto_the_target_widget() {
+    let mut ui = windowless_ui();
+    ui.win_w = 250.0;
+    ui.win_h = 300.0;
+
+    const CANVAS_ID: widget::Id = widget::Id(0);
+    const SOURCE_ID: widget::Id = widget::Id(1);
+    const TARGET_ID: widget::Id = widget::Id(2);
+    const PREVIEW_ID: widget::Id = widget::Id(3);
+    let set_widgets = |ui: &mut Ui, dropped: &mut Option<u32>| ui.set_widgets(|ref mut ui| {
+        Canvas::new().set(CANVAS_ID, ui);
+        ButtonWidget::new()
+            .w_h(50.0, 50.0)
+            .mid_left_of(CANVAS_ID)
+            .react(|| {})
+            .set(SOURCE_ID, ui);
+        ButtonWidget::new()
+            .w_h(50.0, 50.0)
+            .mid_right_of(CANVAS_ID)
+            .react(|| {})
+            .set(TARGET_ID, ui);
+
+        if ui.widget_input(SOURCE_ID).drags().left().next().is_some() {
+            ui.start_drag(SOURCE_ID, Box::new(42u32));
+        }
+        if ui.global_input().dragging().is_some() {
+            let mouse_xy = ui.global_input().current.mouse.xy;
+            Rectangle::fill([20.0, 20.0])
+                .xy(mouse_xy)
+                .floating(true)
+                .graphics_for(SOURCE_ID)
+                .set(PREVIEW_ID, ui);
+        }
+        let is_u32 = ui.widget_input(TARGET_ID).drag_payload().map_or(false, |p| p.is::<u32>());
+        let events: Vec<_> = ui.widget_input(TARGET_ID).events().collect();
+        for event in events {
+            match event {
+                event::Widget::DragEnter(_) if is_u32 => ui.accept_drop(TARGET_ID),
+                event::Widget::Drop(_) => {
+                    let payload = ui.take_dropped_payload(TARGET_ID).unwrap();
+                    *dropped = payload.downcast::<u32>().ok().map(|n| *n);
+                },
+                _ => (),
+            }
+        }
+    });
+
+    let mut dropped = None;
+    set_widgets(&mut ui, &mut dropped);
+
+    // Dragging the source begins the drag-and-drop.
+    move_mouse_to_widget(&mut ui, Index::Public(SOURCE_ID));
+    press_mouse_button(&mut ui, MouseButton::Left);
+    let source_xy = ui.xy_of(SOURCE_ID).unwrap();
+    move_mouse_to(&mut ui, [source_xy[0] + 5.0, source_xy[1]]);
+    set_widgets(&mut ui, &mut dropped);
+    assert!(ui.global_input.dragging().is_some());
+
+    // The target receives a `DragEnter` and accepts the payload, despite the preview under the
+    // mouse.
+    move_mouse_to_widget(&mut ui, Index::Public(TARGET_ID));
+    let is_drag_enter = |ui: &Ui| ui.global_input.events().any(|event| match *event {
+        event::Event::Ui(event::Ui::DragEnter(idx, drag_and_drop)) =>
+            idx == Index::Public(TARGET_ID) && drag_and_drop.source == Index::Public(SOURCE_ID),
+        _ => false,
+    });
+    assert!(is_drag_enter(&ui));
+    set_widgets(&mut ui, &mut dropped);
+    assert!(ui.global_input.dragging().unwrap().is_accepted);
+
+    // The preview now under the mouse is never considered a target itself.
+    let target_xy = ui.xy_of(TARGET_ID).unwrap();
+    move_mouse_to(&mut ui, [target_xy[0] + 1.0, target_xy[1]]);
+    assert_eq!(ui.global_input.dragging().unwrap().maybe_target, Some(Index::Public(TARGET_ID)));
+    assert!(ui.global_input.dragging().unwrap().is_accepted);
+
+    // Releasing the mouse drops the payload onto the target.
+    release_mouse_button(&mut ui, MouseButton::Left);
+    assert!(ui.global_input.dragging().is_none());
+    set_widgets(&mut ui, &mut dropped);
+    assert_eq!(dropped, Some(42));
+}
+
+#[test]
+fn ui_should_cancel_drag_and_drop_when_escape_is_pressed() {
+    use testing::press_and_release_key;
+
+    let mut ui = windowless_ui();
+    let (source, target) = (Index::Public(widget::Id(1)), Index::Public(widget::Id(2)));
+    ui.global_input.start_drag(source, Box::new("payload"));
+    ui.global_input.set_drag_target(Some(target));
+    ui.global_input.accept_drop(target);
+
+    press_and_release_key(&mut ui, Key::Escape);
+    assert!(ui.global_input.dragging().is_none());
+    let is_drag_leave = ui.global_input.events().any(|event| match *event {
+        event::Event::Ui(event::Ui::DragLeave(idx, _)) => idx == target,
+        _ => false,
+    });
+    assert!(is_drag_leave);
+    assert!(ui.global_input.take_dropped_payload(target).is_none());
+}
@@ -46,3 +46,48 @@ fn widget_input_should_provide_any_mouse_events_over_the_widgets_area_if_nothing
 
     assert!(another_widget_input.clicks().left().next().is_none());
 }
+
+#[test]
+fn widgets_should_receive_enter_and_leave_events_as_the_widget_under_mouse_changes() {
+    let mut global_input = input::Global::new();
+    let (a, b) = (Index::Public(Id(1)), Index::Public(Id(2)));
+    let area = Rect::from_corners([0.0, 0.0], [40.0, 40.0]);
+
+    global_input.set_widget_under_mouse(Some(a));
+    global_input.set_widget_under_mouse(Some(b));
+
+    let a_input = input::Widget::for_widget(a, area, &global_input);
+    let a_events: Vec<_> = a_input.events().collect();
+    assert_eq!(a_events, vec![event::Widget::MouseEnter, event::Widget::MouseLeave]);
+    assert!(a_input.hover_start().is_none());
+
+    let b_input = input::Widget::for_widget(b, area, &global_input);
+    let b_events: Vec<_> = b_input.events().collect();
+    assert_eq!(b_events, vec![event::Widget::MouseEnter]);
+    assert!(b_input.hover_start().is_some());
+
+    // Setting the same widget again neither emits events nor resets the hover start.
+    let hover_start = b_input.hover_start();
+    global_input.set_widget_under_mouse(Some(b));
+    assert_eq!(global_input.events().count(), 3);
+    assert_eq!(input::Widget::for_widget(b, area, &global_input).hover_start(), hover_start);
+}
+
+#[test]
+fn scrolls_should_be_rotated_and_scaled_into_a_transformed_widgets_space() {
+    let mut global_input = input::Global::new();
+    let widget = Index::Public(Id(3));
+    push_event(&mut global_input, event::Ui::Scroll(Some(widget), event::Scroll {
+        x: 0.0,
+        y: 10.0,
+        modifiers: NO_MODIFIER,
+    }).into());
+
+    // Rotated a quarter turn anti-clockwise, doubled in size and translated.
+    let matrix = [[0.0, -2.0, 30.0], [2.0, 0.0, 30.0]];
+    let area = Rect::from_corners([0.0, 0.0], [40.0, 40.0]);
+    let widget_input = input::Widget::for_widget(widget, area, &global_input).transformed(matrix);
+
+    let scroll = widget_input.scrolls().next().expect("expected a scroll event");
+    assert_eq!([scroll.x, scroll.y], [5.0, 0.0]);
+}
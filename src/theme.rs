@@ -3,7 +3,7 @@
 //!
 
 use Scalar;
-use color::{Color, BLACK, WHITE};
+use color::{Color, BLACK, LIGHT_BLUE, WHITE};
 use position::{Align, Direction, Padding, Position};
 use std;
 use std::any::Any;
@@ -45,6 +45,20 @@ pub struct Theme {
     /// Once the `Duration` that separates two consecutive `Click`s is greater than this value, a
     /// `DoubleClick` event will no longer be generated.
     pub double_click_threshold: std::time::Duration,
+    /// The `Duration` for which the mouse must rest over a widget before its **Tooltip** is shown.
+    pub tooltip_delay: std::time::Duration,
+    /// The ring drawn around the widget capturing the keyboard once focus has been moved with
+    /// `Tab`. If `None`, no ring is drawn.
+    pub focus_ring: Option<FocusRing>,
+}
+
+/// Styling for the ring drawn around the widget that has keyboard focus.
+#[derive(Copy, Clone, Debug)]
+pub struct FocusRing {
+    /// The style of the line outlining the focused widget.
+    pub line_style: widget::primitive::line::Style,
+    /// The distance between the edges of the focused widget's **Rect** and the ring.
+    pub padding: Scalar,
 }
 
 /// The defaults for a specific widget.
@@ -94,6 +108,13 @@ impl Theme {
             widget_styling: HashMap::new(),
             mouse_drag_threshold: 0.0,
             double_click_threshold: std::time::Duration::from_millis(500),
+            tooltip_delay: std::time::Duration::from_millis(500),
+            focus_ring: Some(FocusRing {
+                line_style: widget::primitive::line::Style::solid()
+                    .color(LIGHT_BLUE)
+                    .thickness(2.0),
+                padding: 2.0,
+            }),
         }
     }
 
@@ -7,12 +7,14 @@ use glyph_cache::GlyphCache;
 use graph::{self, Graph, NodeIndex};
 use position::{Align, Direction, Dimensions, Padding, Place, Point, Position, Range, Rect};
 use std;
+use std::any::Any;
 use std::collections::HashSet;
 use std::marker::PhantomData;
 use theme::Theme;
 use utils;
 use widget::{self, Widget};
 use input;
+use render;
 
 
 /// `Ui` is the most important type within Conrod and is necessary for rendering and maintaining
@@ -65,6 +67,18 @@ pub struct Ui<B>
     /// the end of the `Ui::set_widgets` method. This ensures that the events are received by the
     /// target widgets during the next call to `Ui::set_widgets`.
     pending_scroll_events: Vec<event::Ui>,
+    /// Keyboard and mouse capture changes requested during a call to `Ui::set_widgets`.
+    ///
+    /// Like the `pending_scroll_events`, these are applied at the end of the `Ui::set_widgets`
+    /// method so that the resulting events are received by widgets during the next update.
+    pending_captures: Vec<Capture>,
+    /// Whether or not keyboard focus was last moved via `Tab`, in which case a focus ring is drawn
+    /// around the widget capturing the keyboard.
+    is_focus_visible: bool,
+    /// Whether or not the `"\t"` **Text** input produced by a `Tab` press that moved keyboard focus
+    /// has yet to be received, in which case it is ignored rather than sent to the newly focused
+    /// widget.
+    is_tab_text_pending: bool,
 
     // TODO: Remove the following fields as they should now be handled by `input::Global`.
 
@@ -97,6 +111,15 @@ pub struct UiCell<'a, B: 'a>
 /// buffer. Otherwise if we don't draw into each buffer, we will probably be subject to flickering.
 pub const SAFE_REDRAW_COUNT: u8 = 3;
 
+/// A change in keyboard or mouse capture requested via the **UiCell**.
+#[derive(Copy, Clone, Debug)]
+enum Capture {
+    Keyboard(widget::Index),
+    UncaptureKeyboard(widget::Index),
+    Mouse(widget::Index),
+    UncaptureMouse(widget::Index),
+}
+
 
 impl<B> Ui<B>
     where B: Backend,
@@ -148,6 +171,9 @@ impl<B> Ui<B>
             prev_updated_widgets: prev_updated_widgets,
             global_input: input::Global::new(),
             pending_scroll_events: Vec::new(),
+            pending_captures: Vec::new(),
+            is_focus_visible: false,
+            is_tab_text_pending: false,
         }
     }
 
@@ -163,7 +189,8 @@ impl<B> Ui<B>
             let bottom_edge = self.win_h / 2.0;
             Rect::from_xy_dim([right_edge, bottom_edge], [0.0, 0.0])
         });
-        input::Widget::for_widget(idx, rect, &self.global_input)
+        let transform = graph::algo::transform_of_widget(&self.widget_graph, idx);
+        input::Widget::for_widget(idx, rect, &self.global_input).transformed(transform)
     }
 
     /// The **Rect** for the widget at the given index.
@@ -293,10 +320,13 @@ impl<B> Ui<B>
         //
         // Note: This function expects that `ui.global_input.current.mouse.xy` is up-to-date.
         fn track_widget_under_mouse_and_update_capturing<B: Backend>(ui: &mut Ui<B>) {
-            ui.global_input.current.widget_under_mouse =
+            let widget_under_mouse =
                 graph::algo::pick_widgets(&ui.depth_order.indices,
                                           ui.global_input.current.mouse.xy)
-                                          .next(&ui.widget_graph, &ui.depth_order.indices);
+                                          .next(&ui.widget_graph,
+                                                &ui.depth_order.indices,
+                                                &ui.theme);
+            ui.global_input.set_widget_under_mouse(widget_under_mouse);
 
             // If MouseButton::Left is up and `widget_under_mouse` has changed, capture new widget
             // under mouse.
@@ -323,6 +353,90 @@ impl<B> Ui<B>
             }
         }
 
+        // Determines the widget over which the current drag-and-drop payload is being dragged.
+        //
+        // The source widget is skipped, along with any of its graphics such as a drag preview
+        // drawn under the mouse, so that the payload may be dropped onto whatever lies beneath.
+        fn track_drag_target<B: Backend>(ui: &mut Ui<B>) {
+            use daggy::Walker;
+
+            let source = match ui.global_input.dragging() {
+                Some(dragging) => dragging.source,
+                None => return,
+            };
+            let maybe_target = {
+                let Ui { ref widget_graph, ref depth_order, ref theme, ref global_input, .. } = *ui;
+                let mut widgets =
+                    graph::algo::pick_widgets(&depth_order.indices, global_input.current.mouse.xy);
+                let mut maybe_target = None;
+                while let Some(idx) = widgets.next_node_index(widget_graph,
+                                                              &depth_order.indices,
+                                                              theme) {
+                    // Skip the source along with any of its graphical elements, such as the
+                    // preview, otherwise targeting the top-most graphic parent of the widget.
+                    let mut is_of_source = widget_graph.widget_index(idx) == Some(source);
+                    let mut graphic_parent = idx;
+                    let mut graphic_parents = widget_graph.graphic_parent_recursion(idx);
+                    while let Some(parent) = graphic_parents.next_node(widget_graph) {
+                        is_of_source |= widget_graph.widget_index(parent) == Some(source);
+                        graphic_parent = parent;
+                    }
+                    if !is_of_source {
+                        maybe_target = widget_graph.widget_index(graphic_parent);
+                        break;
+                    }
+                }
+                maybe_target
+            };
+            ui.global_input.set_drag_target(maybe_target);
+        }
+
+        // Moves keyboard focus to the next focusable widget in instantiation order (or the
+        // previous widget if `reverse` is `true`), wrapping around at either end.
+        //
+        // Returns whether or not keyboard focus moved to some other widget.
+        fn cycle_keyboard_focus<B: Backend>(ui: &mut Ui<B>, reverse: bool) -> bool {
+            // Only visible widgets that were updated during the last `set_widgets` are focusable.
+            let mut focusable: Vec<(usize, NodeIndex)> = ui.depth_order.indices.iter()
+                .filter(|idx| ui.updated_widgets.contains(idx))
+                .filter_map(|&idx| ui.widget_graph.widget(idx).and_then(|container| {
+                    if container.is_focusable {
+                        Some((container.instantiation_order_idx, idx))
+                    } else {
+                        None
+                    }
+                }))
+                .collect();
+            focusable.sort_by_key(|&(order, _)| order);
+
+            let maybe_order = ui.global_input.current.widget_capturing_keyboard
+                .and_then(|idx| ui.widget_graph.widget(idx))
+                .map(|container| container.instantiation_order_idx);
+
+            let maybe_next = match (maybe_order, reverse) {
+                (Some(order), false) => focusable.iter()
+                    .find(|&&(o, _)| o > order)
+                    .or(focusable.first()),
+                (Some(order), true) => focusable.iter().rev()
+                    .find(|&&(o, _)| o < order)
+                    .or(focusable.last()),
+                (None, false) => focusable.first(),
+                (None, true) => focusable.last(),
+            };
+            let next = match maybe_next {
+                Some(&(_, idx)) => idx,
+                None => return false,
+            };
+            if maybe_order == ui.widget_graph.widget(next).map(|c| c.instantiation_order_idx) {
+                return false;
+            }
+
+            ui.capture_keyboard(next);
+            ui.is_focus_visible = true;
+            ui.needs_redraw();
+            true
+        }
+
         // A function for filtering `ModifierKey`s.
         fn filter_modifier(key: Key) -> Option<ModifierKey> {
             use backend::event::keyboard::{CTRL, SHIFT, ALT, GUI};
@@ -389,6 +503,9 @@ impl<B> Ui<B>
                             self.global_input.push_event(press_event);
 
                             if let MouseButton::Left = mouse_button {
+                                // Focus moved via the mouse does not show the focus ring.
+                                self.is_focus_visible = false;
+
                                 // Check to see if we need to uncapture the keyboard.
                                 if let Some(idx) = self.global_input.current.widget_capturing_keyboard {
                                     if Some(idx) != self.global_input.current.widget_under_mouse {
@@ -413,15 +530,25 @@ impl<B> Ui<B>
                         },
 
                         Button::Keyboard(key) => {
+                            use backend::event::keyboard::{NO_MODIFIER, SHIFT};
+
+                            // `Tab` and `Shift+Tab` move keyboard focus between focusable widgets.
+                            let modifiers = self.global_input.current.modifiers;
+                            let widget = self.global_input.current.widget_capturing_keyboard;
+                            let has_moved_focus = key == Key::Tab
+                                && (modifiers == NO_MODIFIER || modifiers == SHIFT)
+                                && cycle_keyboard_focus(self, modifiers == SHIFT);
 
-                            // Create a keyboard `Press` event.
+                            // Create a keyboard `Press` event. A `Tab` that moved keyboard focus
+                            // is not sent to the widget that lost focus, nor is its text.
                             let press = event::Press {
                                 button: event::Button::Keyboard(key),
-                                modifiers: self.global_input.current.modifiers,
+                                modifiers: modifiers,
                             };
-                            let widget = self.global_input.current.widget_capturing_keyboard;
+                            let widget = if has_moved_focus { None } else { widget };
                             let press_event = event::Ui::Press(widget, press).into();
                             self.global_input.push_event(press_event);
+                            self.is_tab_text_pending = has_moved_focus;
 
                             // If some modifier key was pressed, add it to the current modifiers.
                             if let Some(modifier) = filter_modifier(key) {
@@ -431,6 +558,8 @@ impl<B> Ui<B>
                             // If `Esc` was pressed, check to see if we need to cancel a `Drag` or
                             // uncapture a widget.
                             if let Key::Escape = key {
+                                // Cancel any drag-and-drop that is currently under way.
+                                self.global_input.cancel_drag();
                                 // TODO:
                                 // 1. Cancel `Drag` if currently under way.
                                 // 2. If mouse is captured due to pinning widget with left mouse button,
@@ -529,6 +658,9 @@ impl<B> Ui<B>
                             // Uncapture widget capturing mouse if MouseButton::Left is down and
                             // widget_under_mouse != capturing widget.
                             if let MouseButton::Left = mouse_button {
+                                // Releasing the left mouse button ends any drag-and-drop.
+                                self.global_input.drop_payload();
+
                                 if let Some(idx) = self.global_input.current.widget_capturing_mouse {
                                     if Some(idx) != self.global_input.current.widget_under_mouse {
                                         let event = event::Ui::WidgetUncapturesMouse(idx).into();
@@ -595,6 +727,7 @@ impl<B> Ui<B>
                         match motion {
 
                             Motion::MouseCursor(x, y) => {
+                                self.global_input.last_mouse_move = Some(std::time::Instant::now());
 
                                 // Check for drag events.
                                 let last_mouse_xy = self.global_input.current.mouse.xy;
@@ -627,6 +760,7 @@ impl<B> Ui<B>
                                 self.global_input.current.mouse.xy = mouse_xy;
 
                                 track_widget_under_mouse_and_update_capturing(self);
+                                track_drag_target(self);
                             },
 
                             // The mouse was scrolled.
@@ -645,7 +779,8 @@ impl<B> Ui<B>
                                 // direction.
                                 while let Some(idx) =
                                     scrollable_widgets.next(&self.widget_graph,
-                                                            &self.depth_order.indices)
+                                                            &self.depth_order.indices,
+                                                            &self.theme)
                                 {
 
                                     let (kid_area, maybe_x_scroll, maybe_y_scroll) =
@@ -756,6 +891,13 @@ impl<B> Ui<B>
                     },
 
                     Input::Text(string) => {
+                        // The text produced by a `Tab` that moved keyboard focus is ignored.
+                        let is_tab_text = ::std::mem::replace(&mut self.is_tab_text_pending, false)
+                            && string == "\t";
+                        if is_tab_text {
+                            return;
+                        }
+
                         // Create a `Text` event.
                         let text = event::Text {
                             string: string,
@@ -951,6 +1093,120 @@ impl<B> Ui<B>
         for scroll_event in self.pending_scroll_events.drain(0..) {
             self.global_input.push_event(scroll_event.into());
         }
+
+        // Apply any capture changes requested by widgets or the user during this method.
+        for capture in std::mem::replace(&mut self.pending_captures, Vec::new()) {
+            match capture {
+                Capture::Keyboard(idx) => self.capture_keyboard(idx),
+                Capture::UncaptureKeyboard(idx) => self.uncapture_keyboard(idx),
+                Capture::Mouse(idx) => self.capture_mouse(idx),
+                Capture::UncaptureMouse(idx) => self.uncapture_mouse(idx),
+            }
+        }
+
+        // Hidden widgets (along with the children of hidden widgets) are absent from the depth
+        // order, so they should no longer be under the mouse or capture any input.
+        {
+            let Ui {
+                ref widget_graph,
+                ref depth_order,
+                ref updated_widgets,
+                ref theme,
+                ref mut global_input,
+                ..
+            } = *self;
+
+            let is_hidden = |idx: Option<widget::Index>| idx
+                .and_then(|idx| widget_graph.node_index(idx))
+                .map(|idx| updated_widgets.contains(&idx) && !depth_order.indices.contains(&idx))
+                .unwrap_or(false);
+
+            if is_hidden(global_input.current.widget_under_mouse) {
+                let widget_under_mouse =
+                    graph::algo::pick_widgets(&depth_order.indices, global_input.current.mouse.xy)
+                        .next(widget_graph, &depth_order.indices, theme);
+                global_input.set_widget_under_mouse(widget_under_mouse);
+            }
+
+            if is_hidden(global_input.current.widget_capturing_mouse) {
+                if let Some(idx) = global_input.current.widget_capturing_mouse.take() {
+                    global_input.push_event(event::Ui::WidgetUncapturesMouse(idx).into());
+                }
+            }
+
+            if is_hidden(global_input.current.widget_capturing_keyboard) {
+                if let Some(idx) = global_input.current.widget_capturing_keyboard.take() {
+                    global_input.push_event(event::Ui::WidgetUncapturesKeyboard(idx).into());
+                }
+            }
+        }
+    }
+
+
+    /// Capture the keyboard for the widget at the given index so that it receives all keyboard
+    /// input, e.g. to focus a `TextBox` without the user having to click it.
+    ///
+    /// If some other widget is capturing the keyboard, a `WidgetUncapturesKeyboard` event is
+    /// pushed for it before the `WidgetCapturesKeyboard` event is pushed for the given widget.
+    pub fn capture_keyboard<I: Into<widget::Index>>(&mut self, idx: I) {
+        let idx: widget::Index = idx.into();
+        let idx = self.widget_graph.widget_index(idx).unwrap_or(idx);
+        let capturing = self.global_input.current.widget_capturing_keyboard;
+        if capturing != Some(idx) {
+            if let Some(capturing_idx) = capturing {
+                let event = event::Ui::WidgetUncapturesKeyboard(capturing_idx).into();
+                self.global_input.push_event(event);
+            }
+            self.global_input.push_event(event::Ui::WidgetCapturesKeyboard(idx).into());
+            self.global_input.current.widget_capturing_keyboard = Some(idx);
+        }
+    }
+
+    /// Release the keyboard from the widget at the given index, pushing a
+    /// `WidgetUncapturesKeyboard` event.
+    ///
+    /// Does nothing if the widget is not capturing the keyboard.
+    pub fn uncapture_keyboard<I: Into<widget::Index>>(&mut self, idx: I) {
+        let idx: widget::Index = idx.into();
+        let idx = self.widget_graph.widget_index(idx).unwrap_or(idx);
+        if self.global_input.current.widget_capturing_keyboard == Some(idx) {
+            self.global_input.push_event(event::Ui::WidgetUncapturesKeyboard(idx).into());
+            self.global_input.current.widget_capturing_keyboard = None;
+        }
+    }
+
+    /// Capture the mouse for the widget at the given index so that it receives all mouse input.
+    ///
+    /// If some other widget is capturing the mouse, a `WidgetUncapturesMouse` event is pushed for
+    /// it before the `WidgetCapturesMouse` event is pushed for the given widget.
+    ///
+    /// Note that while no mouse buttons are pressed, the mouse is re-captured by whichever widget
+    /// it moves over.
+    pub fn capture_mouse<I: Into<widget::Index>>(&mut self, idx: I) {
+        let idx: widget::Index = idx.into();
+        let idx = self.widget_graph.widget_index(idx).unwrap_or(idx);
+        let capturing = self.global_input.current.widget_capturing_mouse;
+        if capturing != Some(idx) {
+            if let Some(capturing_idx) = capturing {
+                let event = event::Ui::WidgetUncapturesMouse(capturing_idx).into();
+                self.global_input.push_event(event);
+            }
+            self.global_input.push_event(event::Ui::WidgetCapturesMouse(idx).into());
+            self.global_input.current.widget_capturing_mouse = Some(idx);
+        }
+    }
+
+    /// Release the mouse from the widget at the given index, pushing a `WidgetUncapturesMouse`
+    /// event.
+    ///
+    /// Does nothing if the widget is not capturing the mouse.
+    pub fn uncapture_mouse<I: Into<widget::Index>>(&mut self, idx: I) {
+        let idx: widget::Index = idx.into();
+        let idx = self.widget_graph.widget_index(idx).unwrap_or(idx);
+        if self.global_input.current.widget_capturing_mouse == Some(idx) {
+            self.global_input.push_event(event::Ui::WidgetUncapturesMouse(idx).into());
+            self.global_input.current.widget_capturing_mouse = None;
+        }
     }
 
 
@@ -976,9 +1232,10 @@ impl<B> Ui<B>
     pub fn draw<G>(&mut self, context: Context, graphics: &mut G)
         where G: Graphics<Texture=B::Texture>,
     {
-        use backend::graphics::{draw_from_graph, Transformed};
+        use backend::graphics::{draw_from_graph_with_focus, Transformed};
         use std::ops::{Deref, DerefMut};
 
+        let maybe_focused = self.focus_ring_widget();
         let Ui {
             ref mut glyph_cache,
             ref mut redraw_count,
@@ -999,7 +1256,8 @@ impl<B> Ui<B>
         let indices = &depth_order.indices;
 
         // Draw the `Ui` from the `widget_graph`.
-        draw_from_graph::<B, G>(context, graphics, character_cache, widget_graph, indices, theme);
+        draw_from_graph_with_focus::<B, G>(context, graphics, character_cache, widget_graph,
+                                           indices, theme, maybe_focused);
 
         // Because we just drew everything, take one from the redraw count.
         if *redraw_count > 0 {
@@ -1008,6 +1266,32 @@ impl<B> Ui<B>
     }
 
 
+    /// Produce an iterator yielding every graphical **Primitive** necessary for rendering the
+    /// **Ui** in depth order.
+    ///
+    /// This allows the **Ui** to be rendered by any graphics backend, rather than only those that
+    /// implement the piston **Graphics** trait (as required by `Ui::draw`).
+    ///
+    /// Note that unlike `Ui::draw`, this does not affect the `redraw_count`.
+    pub fn render_primitives<'a>(&'a self) -> render::Primitives<'a, B::Texture> {
+        let maybe_focused = self.focus_ring_widget();
+        let Ui { ref widget_graph, ref depth_order, ref theme, .. } = *self;
+        render::primitives_with_focus(widget_graph, &depth_order.indices, theme, maybe_focused)
+    }
+
+
+    /// The widget around which the focus ring should be drawn, if any.
+    ///
+    /// This is the widget capturing the keyboard, but only if focus was moved to it via `Tab`.
+    fn focus_ring_widget(&self) -> Option<NodeIndex> {
+        if !self.is_focus_visible {
+            return None;
+        }
+        self.global_input.current.widget_capturing_keyboard
+            .and_then(|idx| self.widget_graph.node_index(idx))
+    }
+
+
     /// Same as the `Ui::draw` method, but *only* draws if the `redraw_count` is greater than 0.
     ///
     /// The `redraw_count` is set to `SAFE_REDRAW_COUNT` whenever a `Widget` indicates that it
@@ -1098,6 +1382,87 @@ impl<'a, B> UiCell<'a, B>
         self.ui.kids_bounding_box(idx)
     }
 
+    /// Tells the `Ui` that it needs to be re-drawn.
+    ///
+    /// This is useful for widgets that change over time without receiving any input, such as
+    /// animations. See `Ui::needs_redraw` for more info.
+    pub fn needs_redraw(&mut self) {
+        self.ui.needs_redraw();
+    }
+
+    /// Capture the keyboard for the widget at the given index.
+    ///
+    /// The change is applied at the end of `Ui::set_widgets` so that the resulting events are
+    /// received by widgets during the next update. See `Ui::capture_keyboard` for more info.
+    pub fn capture_keyboard<I: Into<widget::Index>>(&mut self, idx: I) {
+        self.ui.pending_captures.push(Capture::Keyboard(idx.into()));
+    }
+
+    /// Release the keyboard from the widget at the given index if it is capturing it.
+    ///
+    /// The change is applied at the end of `Ui::set_widgets`. See `Ui::uncapture_keyboard`.
+    pub fn uncapture_keyboard<I: Into<widget::Index>>(&mut self, idx: I) {
+        self.ui.pending_captures.push(Capture::UncaptureKeyboard(idx.into()));
+    }
+
+    /// Capture the mouse for the widget at the given index.
+    ///
+    /// The change is applied at the end of `Ui::set_widgets`. See `Ui::capture_mouse`.
+    pub fn capture_mouse<I: Into<widget::Index>>(&mut self, idx: I) {
+        self.ui.pending_captures.push(Capture::Mouse(idx.into()));
+    }
+
+    /// Release the mouse from the widget at the given index if it is capturing it.
+    ///
+    /// The change is applied at the end of `Ui::set_widgets`. See `Ui::uncapture_mouse`.
+    pub fn uncapture_mouse<I: Into<widget::Index>>(&mut self, idx: I) {
+        self.ui.pending_captures.push(Capture::UncaptureMouse(idx.into()));
+    }
+
+    /// Begin dragging the given payload from the widget at the given index.
+    ///
+    /// This is normally called by the source widget in response to a left mouse button `Drag`, and
+    /// does nothing if a drag-and-drop is already under way.
+    /// While the payload is dragged, the widget beneath the mouse receives `DragEnter`, `DragOver`
+    /// and `DragLeave` events and may accept the payload via `accept_drop`. Once the left mouse
+    /// button is released over a target that has accepted it, the target receives a `Drop` event
+    /// and may take the payload via `take_dropped_payload`. Pressing `Escape` cancels the drag.
+    ///
+    /// An optional preview may be drawn under the mouse while `input::Global::dragging` is `Some`.
+    /// The preview must be instantiated as a `graphics_for` the source, so that it is never
+    /// considered a target itself.
+    pub fn start_drag<I>(&mut self, idx: I, payload: Box<Any>)
+        where I: Into<widget::Index>,
+    {
+        let idx: widget::Index = idx.into();
+        let idx = self.ui.widget_graph.widget_index(idx).unwrap_or(idx);
+        self.ui.global_input.start_drag(idx, payload);
+    }
+
+    /// Accept the drag-and-drop payload currently being dragged over the widget at the given index,
+    /// so that it is dropped onto the widget once released.
+    ///
+    /// Acceptance is reset whenever the payload is dragged onto a different widget.
+    pub fn accept_drop<I: Into<widget::Index>>(&mut self, idx: I) {
+        let idx: widget::Index = idx.into();
+        let idx = self.ui.widget_graph.widget_index(idx).unwrap_or(idx);
+        self.ui.global_input.accept_drop(idx);
+    }
+
+    /// Take the payload that was dropped onto the widget at the given index, which may then be
+    /// downcast to the expected type.
+    ///
+    /// The payload is only available during the update following the widget's `Drop` event. If
+    /// several payloads were dropped onto the widget, each call takes the next in the order in
+    /// which they were dropped.
+    pub fn take_dropped_payload<I>(&mut self, idx: I) -> Option<Box<Any>>
+        where I: Into<widget::Index>,
+    {
+        let idx: widget::Index = idx.into();
+        let idx = self.ui.widget_graph.widget_index(idx).unwrap_or(idx);
+        self.ui.global_input.take_dropped_payload(idx)
+    }
+
     /// Scroll the widget at the given index by the given offset amount.
     ///
     /// The produced `Scroll` event will be pushed to the `pending_scroll_events` and will be
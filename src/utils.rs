@@ -4,7 +4,7 @@
 
 
 use num::{Float, NumCast, PrimInt, ToPrimitive};
-use position::{Point, Range, Rect};
+use position::{Point, Range, Rect, Scalar};
 use std::borrow::Cow;
 use std::iter::{Chain, once, Once};
 use std;
@@ -138,6 +138,27 @@ pub fn vec2_sub<T>(a: [T; 2], b: [T; 2]) -> [T; 2]
     [a[0] - b[0], a[1] - b[1]]
 }
 
+/// Whether or not the point `p` lies within the triangle `t`, regardless of its winding.
+pub fn is_over_triangle(t: [Point; 3], p: Point) -> bool {
+    let side = |a: Point, b: Point| (b[0] - a[0]) * (p[1] - a[1]) - (b[1] - a[1]) * (p[0] - a[0]);
+    let (ab, bc, ca) = (side(t[0], t[1]), side(t[1], t[2]), side(t[2], t[0]));
+    (ab >= 0.0 && bc >= 0.0 && ca >= 0.0) || (ab <= 0.0 && bc <= 0.0 && ca <= 0.0)
+}
+
+/// The shortest distance between the point `p` and the line segment from `a` to `b`.
+pub fn distance_to_segment(a: Point, b: Point, p: Point) -> Scalar {
+    let ab = vec2_sub(b, a);
+    let ap = vec2_sub(p, a);
+    let len_sq = ab[0] * ab[0] + ab[1] * ab[1];
+    let t = if len_sq > 0.0 {
+        clamp((ap[0] * ab[0] + ap[1] * ab[1]) / len_sq, 0.0, 1.0)
+    } else {
+        0.0
+    };
+    let closest = [a[0] + ab[0] * t, a[1] + ab[1] * t];
+    let d = vec2_sub(p, closest);
+    d[0].hypot(d[1])
+}
 
 /// Find the bounding rect for the given series of points.
 pub fn bounding_box_for_points<I>(mut points: I) -> Rect
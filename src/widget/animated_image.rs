@@ -0,0 +1,259 @@
+use {
+    Backend,
+    Color,
+    Dimension,
+    Image,
+    IndexSlot,
+    Positionable,
+    Rect,
+    Scalar,
+    Sizeable,
+    Ui,
+};
+use backend::graphics::ImageSize;
+use std::any::Any;
+use std::sync::Arc;
+use std::time::Instant;
+use widget::{self, Widget};
+
+
+/// A widget that animates an image by cycling through a series of frames, such as those of a
+/// sprite sheet, at a given frame rate.
+///
+/// Frames are advanced by the time elapsed between calls to `Ui::set_widgets`. While the
+/// animation is playing at a positive frame rate, the **AnimatedImage** requests that the **Ui** is
+/// redrawn. Playing a finished animation again restarts it from its first frame.
+pub struct AnimatedImage<'a, T> {
+    /// Data necessary and common for all widget builder types.
+    pub common: widget::CommonBuilder,
+    /// Unique styling for the **AnimatedImage**.
+    pub style: Style,
+    /// The texture containing each of the frames.
+    pub texture: Arc<T>,
+    /// The areas of the texture used as frames.
+    pub frames: Frames<'a>,
+    /// The number of frames displayed per second.
+    pub fps: Scalar,
+    /// Whether or not the animation is currently advancing.
+    pub is_playing: bool,
+    /// Whether or not the animation restarts after its last frame.
+    pub is_looping: bool,
+}
+
+/// Describes the areas of a texture used as the frames of an **AnimatedImage**.
+#[derive(Copy, Clone, Debug)]
+pub enum Frames<'a> {
+    /// The texture is divided into a grid of equally sized frames, ordered from left to right
+    /// and then from top to bottom.
+    Grid {
+        /// The number of frames along the *x* axis.
+        columns: usize,
+        /// The number of frames along the *y* axis.
+        rows: usize,
+    },
+    /// A list of source rectangles following the same convention as `Image::source_rectangle`,
+    /// where the position of each describes its top-left corner within the texture in pixels.
+    List(&'a [Rect]),
+}
+
+/// Unique state for the **AnimatedImage**.
+#[derive(Clone, Debug, PartialEq)]
+pub struct State {
+    image_idx: IndexSlot,
+    /// The index of the frame currently being displayed.
+    pub frame: usize,
+    /// The total time in seconds for which the animation has played.
+    elapsed: Scalar,
+    /// The moment at which the animation last advanced, if it is playing.
+    last_advanced: Option<Instant>,
+    /// Whether or not the animation was playing during the previous update.
+    was_playing: bool,
+}
+
+/// Unique kind for the widget.
+pub const KIND: widget::Kind = "AnimatedImage";
+
+widget_style!{
+    KIND;
+    /// Unique styling for the **AnimatedImage** widget.
+    style Style {
+        /// Optionally specify a single colour to use for the image.
+        - maybe_color: Option<Color> { None }
+    }
+}
+
+
+impl<'a, T> AnimatedImage<'a, T> {
+
+    /// Construct a new **AnimatedImage** from the given texture and frames.
+    pub fn new(texture: Arc<T>, frames: Frames<'a>) -> Self {
+        AnimatedImage {
+            common: widget::CommonBuilder::new(),
+            style: Style::new(),
+            texture: texture,
+            frames: frames,
+            fps: 12.0,
+            is_playing: true,
+            is_looping: true,
+        }
+    }
+
+    /// Construct an **AnimatedImage** from a sprite sheet divided into a grid of equally sized
+    /// frames, ordered from left to right and then from top to bottom.
+    pub fn grid(texture: Arc<T>, columns: usize, rows: usize) -> Self {
+        Self::new(texture, Frames::Grid { columns: columns, rows: rows })
+    }
+
+    /// Construct an **AnimatedImage** from a list of source rectangles within the texture.
+    pub fn list(texture: Arc<T>, frames: &'a [Rect]) -> Self {
+        Self::new(texture, Frames::List(frames))
+    }
+
+    builder_methods!{
+        pub fps { fps = Scalar }
+        pub playing { is_playing = bool }
+        pub looping { is_looping = bool }
+        pub color { style.maybe_color = Some(Option<Color>) }
+    }
+
+}
+
+
+impl<'a> Frames<'a> {
+
+    /// The total number of frames.
+    pub fn len(&self) -> usize {
+        match *self {
+            Frames::Grid { columns, rows } => columns * rows,
+            Frames::List(rects) => rects.len(),
+        }
+    }
+
+    /// The source rectangle of the frame at the given index within a texture of the given
+    /// dimensions.
+    pub fn src_rect(&self, idx: usize, texture_dim: (u32, u32)) -> Option<Rect> {
+        if idx >= self.len() {
+            return None;
+        }
+        match *self {
+            Frames::Grid { columns, rows } => {
+                let w = texture_dim.0 as Scalar / columns as Scalar;
+                let h = texture_dim.1 as Scalar / rows as Scalar;
+                let (column, row) = (idx % columns, idx / columns);
+                Some(Rect::from_xy_dim([column as Scalar * w, row as Scalar * h], [w, h]))
+            },
+            Frames::List(rects) => Some(rects[idx]),
+        }
+    }
+
+}
+
+
+/// The duration in seconds between the two given moments.
+fn secs_between(earlier: Instant, later: Instant) -> Scalar {
+    let duration = later.duration_since(earlier);
+    duration.as_secs() as Scalar + duration.subsec_nanos() as Scalar * 1e-9
+}
+
+
+impl<'a, T> Widget for AnimatedImage<'a, T>
+    where T: Any + ImageSize,
+{
+    type State = State;
+    type Style = Style;
+
+    fn common(&self) -> &widget::CommonBuilder {
+        &self.common
+    }
+
+    fn common_mut(&mut self) -> &mut widget::CommonBuilder {
+        &mut self.common
+    }
+
+    fn unique_kind(&self) -> widget::Kind {
+        KIND
+    }
+
+    fn init_state(&self) -> State {
+        State {
+            image_idx: IndexSlot::new(),
+            frame: 0,
+            elapsed: 0.0,
+            last_advanced: None,
+            was_playing: false,
+        }
+    }
+
+    fn style(&self) -> Style {
+        self.style.clone()
+    }
+
+    fn default_x_dimension<B: Backend>(&self, _ui: &Ui<B>) -> Dimension {
+        let w = self.frames.src_rect(0, self.texture.get_size()).map(|r| r.w()).unwrap_or(0.0);
+        Dimension::Absolute(w)
+    }
+
+    fn default_y_dimension<B: Backend>(&self, _ui: &Ui<B>) -> Dimension {
+        let h = self.frames.src_rect(0, self.texture.get_size()).map(|r| r.h()).unwrap_or(0.0);
+        Dimension::Absolute(h)
+    }
+
+    /// Update the state of the AnimatedImage.
+    fn update<B: Backend>(self, args: widget::UpdateArgs<Self, B>) {
+        let widget::UpdateArgs { idx, state, style, rect, mut ui, .. } = args;
+        let AnimatedImage { texture, frames, fps, is_playing, is_looping, .. } = self;
+
+        let num_frames = frames.len();
+        if is_playing && num_frames > 0 && fps > 0.0 {
+            let now = Instant::now();
+
+            // A finished animation restarts once it is played again.
+            let has_finished = !is_looping
+                && (state.elapsed * fps).floor() as usize >= num_frames - 1;
+            let start = if has_finished && !state.was_playing { 0.0 } else { state.elapsed };
+            let elapsed = start
+                + state.last_advanced.map(|then| secs_between(then, now)).unwrap_or(0.0);
+            let frame = (elapsed * fps).floor() as usize;
+            let (frame, is_finished) = if is_looping {
+                (frame % num_frames, false)
+            } else if frame >= num_frames - 1 {
+                (num_frames - 1, true)
+            } else {
+                (frame, false)
+            };
+
+            // Finished animations stop advancing and no longer need to be redrawn.
+            let last_advanced = if is_finished { None } else { Some(now) };
+            if state.frame != frame || state.last_advanced != last_advanced {
+                state.update(|state| {
+                    state.frame = frame;
+                    state.elapsed = elapsed;
+                    state.last_advanced = last_advanced;
+                });
+            }
+            if !is_finished {
+                ui.needs_redraw();
+            }
+        } else if state.last_advanced.is_some() {
+            // Time spent paused does not advance the animation.
+            state.update(|state| state.last_advanced = None);
+        }
+
+        if state.was_playing != is_playing {
+            state.update(|state| state.was_playing = is_playing);
+        }
+
+        // The Image widget displaying the current frame.
+        if let Some(src_rect) = frames.src_rect(state.frame, texture.get_size()) {
+            let image_idx = state.image_idx.get(&mut ui);
+            Image::from_texture(texture)
+                .source_rectangle(src_rect)
+                .wh(rect.dim())
+                .middle_of(idx)
+                .graphics_for(idx)
+                .color(style.maybe_color(ui.theme()))
+                .set(image_idx, &mut ui);
+        }
+    }
+
+}
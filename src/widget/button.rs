@@ -3,16 +3,22 @@ use {
     Color,
     Colorable,
     FontSize,
+    FillGradient,
     Frameable,
     FramedRectangle,
+    Gradientable,
     IndexSlot,
     Labelable,
     Positionable,
     Scalar,
+    Sizeable,
     Text,
     Widget,
 };
+use color;
+use std::any::Any;
 use widget;
+use widget::primitive::image;
 
 
 /// A pressable button widget whose reaction is triggered upon release.
@@ -25,6 +31,8 @@ pub struct Button<'a, F> {
     pub style: Style,
     /// Whether or not user input is enabled.
     enabled: bool,
+    /// An optional **image::Skin** drawn in place of the Button's rectangle.
+    maybe_skin: Option<&'a Any>,
 }
 
 /// Unique kind for the widget.
@@ -40,6 +48,10 @@ widget_style!{
         - frame: Scalar { theme.frame_width }
         /// The color of the frame.
         - frame_color: Color { theme.frame_color }
+        /// The radius of the corners of the frame. Corners are square when zero.
+        - corner_radius: Scalar { 0.0 }
+        /// A gradient with which the Button's pressable area is filled instead of its color.
+        - maybe_gradient: Option<FillGradient> { None }
         /// The color of the Button's label.
         - label_color: Color { theme.label_color }
         /// The font size of the Button's label.
@@ -51,6 +63,7 @@ widget_style!{
 #[derive(Clone, Debug, PartialEq)]
 pub struct State {
     rectangle_idx: IndexSlot,
+    image_idx: IndexSlot,
     label_idx: IndexSlot,
 }
 
@@ -64,12 +77,25 @@ impl<'a, F> Button<'a, F> {
             maybe_label: None,
             style: Style::new(),
             enabled: true,
+            maybe_skin: None,
         }
     }
 
     builder_methods!{
         pub react { maybe_react = Some(F) }
         pub enabled { enabled = bool }
+        pub corner_radius { style.corner_radius = Some(Scalar) }
+    }
+
+    /// Draw the Button using the given **image::Skin** in place of its rectangle and frame.
+    ///
+    /// The skin is tinted while the Button is highlighted or pressed.
+    ///
+    /// Panics upon update if the skin's texture type differs from that of the **Ui**'s
+    /// **Backend**.
+    pub fn skin<T: Any>(mut self, skin: &'a image::Skin<T>) -> Self {
+        self.maybe_skin = Some(skin);
+        self
     }
 }
 
@@ -95,6 +121,7 @@ impl<'a, F> Widget for Button<'a, F>
     fn init_state(&self) -> State {
         State {
             rectangle_idx: IndexSlot::new(),
+            image_idx: IndexSlot::new(),
             label_idx: IndexSlot::new(),
         }
     }
@@ -103,11 +130,16 @@ impl<'a, F> Widget for Button<'a, F>
         self.style.clone()
     }
 
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
     /// Update the state of the Button.
     fn update<B: Backend>(self, args: widget::UpdateArgs<Self, B>) {
         let widget::UpdateArgs { idx, state, style, rect, mut ui, .. } = args;
 
-        let color = {
+        // The function used to highlight the Button's color or gradient.
+        let highlight: fn(Color) -> Color = {
             let input = ui.widget_input(idx);
             if input.clicks().left().next().is_some() {
                 if let Some(react) = self.maybe_react {
@@ -115,28 +147,58 @@ impl<'a, F> Widget for Button<'a, F>
                 }
             }
 
-            let color = style.color(ui.theme());
-            input.mouse().map_or(color, |mouse| {
+            fn unchanged(color: Color) -> Color { color }
+            fn clicked(color: Color) -> Color { color.clicked() }
+            fn highlighted(color: Color) -> Color { color.highlighted() }
+            input.mouse().map_or(unchanged, |mouse| {
                 if mouse.buttons.left().is_down() {
-                    color.clicked()
+                    clicked
                 } else {
-                    color.highlighted()
+                    highlighted
                 }
             })
         };
-
-        // FramedRectangle widget.
-        let rectangle_idx = state.rectangle_idx.get(&mut ui);
         let dim = rect.dim();
-        let frame = style.frame(ui.theme());
-        let frame_color = style.frame_color(ui.theme());
-        FramedRectangle::new(dim)
-            .middle_of(idx)
-            .graphics_for(idx)
-            .color(color)
-            .frame(frame)
-            .frame_color(frame_color)
-            .set(rectangle_idx, &mut ui);
+        let maybe_skin = self.maybe_skin.map(|skin| {
+            skin.downcast_ref::<image::Skin<B::Texture>>()
+                .expect("the Button's skin texture type differs from the Backend's Texture")
+        });
+        let surface_idx = match maybe_skin {
+
+            // Image widget if we were given a skin.
+            Some(skin) => {
+                let image_idx = state.image_idx.get(&mut ui);
+                let tint = highlight(color::WHITE);
+                skin.image()
+                    .wh(dim)
+                    .middle_of(idx)
+                    .graphics_for(idx)
+                    .color(if tint == color::WHITE { None } else { Some(tint) })
+                    .set(image_idx, &mut ui);
+                image_idx
+            },
+
+            // Otherwise a FramedRectangle widget.
+            None => {
+                let rectangle_idx = state.rectangle_idx.get(&mut ui);
+                let color = highlight(style.color(ui.theme()));
+                let maybe_gradient =
+                    style.maybe_gradient(ui.theme()).map(|g| g.map_colors(highlight));
+                let frame = style.frame(ui.theme());
+                let frame_color = style.frame_color(ui.theme());
+                let corner_radius = style.corner_radius(ui.theme());
+                FramedRectangle::new(dim)
+                    .middle_of(idx)
+                    .graphics_for(idx)
+                    .color(color)
+                    .maybe_gradient(maybe_gradient)
+                    .frame(frame)
+                    .frame_color(frame_color)
+                    .corner_radius(corner_radius)
+                    .set(rectangle_idx, &mut ui);
+                rectangle_idx
+            },
+        };
 
         // Label widget.
         if let Some(label) = self.maybe_label {
@@ -144,7 +206,7 @@ impl<'a, F> Widget for Button<'a, F>
             let color = style.label_color(ui.theme());
             let font_size = style.label_font_size(ui.theme());
             Text::new(label)
-                .middle_of(rectangle_idx)
+                .middle_of(surface_idx)
                 .graphics_for(idx)
                 .color(color)
                 .font_size(font_size)
@@ -160,6 +222,13 @@ impl<'a, F> Colorable for Button<'a, F> {
     builder_method!(color { style.color = Some(Color) });
 }
 
+impl<'a, F> Gradientable for Button<'a, F> {
+    fn gradient(mut self, gradient: FillGradient) -> Self {
+        self.style.maybe_gradient = Some(Some(gradient));
+        self
+    }
+}
+
 impl<'a, F> Frameable for Button<'a, F> {
     builder_methods!{
         frame { style.frame = Some(Scalar) }
@@ -5,6 +5,7 @@ use {
     Color,
     Colorable,
     Dimensions,
+    FillGradient,
     FontSize,
     Frameable,
     FramedRectangle,
@@ -27,7 +28,9 @@ use {
 };
 use position;
 use position::Direction::{Forwards, Backwards};
+use std::any::Any;
 use widget::{self, title_bar};
+use widget::primitive::image;
 
 
 /// **Canvas** is designed to be a "container"-like "parent" widget that simplifies placement of
@@ -54,12 +57,15 @@ pub struct Canvas<'a> {
     pub maybe_title_bar_label: Option<&'a str>, 
     /// A list of child **Canvas**ses as splits of this **Canvas** flowing in the given direction.
     pub maybe_splits: Option<FlowOfSplits<'a>>,
+    /// An optional **image::Skin** drawn in place of the **Canvas**' rectangle.
+    pub maybe_skin: Option<&'a Any>,
 }
 
 /// **Canvas** state to be cached.
 #[derive(Clone, Debug, PartialEq)]
 pub struct State {
     rectangle_idx: IndexSlot,
+    image_idx: IndexSlot,
     title_bar_idx: IndexSlot,
 }
 
@@ -73,6 +79,10 @@ widget_style!{
         - frame: Scalar { theme.frame_width }
         /// The color of the Canvas' frame.
         - frame_color: Color { theme.frame_color }
+        /// The radius of the corners of the frame. Corners are square when zero.
+        - corner_radius: Scalar { 0.0 }
+        /// A gradient with which the background is filled instead of its color.
+        - maybe_gradient: Option<FillGradient> { None }
         /// If this Canvas is a split of some parent Canvas, this is the length of the split.
         - length: Length { Length::Weight(1.0) }
 
@@ -139,9 +149,19 @@ impl<'a> Canvas<'a> {
             style: Style::new(),
             maybe_title_bar_label: None,
             maybe_splits: None,
+            maybe_skin: None,
         }
     }
 
+    /// Draw the **Canvas** using the given **image::Skin** in place of its rectangle and frame.
+    ///
+    /// Panics upon update if the skin's texture type differs from that of the **Ui**'s
+    /// **Backend**.
+    pub fn skin<T: Any>(mut self, skin: &'a image::Skin<T>) -> Self {
+        self.maybe_skin = Some(skin);
+        self
+    }
+
     builder_methods!{
         pub title_bar { maybe_title_bar_label = Some(&'a str) }
         pub pad_left { style.pad_left = Some(Scalar) }
@@ -149,6 +169,7 @@ impl<'a> Canvas<'a> {
         pub pad_bottom { style.pad_bottom = Some(Scalar) }
         pub pad_top { style.pad_top = Some(Scalar) }
         pub with_style { style = Style }
+        pub corner_radius { style.corner_radius = Some(Scalar) }
     }
 
     /// Set the length of the Split as an absolute scalar.
@@ -228,6 +249,7 @@ impl<'a> Widget for Canvas<'a> {
     fn init_state(&self) -> State {
         State {
             rectangle_idx: IndexSlot::new(),
+            image_idx: IndexSlot::new(),
             title_bar_idx: IndexSlot::new(),
         }
     }
@@ -278,22 +300,48 @@ impl<'a> Widget for Canvas<'a> {
     /// Update the state of the Canvas.
     fn update<B: Backend>(self, args: widget::UpdateArgs<Self, B>) {
         let widget::UpdateArgs { idx, state, rect, mut ui, .. } = args;
-        let Canvas { style, maybe_title_bar_label, maybe_splits, .. } = self;
+        let Canvas { style, maybe_title_bar_label, maybe_splits, maybe_skin, .. } = self;
 
-        // FramedRectangle widget as the rectangle backdrop.
-        let rectangle_idx = state.rectangle_idx.get(&mut ui);
         let dim = rect.dim();
         let color = style.color(ui.theme());
         let frame = style.frame(ui.theme());
         let frame_color = style.frame_color(ui.theme());
-        FramedRectangle::new(dim)
-            .color(color)
-            .frame(frame)
-            .frame_color(frame_color)
-            .middle_of(idx)
-            .graphics_for(idx)
-            .place_on_kid_area(false)
-            .set(rectangle_idx, &mut ui);
+        let maybe_skin = maybe_skin.map(|skin| {
+            skin.downcast_ref::<image::Skin<B::Texture>>()
+                .expect("the Canvas' skin texture type differs from the Backend's Texture")
+        });
+        let surface_idx = match maybe_skin {
+
+            // Image widget as the backdrop if we were given a skin.
+            Some(skin) => {
+                let image_idx = state.image_idx.get(&mut ui);
+                skin.image()
+                    .wh(dim)
+                    .middle_of(idx)
+                    .graphics_for(idx)
+                    .place_on_kid_area(false)
+                    .set(image_idx, &mut ui);
+                image_idx
+            },
+
+            // Otherwise a FramedRectangle widget as the rectangle backdrop.
+            None => {
+                let rectangle_idx = state.rectangle_idx.get(&mut ui);
+                let corner_radius = style.corner_radius(ui.theme());
+                let maybe_gradient = style.maybe_gradient(ui.theme());
+                FramedRectangle::new(dim)
+                    .color(color)
+                    .maybe_gradient(maybe_gradient)
+                    .frame(frame)
+                    .frame_color(frame_color)
+                    .corner_radius(corner_radius)
+                    .middle_of(idx)
+                    .graphics_for(idx)
+                    .place_on_kid_area(false)
+                    .set(rectangle_idx, &mut ui);
+                rectangle_idx
+            },
+        };
 
         // TitleBar widget if we were given some label.
         if let Some(label) = maybe_title_bar_label {
@@ -303,7 +351,7 @@ impl<'a> Widget for Canvas<'a> {
             let text_align = style.title_bar_text_align(ui.theme());
             let line_spacing = style.title_bar_line_spacing(ui.theme());
             let maybe_wrap = style.title_bar_maybe_wrap(ui.theme());
-            TitleBar::new(label, rectangle_idx)
+            TitleBar::new(label, surface_idx)
                 .and_mut(|title_bar| {
                     title_bar.style.maybe_wrap = Some(maybe_wrap);
                     title_bar.style.text_align = Some(text_align);
@@ -431,6 +479,13 @@ impl<'a> ::color::Colorable for Canvas<'a> {
     builder_method!(color { style.color = Some(Color) });
 }
 
+impl<'a> ::color::Gradientable for Canvas<'a> {
+    fn gradient(mut self, gradient: FillGradient) -> Self {
+        self.style.maybe_gradient = Some(Some(gradient));
+        self
+    }
+}
+
 impl<'a> ::frame::Frameable for Canvas<'a> {
     builder_methods!{
         frame { style.frame = Some(Scalar) }
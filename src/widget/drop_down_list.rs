@@ -13,6 +13,7 @@ use {
     Rect,
     Rectangle,
     Scalar,
+    Shadow,
     Sizeable,
 };
 use widget::{self, Widget};
@@ -48,12 +49,16 @@ widget_style!{
         - frame: Scalar { theme.frame_width }
         /// Color of the widget's frame.
         - frame_color: Color { theme.frame_color }
+        /// The radius of the corners of each item's frame. Corners are square when zero.
+        - corner_radius: Scalar { 0.0 }
         /// Color of the item labels.
         - label_color: Color { theme.label_color }
         /// Font size for the item labels.
         - label_font_size: FontSize { theme.font_size_medium }
         /// Maximum height of the Open menu before the scrollbar appears.
         - maybe_max_visible_height: Option<MaxHeight> { None }
+        /// The shadow drawn beneath the Open menu.
+        - maybe_menu_shadow: Option<Shadow> { None }
     }
 }
 
@@ -98,6 +103,7 @@ impl<'a, F> DropDownList<'a, F> {
     builder_methods!{
         pub react { maybe_react = Some(F) }
         pub enabled { enabled = bool }
+        pub corner_radius { style.corner_radius = Some(Scalar) }
     }
 
     /// Set the maximum height of the DropDownList (before the scrollbar appears) as a number of
@@ -114,6 +120,12 @@ impl<'a, F> DropDownList<'a, F> {
         self
     }
 
+    /// Draw the given shadow beneath the Open menu.
+    pub fn menu_shadow(mut self, shadow: Shadow) -> Self {
+        self.style.maybe_menu_shadow = Some(Some(shadow));
+        self
+    }
+
 }
 
 
@@ -225,14 +237,15 @@ impl<'a, F> Widget for DropDownList<'a, F>
                 let canvas_shift_y = dim[1] / 2.0 - canvas_dim[1] / 2.0;
                 let canvas_xy = [xy[0], xy[1] + canvas_shift_y];
                 let canvas_rect = Rect::from_xy_dim(canvas_xy, canvas_dim);
-                Rectangle::fill([dim[0], max_visible_height])
+                let mut menu = Rectangle::fill([dim[0], max_visible_height])
                     .graphics_for(idx)
                     .color(::color::BLACK.alpha(0.0))
                     .xy(canvas_xy)
                     .parent(idx)
                     .floating(true)
-                    .scroll_kids_vertically()
-                    .set(canvas_idx, &mut ui);
+                    .scroll_kids_vertically();
+                menu.common.style.maybe_shadow = style.maybe_menu_shadow(ui.theme());
+                menu.set(canvas_idx, &mut ui);
 
                 let labels = self.strings.iter();
                 let button_indices = state.buttons.iter().map(|&(idx, _)| idx);
@@ -295,6 +308,8 @@ impl Style {
             color: self.color.map(|c| if is_selected { c.highlighted() } else { c }),
             frame: self.frame,
             frame_color: self.frame_color,
+            corner_radius: self.corner_radius,
+            maybe_gradient: None,
             label_color: self.label_color,
             label_font_size: self.label_font_size,
         }
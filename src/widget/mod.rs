@@ -1,6 +1,7 @@
-use {Backend, CharacterCache, Dimension, GlyphCache};
+use {Backend, CharacterCache, Color, Dimension, GlyphCache, Scalar};
 use graph::{self, NodeIndex};
-use position::{Align, Depth, Dimensions, Padding, Position, Positionable, Rect, Sizeable};
+use position::{Align, Depth, Dimensions, Padding, Point, Position, Positionable, Rect, Sizeable,
+               Transform};
 use std;
 use theme::{self, Theme};
 use ui::{self, Ui, UiCell};
@@ -21,6 +22,7 @@ pub mod scroll;
 pub mod primitive;
 
 // Widget modules.
+pub mod animated_image;
 pub mod button;
 pub mod canvas;
 pub mod drop_down_list;
@@ -35,6 +37,7 @@ pub mod text_box;
 pub mod text_edit;
 pub mod title_bar;
 pub mod toggle;
+pub mod tooltip;
 pub mod xy_pad;
 
 
@@ -159,6 +162,22 @@ pub struct Floating {
     pub time_last_clicked: std::time::Instant,
 }
 
+/// A soft shadow drawn beneath a **Widget**'s **Rect**.
+///
+/// Shadows help "floating" widgets such as pop-ups and menus to stand out from the content beneath
+/// them. A shadow with no offset and a light color may be used as a glow.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Shadow {
+    /// The offset of the shadow from the widget's **Rect**.
+    pub offset: [Scalar; 2],
+    /// The distance over which the edge of the shadow fades out.
+    ///
+    /// The fade is centred upon the edge of the offset **Rect**.
+    pub blur: Scalar,
+    /// The color of the shadow at its most opaque.
+    pub color: Color,
+}
+
 /// A struct containing builder data common to all **Widget** types.
 ///
 /// This type also allows us to do a blanket impl of **Positionable** and **Sizeable** for `T: Widget`.
@@ -173,6 +192,8 @@ pub struct CommonBuilder {
     pub maybe_parent_idx: MaybeParent,
     /// Whether or not the Widget is a "floating" Widget.
     pub is_floating: bool,
+    /// Whether or not the floating Widget is drawn above all other floating Widgets.
+    pub is_overlay: bool,
     /// Whether or not the children of this **Widget** should be cropped to its `kid_area`.
     ///
     /// By default, the kid_area is the size of the entire widget, though it may be specified
@@ -203,6 +224,13 @@ pub struct CommonBuilder {
     /// default.
     /// - Any **Graphic** child of *b* will be considered as a **Graphic** child of *a*.
     pub maybe_graphics_for: Option<Index>,
+    /// The rotation, scale and translation applied to the **Widget** and its children.
+    pub maybe_transform: Option<Transform>,
+    /// The opacity by which the alpha of every color of the **Widget** and its children is
+    /// multiplied.
+    pub opacity: f32,
+    /// Whether or not the **Widget** and its children should be excluded from drawing and picking.
+    pub is_hidden: bool,
 }
 
 /// Styling and positioning data that is common between all widget types.
@@ -218,6 +246,8 @@ pub struct CommonStyle {
     pub maybe_y_position: Option<Position>,
     /// The rendering Depth of the Widget.
     pub maybe_depth: Option<Depth>,
+    /// A shadow drawn beneath the Widget.
+    pub maybe_shadow: Option<Shadow>,
 }
 
 /// A wrapper around a **Widget**'s unique **Widget::State**.
@@ -249,6 +279,14 @@ pub struct CommonState {
     pub maybe_floating: Option<Floating>,
     /// The area of the widget upon which kid widgets are placed.
     pub kid_area: KidArea,
+    /// The shadow drawn beneath the widget if it has one.
+    pub maybe_shadow: Option<Shadow>,
+    /// The transform applied to the widget and its children if it has one.
+    pub maybe_transform: Option<Transform>,
+    /// The opacity applied to the widget and its children.
+    pub opacity: f32,
+    /// Whether or not the widget and its children are hidden.
+    pub is_hidden: bool,
 }
 
 /// A **Widget**'s state in a form that is retrievable from the **Ui**'s widget cache.
@@ -267,12 +305,32 @@ pub struct Cached<W>
     pub kid_area: KidArea,
     /// Whether or not the Widget is a "floating" Widget.
     pub maybe_floating: Option<Floating>,
+    /// The shadow drawn beneath the Widget if it has one.
+    pub maybe_shadow: Option<Shadow>,
+    /// The transform applied to the Widget and its children if it has one.
+    pub maybe_transform: Option<Transform>,
+    /// The opacity applied to the Widget and its children.
+    pub opacity: f32,
+    /// Whether or not the Widget and its children are hidden.
+    pub is_hidden: bool,
     /// The state for a widget's scrollable *x* axis.
     pub maybe_x_scroll_state: Option<scroll::StateX>,
     /// The state for a widget's scrollable *y* axis.
     pub maybe_y_scroll_state: Option<scroll::StateY>,
 }
 
+/// A function that determines whether or not the given point lies over the widget whose state is
+/// stored within the given **Container**.
+///
+/// See [**Widget::is_over**](./trait.Widget#method.is_over).
+pub type IsOverFn = fn(&graph::Container, Point, &Theme) -> bool;
+
+/// The default **IsOverFn**, which considers every point within the widget's **Rect** to be over
+/// the widget.
+pub fn is_over_rect(container: &graph::Container, xy: Point, _theme: &Theme) -> bool {
+    container.rect.is_over(xy)
+}
+
 /// A unique identifier for a **Widget** type.
 ///
 /// Note: This might be replaced with **Any::get_type_id** when it stabilises.
@@ -306,6 +364,20 @@ pub struct PreUpdateCache {
     pub kid_area: KidArea,
     /// Floating data for the **Widget** if there is some.
     pub maybe_floating: Option<Floating>,
+    /// Whether or not the floating **Widget** is drawn above all other floating **Widget**s.
+    pub is_overlay: bool,
+    /// The shadow drawn beneath the **Widget** if it has one.
+    pub maybe_shadow: Option<Shadow>,
+    /// The transform applied to the **Widget** and its children if it has one.
+    pub maybe_transform: Option<Transform>,
+    /// The opacity applied to the **Widget** and its children.
+    pub opacity: f32,
+    /// Whether or not the **Widget** and its children are hidden.
+    pub is_hidden: bool,
+    /// Determines whether or not some point lies over the **Widget**.
+    pub is_over: IsOverFn,
+    /// Whether or not the **Widget** may receive keyboard focus via `Tab` traversal.
+    pub is_focusable: bool,
     /// Whether or not the children of the **Widget** should be cropped to its `kid_area`.
     pub crop_kids: bool,
     /// Scrolling data for the **Widget**'s *x* axis if there is some.
@@ -429,6 +501,8 @@ pub fn default_y_dimension<W, B>(widget: &W, ui: &Ui<B>) -> Dimension
 /// - default_height
 /// - drag_area
 /// - kid_area
+/// - is_over
+/// - is_focusable
 ///
 /// Methods that should not be overridden:
 ///
@@ -616,6 +690,27 @@ pub trait Widget: Sized {
         }
     }
 
+    /// Returns the function used to determine whether or not some point lies over the widget.
+    ///
+    /// The function is stored within the widget's **graph::Container** so that it may be called
+    /// when picking the widget under the mouse (see `graph::algo::pick_widgets`). By default,
+    /// every point within the widget's **Rect** is considered to be over the widget.
+    ///
+    /// Override this for widgets whose graphics do not fill their **Rect** (e.g. a circular
+    /// button or knob) so that they only receive hover and mouse capture over their visible shape.
+    fn is_over(&self) -> IsOverFn {
+        is_over_rect
+    }
+
+    /// Whether or not the widget may receive keyboard focus when the user cycles through widgets
+    /// with `Tab` and `Shift+Tab`.
+    ///
+    /// Focusable widgets are visited in the order in which they were instantiated. By default,
+    /// widgets are not focusable. Override this for widgets that the user interacts with directly.
+    fn is_focusable(&self) -> bool {
+        false
+    }
+
 
     // None of the following methods should require overriding. Perhaps they should be split off
     // into a separate trait which is impl'ed for W: Widget to make this clearer?
@@ -684,6 +779,59 @@ pub trait Widget: Sized {
         self
     }
 
+    /// Set whether or not the floating widget is an overlay (the default is `false`).
+    ///
+    /// Overlays are rendered after all other floating widgets, regardless of which was last
+    /// clicked. This is useful for transient popups such as tooltips. Has no effect unless the
+    /// widget is also `floating`.
+    fn overlay(mut self, is_overlay: bool) -> Self {
+        self.common_mut().is_overlay = is_overlay;
+        self
+    }
+
+    /// Draw the given shadow beneath the widget's **Rect**.
+    ///
+    /// This is particularly useful for distinguishing floating widgets from the widgets beneath
+    /// them.
+    fn shadow(mut self, shadow: Shadow) -> Self {
+        self.common_mut().style.maybe_shadow = Some(shadow);
+        self
+    }
+
+    /// Rotate, scale and/or translate the widget along with all of its depth-wise children.
+    ///
+    /// Transforms compose down the depth tree, so a child's transform is applied within the space
+    /// of its parent's. The widget is still laid out using its un-transformed **Rect**, while
+    /// drawing, mouse picking and relative mouse positions all respect the transform.
+    fn transform(mut self, transform: Transform) -> Self {
+        self.common_mut().maybe_transform = Some(transform);
+        self
+    }
+
+    /// Multiply the alpha of every color and image tint of the widget and all of its depth-wise
+    /// children by the given opacity, where `0.0` is fully transparent and `1.0` is unchanged.
+    ///
+    /// Opacities compose down the depth tree, making this useful for fading whole panels in and
+    /// out. Note that each primitive is faded individually, so overlapping children remain
+    /// visible through one another.
+    fn opacity(mut self, opacity: f32) -> Self {
+        self.common_mut().opacity = opacity.max(0.0).min(1.0);
+        self
+    }
+
+    /// Whether or not the widget and all of its depth-wise children should be hidden.
+    ///
+    /// Hidden widgets remain within the **Ui**'s widget graph, so their unique `State` and scroll
+    /// offsets are preserved, however they are neither drawn nor picked by the mouse. This is
+    /// useful for collapsing large sections of a GUI without having to re-initialise their state
+    /// when they are shown again.
+    ///
+    /// A hidden widget that is capturing the mouse or keyboard will be uncaptured.
+    fn hidden(mut self, hidden: bool) -> Self {
+        self.common_mut().is_hidden = hidden;
+        self
+    }
+
     /// Indicates that all widgets who are children of this widget should be cropped to the
     /// `kid_area` of this widget.
     fn crop_kids(mut self) -> Self {
@@ -845,6 +993,10 @@ fn set_widget<B, W>(widget: W, idx: Index, ui: &mut Ui<B>)
                 rect,
                 depth,
                 maybe_floating,
+                maybe_shadow,
+                maybe_transform,
+                opacity,
+                is_hidden,
                 maybe_x_scroll_state,
                 maybe_y_scroll_state,
                 kid_area,
@@ -857,6 +1009,10 @@ fn set_widget<B, W>(widget: W, idx: Index, ui: &mut Ui<B>)
                 depth: depth,
                 maybe_floating: maybe_floating,
                 kid_area: kid_area,
+                maybe_shadow: maybe_shadow,
+                maybe_transform: maybe_transform,
+                opacity: opacity,
+                is_hidden: is_hidden,
             };
 
             (Some(state),
@@ -943,6 +1099,15 @@ fn set_widget<B, W>(widget: W, idx: Index, ui: &mut Ui<B>)
         None
     };
 
+    // Retrieve the widget's shadow, falling back to the theme's default for the widget's kind.
+    let maybe_shadow = widget.common().style.maybe_shadow.or_else(|| {
+        ui.theme.widget_style::<W::Style>(kind).and_then(|default| default.common.maybe_shadow)
+    });
+
+    let maybe_transform = widget.common().maybe_transform;
+    let opacity = widget.common().opacity;
+    let is_hidden = widget.common().is_hidden;
+
     // Retrieve the area upon which kid widgets will be placed.
     let kid_area = {
         let args: KidAreaArgs<W, B::CharacterCache> = KidAreaArgs {
@@ -1021,6 +1186,13 @@ fn set_widget<B, W>(widget: W, idx: Index, ui: &mut Ui<B>)
             depth: depth,
             kid_area: kid_area,
             maybe_floating: maybe_floating,
+            is_overlay: widget.common().is_overlay,
+            maybe_shadow: maybe_shadow,
+            maybe_transform: maybe_transform,
+            opacity: opacity,
+            is_hidden: is_hidden,
+            is_over: widget.is_over(),
+            is_focusable: widget.is_focusable(),
             crop_kids: crop_kids,
             maybe_y_scroll_state: maybe_y_scroll_state,
             maybe_x_scroll_state: maybe_x_scroll_state,
@@ -1035,6 +1207,10 @@ fn set_widget<B, W>(widget: W, idx: Index, ui: &mut Ui<B>)
         depth: depth,
         maybe_floating: maybe_floating,
         kid_area: kid_area,
+        maybe_shadow: maybe_shadow,
+        maybe_transform: maybe_transform,
+        opacity: opacity,
+        is_hidden: is_hidden,
     });
 
     // Retrieve the widget's unique state and update it via `Widget::update`.
@@ -1072,6 +1248,10 @@ fn set_widget<B, W>(widget: W, idx: Index, ui: &mut Ui<B>)
     let state_has_changed = has_state_updated
         || rect != prev_common.rect
         || depth != prev_common.depth
+        || maybe_shadow != prev_common.maybe_shadow
+        || maybe_transform != prev_common.maybe_transform
+        || opacity != prev_common.opacity
+        || is_hidden != prev_common.is_hidden
         || is_first_set;
 
     // Determine whether or not the widget's `Style` has changed.
@@ -1161,7 +1341,11 @@ impl CommonBuilder {
             maybe_parent_idx: MaybeParent::Unspecified,
             place_on_kid_area: true,
             maybe_graphics_for: None,
+            maybe_transform: None,
+            opacity: 1.0,
+            is_hidden: false,
             is_floating: false,
+            is_overlay: false,
             maybe_x_scroll: None,
             maybe_y_scroll: None,
             crop_kids: false,
@@ -1178,8 +1362,30 @@ impl CommonStyle {
             maybe_x_position: None,
             maybe_y_position: None,
             maybe_depth: None,
+            maybe_shadow: None,
+        }
+    }
+}
+
+impl Shadow {
+    /// A shadow with the given offset, blur and color.
+    pub fn new(offset: [Scalar; 2], blur: Scalar, color: Color) -> Self {
+        Shadow {
+            offset: offset,
+            blur: blur,
+            color: color,
         }
     }
+
+    /// A shadow centred upon the widget, useful for producing a soft glow around its edges.
+    pub fn glow(blur: Scalar, color: Color) -> Self {
+        Shadow::new([0.0, 0.0], blur, color)
+    }
+
+    /// The **Rect** bounding the shadow cast by the given **Rect**, including its blurred edge.
+    pub fn bounding_rect(&self, rect: Rect) -> Rect {
+        rect.shift(self.offset).pad(-self.blur.max(0.0) / 2.0)
+    }
 }
 
 
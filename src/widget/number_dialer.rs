@@ -187,6 +187,10 @@ impl<'a, T, F> Widget for NumberDialer<'a, T, F>
         self.style.clone()
     }
 
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
     /// Update the state of the NumberDialer.
     fn update<B: Backend>(self, args: widget::UpdateArgs<Self, B>) {
         let widget::UpdateArgs { idx, state, rect, style, mut ui, .. } = args;
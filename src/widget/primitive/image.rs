@@ -19,6 +19,13 @@ pub struct Image<T> {
     pub common: widget::CommonBuilder,
     /// The rectangle area of the original source image that should be used.
     pub src_rect: Option<Rect>,
+    /// Optionally divide the image into nine slices so that it may be scaled without distorting
+    /// its corners.
+    ///
+    /// A nine-slice image always fills the widget's **Rect**, so the `fit` is ignored.
+    pub nine_slice: Option<NineSlice>,
+    /// How the image is fitted to the widget's **Rect**. Defaults to `Fit::Stretch`.
+    pub fit: Fit,
     /// Unique styling.
     pub style: Style,
     /// Where the `Image` data is stored.
@@ -37,6 +44,10 @@ pub struct State<T>
 {
     /// The `Texture` used by the `Image` along with its source rectangle.
     pub texture: Option<Texture<T>>,
+    /// The insets with which the image is divided into nine slices, if any.
+    pub nine_slice: Option<NineSlice>,
+    /// How the image is fitted to the widget's **Rect**.
+    pub fit: Fit,
 }
 
 /// The `Texture` used by the `Image` along with its source rectangle.
@@ -48,11 +59,66 @@ pub struct Texture<T> {
     pub src_rect: Rect,
 }
 
+/// Describes how an image is fitted to the **Rect** of its widget.
+///
+/// The aspect ratio of the image is that of its source rectangle, which is the whole texture by
+/// default.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Fit {
+    /// Stretch the image to fill the **Rect**, ignoring its aspect ratio.
+    Stretch,
+    /// Scale the image to the largest size that fits within the **Rect** while preserving its
+    /// aspect ratio. The image is centred, leaving empty space along one axis.
+    Contain,
+    /// Scale the image to the smallest size that covers the **Rect** while preserving its aspect
+    /// ratio. The image is centred and the parts that fall outside of the **Rect** are cropped.
+    Cover,
+    /// Repeat the image at its native size, starting from the top-left corner of the **Rect**.
+    /// Tiles that fall partially outside of the **Rect** are cropped.
+    Tile,
+    /// Draw the image at its native size in the centre of the **Rect**, cropping any parts that
+    /// fall outside of it.
+    Center,
+}
+
+/// The distances from each edge of an image at which it is divided into a 3x3 grid of slices.
+///
+/// When drawn, the corner slices keep their size, the edge slices are stretched along one axis
+/// and the centre slice is stretched along both. This allows skins for buttons, panels and frames
+/// to be scaled to any size without distorting their corners.
+///
+/// The insets are given in pixels of the source image. If the destination area is smaller than
+/// the sum of opposing insets, those insets are scaled down to fit.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct NineSlice {
+    /// The width of the left column.
+    pub left: Scalar,
+    /// The width of the right column.
+    pub right: Scalar,
+    /// The height of the top row.
+    pub top: Scalar,
+    /// The height of the bottom row.
+    pub bottom: Scalar,
+}
+
+/// A texture used to draw the surface of a widget, such as a **Button** or **Canvas**, in place
+/// of its default rectangle.
+pub struct Skin<T> {
+    /// A pointer to the backend texture type.
+    pub texture: Arc<T>,
+    /// The rectangle area of the texture to use. The whole texture is used if `None`.
+    pub src_rect: Option<Rect>,
+    /// The insets used to scale the skin without distorting its corners, if any.
+    pub nine_slice: Option<NineSlice>,
+}
+
 impl<T> PartialEq for State<T>
     where T: ImageSize,
 {
     fn eq(&self, other: &Self) -> bool {
         self.texture == other.texture
+            && self.nine_slice == other.nine_slice
+            && self.fit == other.fit
     }
 }
 
@@ -60,7 +126,7 @@ impl<T> Debug for State<T>
     where T: ImageSize,
 {
     fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
-        write!(f, "{:?}", &self.texture)
+        write!(f, "{:?}, nine_slice: {:?}, fit: {:?}", &self.texture, &self.nine_slice, &self.fit)
     }
 }
 
@@ -102,6 +168,8 @@ impl<T> Image<T> {
         Image {
             common: widget::CommonBuilder::new(),
             src_rect: None,
+            nine_slice: None,
+            fit: Fit::Stretch,
             style: Style::new(),
             src: src,
         }
@@ -114,12 +182,158 @@ impl<T> Image<T> {
 
     builder_methods!{
         pub source_rectangle { src_rect = Some(Rect) }
+        pub nine_slice { nine_slice = Some(NineSlice) }
+        pub fit { fit = Fit }
         pub color { style.maybe_color = Some(Option<Color>) }
     }
 
 }
 
 
+impl Fit {
+
+    /// Pair each area of the given source rectangle with the area of the destination **Rect**
+    /// over which it should be drawn, as `(src_rect, rect)`.
+    ///
+    /// The `src_rect` follows the same convention as **Texture**, where its position describes
+    /// the top-left corner within the texture in pixels.
+    pub fn slices(&self, src_rect: Rect, rect: Rect) -> Vec<(Rect, Rect)> {
+        let (src_x, src_y, src_w, src_h) = src_rect.x_y_w_h();
+        if src_w <= 0.0 || src_h <= 0.0 || rect.w() <= 0.0 || rect.h() <= 0.0 {
+            return Vec::new();
+        }
+
+        // The area of the source visible through the destination when the source is scaled by
+        // the given factor and centred upon it, along with the area it covers.
+        let centred = |scale: Scalar| {
+            let (w, h) = ((src_w * scale).min(rect.w()), (src_h * scale).min(rect.h()));
+            let (visible_w, visible_h) = (w / scale, h / scale);
+            let src_xy = [src_x + (src_w - visible_w) / 2.0, src_y + (src_h - visible_h) / 2.0];
+            let src = Rect::from_xy_dim(src_xy, [visible_w, visible_h]);
+            (src, Rect::from_xy_dim(rect.xy(), [w, h]))
+        };
+
+        match *self {
+            Fit::Stretch => vec![(src_rect, rect)],
+            Fit::Contain => vec![centred((rect.w() / src_w).min(rect.h() / src_h))],
+            Fit::Cover => vec![centred((rect.w() / src_w).max(rect.h() / src_h))],
+            Fit::Center => vec![centred(1.0)],
+            Fit::Tile => {
+                let (l, r, b, t) = rect.l_r_b_t();
+                let mut slices = Vec::new();
+                let mut top = t;
+                while top > b {
+                    let h = src_h.min(top - b);
+                    let mut left = l;
+                    while left < r {
+                        let w = src_w.min(r - left);
+                        let src = Rect::from_xy_dim([src_x, src_y], [w, h]);
+                        let dst = Rect::from_corners([left, top - h], [left + w, top]);
+                        slices.push((src, dst));
+                        left += src_w;
+                    }
+                    top -= src_h;
+                }
+                slices
+            },
+        }
+    }
+
+}
+
+
+impl NineSlice {
+
+    /// Divide an image using the given insets from each edge.
+    pub fn new(left: Scalar, right: Scalar, top: Scalar, bottom: Scalar) -> Self {
+        NineSlice {
+            left: left,
+            right: right,
+            top: top,
+            bottom: bottom,
+        }
+    }
+
+    /// Divide an image using the same inset from every edge.
+    pub fn uniform(inset: Scalar) -> Self {
+        NineSlice::new(inset, inset, inset, inset)
+    }
+
+    /// Pair each slice of the given source rectangle with the area of the destination **Rect**
+    /// over which it should be drawn, as `(src_rect, rect)`.
+    ///
+    /// The `src_rect` follows the same convention as **Texture**, where its position describes
+    /// the top-left corner within the texture in pixels. Empty slices are omitted.
+    pub fn slices(&self, src_rect: Rect, rect: Rect) -> Vec<(Rect, Rect)> {
+        // The size of each inset within the destination, scaled down to fit if necessary.
+        fn fit(a: Scalar, b: Scalar, len: Scalar) -> (Scalar, Scalar) {
+            let (a, b) = (a.max(0.0), b.max(0.0));
+            let scale = if a + b > len && a + b > 0.0 { len / (a + b) } else { 1.0 };
+            (a * scale, b * scale)
+        }
+
+        let (src_x, src_y, src_w, src_h) = src_rect.x_y_w_h();
+        let (src_l, src_r) = fit(self.left, self.right, src_w);
+        let (src_t, src_b) = fit(self.top, self.bottom, src_h);
+        let (l, r, b, t) = rect.l_r_b_t();
+        let (dst_l, dst_r) = fit(src_l, src_r, rect.w());
+        let (dst_t, dst_b) = fit(src_t, src_b, rect.h());
+
+        // The start and length of each column and row within the source, from the top-left.
+        let src_columns = [(src_x, src_l),
+                           (src_x + src_l, src_w - src_l - src_r),
+                           (src_x + src_w - src_r, src_r)];
+        let src_rows = [(src_y, src_t),
+                        (src_y + src_t, src_h - src_t - src_b),
+                        (src_y + src_h - src_b, src_b)];
+        // The range of each column and row within the destination, from the top-left.
+        let dst_columns = [(l, l + dst_l), (l + dst_l, r - dst_r), (r - dst_r, r)];
+        let dst_rows = [(t - dst_t, t), (b + dst_b, t - dst_t), (b, b + dst_b)];
+
+        let mut slices = Vec::with_capacity(9);
+        for (&(src_y, src_h), &(dst_b, dst_t)) in src_rows.iter().zip(dst_rows.iter()) {
+            for (&(src_x, src_w), &(dst_l, dst_r)) in src_columns.iter().zip(dst_columns.iter()) {
+                if src_w <= 0.0 || src_h <= 0.0 || dst_r <= dst_l || dst_t <= dst_b {
+                    continue;
+                }
+                let src = Rect::from_xy_dim([src_x, src_y], [src_w, src_h]);
+                let dst = Rect::from_corners([dst_l, dst_b], [dst_r, dst_t]);
+                slices.push((src, dst));
+            }
+        }
+        slices
+    }
+
+}
+
+
+impl<T> Skin<T> {
+
+    /// Construct a new **Skin** from the given texture.
+    pub fn new(texture: Arc<T>) -> Self {
+        Skin {
+            texture: texture,
+            src_rect: None,
+            nine_slice: None,
+        }
+    }
+
+    builder_methods!{
+        pub source_rectangle { src_rect = Some(Rect) }
+        pub nine_slice { nine_slice = Some(NineSlice) }
+    }
+
+    /// Produce an **Image** widget that draws the **Skin**.
+    pub fn image(&self) -> Image<T> {
+        let mut image = Image::from_texture(self.texture.clone());
+        image.src_rect = self.src_rect;
+        image.nine_slice = self.nine_slice;
+        image
+    }
+
+}
+
+
 impl<T> Widget for Image<T>
     where T: Any + ImageSize,
 {
@@ -141,6 +355,8 @@ impl<T> Widget for Image<T>
     fn init_state(&self) -> Self::State {
         State {
             texture: None,
+            nine_slice: None,
+            fit: Fit::Stretch,
         }
     }
 
@@ -174,7 +390,15 @@ impl<T> Widget for Image<T>
 
     fn update<B: Backend>(self, args: widget::UpdateArgs<Self, B>) {
         let widget::UpdateArgs { state, .. } = args;
-        let Image { src_rect, src, .. } = self;
+        let Image { src_rect, nine_slice, fit, src, .. } = self;
+
+        if state.nine_slice != nine_slice {
+            state.update(|state| state.nine_slice = nine_slice);
+        }
+
+        if state.fit != fit {
+            state.update(|state| state.fit = fit);
+        }
 
         match src {
             Source::Texture(texture) => {
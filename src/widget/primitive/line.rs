@@ -11,7 +11,8 @@ use {
     Theme,
     Widget,
 };
-use utils::{vec2_add, vec2_sub};
+use graph;
+use utils::{self, vec2_add, vec2_sub};
 use widget;
 
 
@@ -50,6 +51,26 @@ pub struct Style {
     pub maybe_thickness: Option<Scalar>,
     /// The style with which the ends of the line are drawn.
     pub maybe_cap: Option<Cap>,
+    /// The style with which the corners between connected segments are drawn.
+    pub maybe_join: Option<Join>,
+    /// The length of each dash for a **Dashed** pattern.
+    pub maybe_dash_length: Option<Scalar>,
+    /// The length of the gap between each dash or dot for a **Dashed** or **Dotted** pattern.
+    pub maybe_gap_length: Option<Scalar>,
+    /// The distance into the pattern at which the line begins.
+    pub maybe_phase: Option<Scalar>,
+}
+
+/// The lengths describing how a **Dashed** or **Dotted** line is broken up.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Dash {
+    /// The length of each dash. Unused by the **Dotted** pattern, as each dot has a diameter equal
+    /// to the thickness of the line.
+    pub length: Scalar,
+    /// The length of the gap between the end of one dash or dot and the start of the next.
+    pub gap: Scalar,
+    /// The distance into the pattern at which the line begins.
+    pub phase: Scalar,
 }
 
 /// Unique kind for the widget.
@@ -67,10 +88,31 @@ pub enum Pattern {
 #[allow(dead_code)]
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum Cap {
+    /// The line ends exactly at its end points.
     Flat,
+    /// The line ends with a semicircle whose diameter is the thickness of the line.
+    Round,
+    /// The line is extended past its end points by half of its thickness.
+    Square,
+}
+
+/// How the corner between two connected segments of a line should be drawn.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Join {
+    /// The outer edges of the segments are extended until they meet at a point.
+    ///
+    /// If the ratio of the length of the miter to the thickness of the line exceeds the given
+    /// limit, a **Bevel** is drawn instead. This avoids long spikes at sharp corners.
+    Miter(Scalar),
+    /// The corner is rounded with a radius of half the thickness of the line.
     Round,
+    /// The outer corners of the segments are joined by a straight edge.
+    Bevel,
 }
 
+/// The miter limit used by the default **Join**, matching the default used by SVG.
+pub const DEFAULT_MITER_LIMIT: Scalar = 4.0;
+
 
 impl Line {
 
@@ -153,6 +195,24 @@ impl Line {
         self
     }
 
+    /// The length of each dash for a **Dashed** pattern.
+    pub fn dash_length(mut self, length: Scalar) -> Self {
+        self.style.set_dash_length(length);
+        self
+    }
+
+    /// The length of the gap between each dash or dot.
+    pub fn gap_length(mut self, length: Scalar) -> Self {
+        self.style.set_gap_length(length);
+        self
+    }
+
+    /// The distance into the **Dashed** or **Dotted** pattern at which the line begins.
+    pub fn phase(mut self, phase: Scalar) -> Self {
+        self.style.set_phase(phase);
+        self
+    }
+
 }
 
 
@@ -165,6 +225,10 @@ impl Style {
             maybe_color: None,
             maybe_thickness: None,
             maybe_cap: None,
+            maybe_join: None,
+            maybe_dash_length: None,
+            maybe_gap_length: None,
+            maybe_phase: None,
         }
     }
 
@@ -207,6 +271,30 @@ impl Style {
         self
     }
 
+    /// The style for the corners between connected segments.
+    pub fn join(mut self, join: Join) -> Self {
+        self.set_join(join);
+        self
+    }
+
+    /// The style with some given dash length.
+    pub fn dash_length(mut self, length: Scalar) -> Self {
+        self.set_dash_length(length);
+        self
+    }
+
+    /// The style with some given gap length.
+    pub fn gap_length(mut self, length: Scalar) -> Self {
+        self.set_gap_length(length);
+        self
+    }
+
+    /// The style with some given phase.
+    pub fn phase(mut self, phase: Scalar) -> Self {
+        self.set_phase(phase);
+        self
+    }
+
     /// Set the pattern for the line.
     pub fn set_pattern(&mut self, pattern: Pattern) {
         self.maybe_pattern = Some(pattern);
@@ -227,6 +315,26 @@ impl Style {
         self.maybe_cap = Some(cap);
     }
 
+    /// Set the **Join** for the line.
+    pub fn set_join(&mut self, join: Join) {
+        self.maybe_join = Some(join);
+    }
+
+    /// Set the length of each dash for a **Dashed** pattern.
+    pub fn set_dash_length(&mut self, length: Scalar) {
+        self.maybe_dash_length = Some(length);
+    }
+
+    /// Set the length of the gap between each dash or dot.
+    pub fn set_gap_length(&mut self, length: Scalar) {
+        self.maybe_gap_length = Some(length);
+    }
+
+    /// Set the distance into the pattern at which the line begins.
+    pub fn set_phase(&mut self, phase: Scalar) {
+        self.maybe_phase = Some(phase);
+    }
+
     /// The Pattern for the Line.
     pub fn get_pattern(&self, theme: &Theme) -> Pattern {
         const DEFAULT_PATTERN: Pattern = Pattern::Solid;
@@ -258,6 +366,60 @@ impl Style {
         })).unwrap_or(DEFAULT_CAP)
     }
 
+    /// The styling for the corners between connected segments of the Line.
+    pub fn get_join(&self, theme: &Theme) -> Join {
+        const DEFAULT_JOIN: Join = Join::Miter(DEFAULT_MITER_LIMIT);
+        self.maybe_join.or_else(|| theme.widget_style::<Style>(KIND).map(|default| {
+            default.style.maybe_join.unwrap_or(DEFAULT_JOIN)
+        })).unwrap_or(DEFAULT_JOIN)
+    }
+
+    /// The length of each dash for a **Dashed** pattern.
+    pub fn get_dash_length(&self, theme: &Theme) -> Scalar {
+        const DEFAULT_DASH_LENGTH: Scalar = 6.0;
+        self.maybe_dash_length.or_else(|| theme.widget_style::<Style>(KIND).map(|default| {
+            default.style.maybe_dash_length.unwrap_or(DEFAULT_DASH_LENGTH)
+        })).unwrap_or(DEFAULT_DASH_LENGTH)
+    }
+
+    /// The length of the gap between each dash or dot.
+    pub fn get_gap_length(&self, theme: &Theme) -> Scalar {
+        const DEFAULT_GAP_LENGTH: Scalar = 4.0;
+        self.maybe_gap_length.or_else(|| theme.widget_style::<Style>(KIND).map(|default| {
+            default.style.maybe_gap_length.unwrap_or(DEFAULT_GAP_LENGTH)
+        })).unwrap_or(DEFAULT_GAP_LENGTH)
+    }
+
+    /// The distance into the pattern at which the line begins.
+    pub fn get_phase(&self, theme: &Theme) -> Scalar {
+        const DEFAULT_PHASE: Scalar = 0.0;
+        self.maybe_phase.or_else(|| theme.widget_style::<Style>(KIND).map(|default| {
+            default.style.maybe_phase.unwrap_or(DEFAULT_PHASE)
+        })).unwrap_or(DEFAULT_PHASE)
+    }
+
+    /// The **Dash** lengths for the Line.
+    pub fn get_dash(&self, theme: &Theme) -> Dash {
+        Dash {
+            length: self.get_dash_length(theme),
+            gap: self.get_gap_length(theme),
+            phase: self.get_phase(theme),
+        }
+    }
+
+}
+
+
+/// Whether or not the given point lies over the **Line** stored within the given **Container**.
+///
+/// Only points within half the line's thickness of the line are considered to be over it.
+pub fn is_over_widget(container: &graph::Container, xy: Point, theme: &Theme) -> bool {
+    container.unique_widget_state::<Line>()
+        .map(|line| {
+            let half_thickness = line.style.get_thickness(theme) / 2.0;
+            utils::distance_to_segment(line.state.start, line.state.end, xy) <= half_thickness
+        })
+        .unwrap_or_else(|| widget::is_over_rect(container, xy, theme))
 }
 
 
@@ -288,6 +450,10 @@ impl Widget for Line {
         self.style.clone()
     }
 
+    fn is_over(&self) -> widget::IsOverFn {
+        is_over_widget
+    }
+
     /// Update the state of the Line.
     fn update<B: Backend>(self, args: widget::UpdateArgs<Self, B>) {
         let widget::UpdateArgs { rect, state, .. } = args;
@@ -320,3 +486,286 @@ impl Colorable for Line {
         self
     }
 }
+
+
+/// Split the path described by the given points into the sub-paths that should be stroked in
+/// order to draw the given **Pattern**.
+///
+/// The pattern is carried continuously across the vertices of the path, so that a dash may begin
+/// on one segment and end on another.
+///
+/// - **Solid** produces the path itself.
+/// - **Dashed** produces a sub-path for each dash.
+/// - **Dotted** produces a zero-length sub-path at the centre of each dot. These should be drawn
+/// with a **Cap::Round** so that each dot has a diameter equal to `thickness`.
+pub fn pattern_paths(points: &[Point],
+                     pattern: Pattern,
+                     dash: Dash,
+                     thickness: Scalar) -> Vec<Vec<Point>>
+{
+    // The length of the "on" and "off" parts of the pattern.
+    let (on, off) = match pattern {
+        Pattern::Solid => (0.0, 0.0),
+        Pattern::Dashed => (dash.length.max(0.0), dash.gap.max(0.0)),
+        Pattern::Dotted => (0.0, thickness.max(0.0) + dash.gap.max(0.0)),
+    };
+    let period = on + off;
+    if pattern == Pattern::Solid || period <= 0.0 || points.is_empty() {
+        return vec![points.to_vec()];
+    }
+
+    let mut paths = Vec::new();
+
+    // The distance travelled into the current period of the pattern.
+    let mut pos = ((dash.phase % period) + period) % period;
+    // The sub-path of the dash that we're currently within, if any.
+    let mut current = if pos < on { Some(vec![points[0]]) } else { None };
+    if on == 0.0 && pos == 0.0 {
+        paths.push(vec![points[0], points[0]]);
+    }
+
+    for window in points.windows(2) {
+        let (a, b) = (window[0], window[1]);
+        let (dx, dy) = (b[0] - a[0], b[1] - a[1]);
+        let len = (dx * dx + dy * dy).sqrt();
+        if len <= 0.0 {
+            continue;
+        }
+        let lerp = |d: Scalar| [a[0] + dx * d / len, a[1] + dy * d / len];
+
+        // The distance travelled along the current segment.
+        let mut d = 0.0;
+        loop {
+            if pos < on {
+                // We're within a dash.
+                let remaining = on - pos;
+                if d + remaining > len {
+                    if let Some(ref mut path) = current {
+                        path.push(b);
+                    }
+                    pos += len - d;
+                    break;
+                }
+                d += remaining;
+                pos = on;
+                if let Some(mut path) = current.take() {
+                    path.push(lerp(d));
+                    paths.push(path);
+                }
+            } else {
+                // We're within a gap.
+                let remaining = period - pos;
+                if d + remaining > len {
+                    pos += len - d;
+                    break;
+                }
+                d += remaining;
+                pos = 0.0;
+                let start = lerp(d);
+                if on == 0.0 {
+                    paths.push(vec![start, start]);
+                } else {
+                    current = Some(vec![start]);
+                }
+            }
+        }
+    }
+
+    // Only keep the final dash if it has some length.
+    if let Some(path) = current {
+        if pos > 0.0 {
+            paths.push(path);
+        }
+    }
+
+    paths
+}
+
+
+/// Tessellate the path described by the given points into a list of triangles that, together,
+/// cover the area of a single stroke of the given thickness.
+///
+/// Unlike stroking each segment independently, corners between connected segments are filled
+/// using the given **Join** and only the two ends of the path are drawn with the given **Cap**.
+/// If the last point is equal to the first, the path is considered closed and the corner at the
+/// first point is joined rather than capped.
+///
+/// A path consisting of a single point (e.g. a zero-length dash) produces a dot when drawn with
+/// a **Round** or **Square** cap.
+pub fn stroke_triangles(points: &[Point], thickness: Scalar, cap: Cap, join: Join)
+    -> Vec<[Point; 3]>
+{
+    use std::f64::consts::PI;
+
+    let mut triangles = Vec::new();
+    let hw = thickness / 2.0;
+    if !(hw > 0.0) || points.is_empty() {
+        return triangles;
+    }
+
+    // Remove consecutive duplicates, as they have no direction.
+    let mut points: Vec<Point> = points.to_vec();
+    points.dedup();
+
+    let add = |a: Point, b: Point| [a[0] + b[0], a[1] + b[1]];
+    let sub = |a: Point, b: Point| [a[0] - b[0], a[1] - b[1]];
+    let scale = |a: Point, s: Scalar| [a[0] * s, a[1] * s];
+    let dot = |a: Point, b: Point| a[0] * b[0] + a[1] * b[1];
+    let cross = |a: Point, b: Point| a[0] * b[1] - a[1] * b[0];
+
+    // Fan triangles from `fan_centre` to the arc centred at `centre` that begins at the offset
+    // `from` and sweeps through `angle` radians.
+    let arc = |triangles: &mut Vec<[Point; 3]>,
+               fan_centre: Point,
+               centre: Point,
+               from: Point,
+               angle: Scalar| {
+        const STEP: Scalar = PI / 16.0;
+        let n = ((angle.abs() / STEP).ceil() as usize).max(1);
+        let step = angle / n as Scalar;
+        let mut prev = add(centre, from);
+        for i in 1..n + 1 {
+            let (sin, cos) = (step * i as Scalar).sin_cos();
+            let offset = [from[0] * cos - from[1] * sin, from[0] * sin + from[1] * cos];
+            let next = add(centre, offset);
+            triangles.push([fan_centre, prev, next]);
+            prev = next;
+        }
+    };
+
+    // A single point can only be drawn as a dot.
+    if points.len() == 1 {
+        let p = points[0];
+        match cap {
+            Cap::Flat => (),
+            Cap::Round => arc(&mut triangles, p, p, [hw, 0.0], 2.0 * PI),
+            Cap::Square => {
+                let (l, r, b, t) = (p[0] - hw, p[0] + hw, p[1] - hw, p[1] + hw);
+                triangles.push([[l, b], [l, t], [r, t]]);
+                triangles.push([[l, b], [r, t], [r, b]]);
+            },
+        }
+        return triangles;
+    }
+
+    let is_closed = points.len() > 2 && points[0] == points[points.len() - 1];
+    if is_closed {
+        points.pop();
+    }
+    let num_points = points.len();
+    let num_segments = if is_closed { num_points } else { num_points - 1 };
+    let segment = |i: usize| (points[i], points[(i + 1) % num_points]);
+
+    // The unit direction, the normal (scaled to the half-width) and the length of each segment.
+    let segments: Vec<(Point, Point, Scalar)> = (0..num_segments).map(|i| {
+        let (a, b) = segment(i);
+        let v = sub(b, a);
+        let len = dot(v, v).sqrt();
+        let d = scale(v, 1.0 / len);
+        (d, [-d[1] * hw, d[0] * hw], len)
+    }).collect();
+
+    // The left and right corners of the start and end of each segment's quad.
+    let mut quads: Vec<[Point; 4]> = (0..num_segments).map(|i| {
+        let (a, b) = segment(i);
+        let n = segments[i].1;
+        [add(a, n), sub(a, n), add(b, n), sub(b, n)]
+    }).collect();
+
+    // The joins at each vertex between two segments.
+    let joints = if is_closed { 0..num_points } else { 1..num_points - 1 };
+    for vertex in joints {
+        let prev = (vertex + num_segments - 1) % num_segments;
+        let next = vertex % num_segments;
+        let p = points[vertex];
+        let (d0, n0, len0) = segments[prev];
+        let (d1, n1, len1) = segments[next];
+        let turn = cross(d0, d1);
+        let cos = dot(d0, d1);
+
+        // No join is necessary where the path continues straight on.
+        if turn.abs() < 1e-9 && cos > 0.0 {
+            continue;
+        }
+
+        // The sign of the normal on the outer side of the corner.
+        let side = if turn > 0.0 { -1.0 } else { 1.0 };
+        let outer0 = add(p, scale(n0, side));
+        let outer1 = add(p, scale(n1, side));
+
+        // The offset from the vertex to the intersection of the edges on either side.
+        let miter = if 1.0 + cos > 1e-9 {
+            Some(scale(add(n0, n1), 1.0 / (1.0 + cos)))
+        } else {
+            None
+        };
+
+        // Where the inner edges meet before the ends of both segments, end both quads at their
+        // intersection to avoid overlap. Otherwise, the quads simply overlap on the inner side.
+        let fan_centre = match miter {
+            Some(m) if dot(m, d0).abs() <= len0 / 2.0 && dot(m, d1).abs() <= len1 / 2.0 => {
+                let inner = sub(p, scale(m, side));
+                // The left corners are at index 0 and 2; the right corners at 1 and 3.
+                let (end, start) = if side > 0.0 { (3, 1) } else { (2, 0) };
+                quads[prev][end] = inner;
+                quads[next][start] = inner;
+                inner
+            },
+            _ => p,
+        };
+
+        match join {
+            Join::Miter(limit) => match miter {
+                Some(m) if dot(m, m).sqrt() / hw <= limit => {
+                    let tip = add(p, scale(m, side));
+                    triangles.push([fan_centre, outer0, tip]);
+                    triangles.push([fan_centre, tip, outer1]);
+                },
+                _ => triangles.push([fan_centre, outer0, outer1]),
+            },
+            Join::Round => {
+                let from = scale(n0, side);
+                let to = scale(n1, side);
+                // Where the path reverses direction, round the end of the previous segment.
+                let angle = if miter.is_some() {
+                    cross(from, to).atan2(dot(from, to))
+                } else {
+                    -PI
+                };
+                arc(&mut triangles, fan_centre, p, from, angle);
+            },
+            Join::Bevel => triangles.push([fan_centre, outer0, outer1]),
+        }
+    }
+
+    for quad in &quads {
+        triangles.push([quad[0], quad[2], quad[3]]);
+        triangles.push([quad[0], quad[3], quad[1]]);
+    }
+
+    // The caps at either end of an open path.
+    if !is_closed {
+        let (start, end) = (points[0], points[num_points - 1]);
+        let (d_start, n_start, _) = segments[0];
+        let (d_end, n_end, _) = segments[num_segments - 1];
+        match cap {
+            Cap::Flat => (),
+            Cap::Round => {
+                arc(&mut triangles, start, start, n_start, PI);
+                arc(&mut triangles, end, end, n_end, -PI);
+            },
+            Cap::Square => {
+                let back = scale(d_start, -hw);
+                let (a, b) = (add(start, n_start), sub(start, n_start));
+                triangles.push([a, b, add(b, back)]);
+                triangles.push([a, add(b, back), add(a, back)]);
+                let forward = scale(d_end, hw);
+                let (a, b) = (add(end, n_end), sub(end, n_end));
+                triangles.push([a, b, add(b, forward)]);
+                triangles.push([a, add(b, forward), add(a, forward)]);
+            },
+        }
+    }
+
+    triangles
+}
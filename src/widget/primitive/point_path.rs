@@ -8,12 +8,14 @@ use {
     Rect,
     Scalar,
     Sizeable,
+    Theme,
     Widget,
 };
-use utils::{vec2_add, vec2_sub};
+use graph;
+use utils::{self, vec2_add, vec2_sub};
 use widget;
 
-pub use super::line::Pattern;
+pub use super::line::{Join, Pattern};
 pub use super::line::Style;
 
 
@@ -151,6 +153,48 @@ impl<I> PointPath<I> {
         self
     }
 
+    /// The style for the corners between each of the path's segments.
+    pub fn join(mut self, join: Join) -> Self {
+        self.style.set_join(join);
+        self
+    }
+
+    /// The length of each dash for a **Dashed** pattern.
+    pub fn dash_length(mut self, length: Scalar) -> Self {
+        self.style.set_dash_length(length);
+        self
+    }
+
+    /// The length of the gap between each dash or dot.
+    pub fn gap_length(mut self, length: Scalar) -> Self {
+        self.style.set_gap_length(length);
+        self
+    }
+
+    /// The distance into the **Dashed** or **Dotted** pattern at which the path begins.
+    ///
+    /// The pattern continues across each of the path's points.
+    pub fn phase(mut self, phase: Scalar) -> Self {
+        self.style.set_phase(phase);
+        self
+    }
+
+}
+
+
+/// Whether or not the given point lies over the **PointPath** stored within the given
+/// **Container**.
+///
+/// Only points within half the line's thickness of one of the path's segments are considered to
+/// be over it.
+pub fn is_over_widget(container: &graph::Container, xy: Point, theme: &Theme) -> bool {
+    container.state_and_style::<State, Style>()
+        .map(|path| {
+            let half_thickness = path.style.get_thickness(theme) / 2.0;
+            path.state.points.windows(2)
+                .any(|w| utils::distance_to_segment(w[0], w[1], xy) <= half_thickness)
+        })
+        .unwrap_or_else(|| widget::is_over_rect(container, xy, theme))
 }
 
 
@@ -182,6 +226,10 @@ impl<I> Widget for PointPath<I>
         self.style.clone()
     }
 
+    fn is_over(&self) -> widget::IsOverFn {
+        is_over_widget
+    }
+
     /// Update the state of the Line.
     fn update<B: Backend>(self, args: widget::UpdateArgs<Self, B>) {
         use utils::{iter_diff, IterDiff};
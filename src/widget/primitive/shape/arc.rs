@@ -0,0 +1,357 @@
+use {
+    Backend,
+    Color,
+    Colorable,
+    FillGradient,
+    Gradientable,
+    LineStyle,
+    Point,
+    Scalar,
+    Sizeable,
+    Theme,
+    Widget,
+};
+use super::Style as Style;
+use super::FLATTENING_TOLERANCE;
+use graph;
+use utils::{distance_to_segment, is_over_triangle};
+use widget;
+
+
+/// A basic, non-interactive widget for drawing a section of a circle.
+///
+/// With an `inner_radius` of zero the **Arc** describes a pie slice, otherwise it describes a
+/// section of a ring. Angles are given in radians, where `0.0` points to the right and angles
+/// increase anti-clockwise.
+///
+/// The **Arc** is centred upon its **Rect**, and its outer radius is half the shorter side of the
+/// **Rect**.
+#[derive(Copy, Clone, Debug)]
+pub struct Arc {
+    /// Data necessary and common for all widget builder types.
+    pub common: widget::CommonBuilder,
+    /// Unique styling for the **Arc**.
+    pub style: Style,
+    /// The section of the circle described by the **Arc**.
+    pub section: Section,
+}
+
+/// Unique state for the **Arc**.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct State {
+    kind: Kind,
+    /// The section of the circle described by the **Arc**.
+    pub section: Section,
+}
+
+/// Whether the **Arc** is drawn as an **Outline** or **Fill**ed with a color.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Kind {
+    /// Only the **Outline** of the **Arc** is drawn.
+    Outline,
+    /// The **Arc**'s area is **Fill**ed with some color.
+    Fill,
+}
+
+/// The section of a circle described by an **Arc**.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Section {
+    /// The angle at which the section begins in radians.
+    pub start: Scalar,
+    /// The angle at which the section ends in radians.
+    ///
+    /// If `end` is less than `start`, the section is swept clockwise.
+    pub end: Scalar,
+    /// The radius of the hole at the centre of the section.
+    pub inner_radius: Scalar,
+}
+
+/// Unique Kind for the widget.
+pub const KIND: widget::Kind = "Arc";
+
+
+fn rad_to_dim(radius: Scalar) -> [Scalar; 2] {
+    let side = radius * 2.0;
+    [side, side]
+}
+
+
+impl Arc {
+
+    /// Build an **Arc** with the given radius, start and end angles and style.
+    pub fn styled(radius: Scalar, start: Scalar, end: Scalar, style: Style) -> Self {
+        Arc {
+            common: widget::CommonBuilder::new(),
+            style: style,
+            section: Section {
+                start: start,
+                end: end,
+                inner_radius: 0.0,
+            },
+        }.wh(rad_to_dim(radius))
+    }
+
+    /// Build a new **Fill**ed **Arc**.
+    pub fn fill(radius: Scalar, start: Scalar, end: Scalar) -> Self {
+        Arc::styled(radius, start, end, Style::fill())
+    }
+
+    /// Build a new **Arc** **Fill**ed with the given color.
+    pub fn fill_with(radius: Scalar, start: Scalar, end: Scalar, color: Color) -> Self {
+        Arc::styled(radius, start, end, Style::fill_with(color))
+    }
+
+    /// Build a new **Outline**d **Arc**.
+    pub fn outline(radius: Scalar, start: Scalar, end: Scalar) -> Self {
+        Arc::styled(radius, start, end, Style::outline())
+    }
+
+    /// Build a new **Arc** **Outline**d with the given style.
+    pub fn outline_styled(radius: Scalar,
+                          start: Scalar,
+                          end: Scalar,
+                          line_style: LineStyle) -> Self
+    {
+        Arc::styled(radius, start, end, Style::outline_styled(line_style))
+    }
+
+    /// The radius of the hole at the centre of the **Arc**, producing a section of a ring.
+    pub fn inner_radius(mut self, radius: Scalar) -> Self {
+        self.section.inner_radius = radius;
+        self
+    }
+
+}
+
+
+impl Section {
+
+    /// The angle swept by the section, limited to a single revolution.
+    pub fn sweep(&self) -> Scalar {
+        use std::f64::consts::PI;
+        let sweep = self.end - self.start;
+        sweep.max(-2.0 * PI).min(2.0 * PI)
+    }
+
+    /// Whether or not the section describes a full revolution.
+    pub fn is_full_circle(&self) -> bool {
+        use std::f64::consts::PI;
+        self.sweep().abs() >= 2.0 * PI
+    }
+
+    /// The triangles filling the section of the circle with the given centre and outer radius.
+    pub fn triangles(&self, centre: Point, radius: Scalar) -> Vec<[Point; 3]> {
+        self.triangles_within(centre, radius, FLATTENING_TOLERANCE)
+    }
+
+    /// The same as **Section::triangles**, but with the curve flattened to within the given
+    /// tolerance rather than `FLATTENING_TOLERANCE`.
+    pub fn triangles_within(&self, centre: Point, radius: Scalar, tolerance: Scalar)
+        -> Vec<[Point; 3]>
+    {
+        let inner_radius = self.inner_radius.max(0.0).min(radius);
+        let outer = points_within(centre, radius, self.start, self.sweep(), tolerance);
+        let mut triangles = Vec::with_capacity(outer.len() * 2);
+        if inner_radius > 0.0 {
+            // The inner edge shares the outer edge's angles so that they may be joined in pairs.
+            let num = outer.len() - 1;
+            let inner = points_with(centre, inner_radius, self.start, self.sweep(), num);
+            for i in 0..outer.len() - 1 {
+                triangles.push([outer[i], outer[i + 1], inner[i + 1]]);
+                triangles.push([outer[i], inner[i + 1], inner[i]]);
+            }
+        } else {
+            for window in outer.windows(2) {
+                triangles.push([centre, window[0], window[1]]);
+            }
+        }
+        triangles
+    }
+
+    /// The closed paths outlining the section of the circle with the given centre and outer
+    /// radius.
+    ///
+    /// A full ring is outlined by two separate circles, otherwise the outline is a single path.
+    pub fn outlines(&self, centre: Point, radius: Scalar) -> Vec<Vec<Point>> {
+        self.outlines_within(centre, radius, FLATTENING_TOLERANCE)
+    }
+
+    /// The same as **Section::outlines**, but with the curves flattened to within the given
+    /// tolerance rather than `FLATTENING_TOLERANCE`.
+    pub fn outlines_within(&self, centre: Point, radius: Scalar, tolerance: Scalar)
+        -> Vec<Vec<Point>>
+    {
+        let inner_radius = self.inner_radius.max(0.0).min(radius);
+        let (start, sweep) = (self.start, self.sweep());
+        let mut outer = points_within(centre, radius, start, sweep, tolerance);
+        if self.is_full_circle() {
+            let mut paths = vec![outer];
+            if inner_radius > 0.0 {
+                paths.push(points_within(centre, inner_radius, start, sweep, tolerance));
+            }
+            return paths;
+        }
+        if inner_radius > 0.0 {
+            let inner = points_within(centre, inner_radius, start, sweep, tolerance);
+            outer.extend(inner.into_iter().rev());
+        } else {
+            outer.push(centre);
+        }
+        let first = outer[0];
+        outer.push(first);
+        vec![outer]
+    }
+
+}
+
+
+/// The number of line segments with which a circular arc of the given radius sweeping the given
+/// angle is approximated.
+///
+/// The number is chosen so that no segment strays further than `FLATTENING_TOLERANCE` from the
+/// true arc, meaning that larger arcs are described with more segments.
+pub fn num_segments(radius: Scalar, sweep: Scalar) -> usize {
+    num_segments_within(radius, sweep, FLATTENING_TOLERANCE)
+}
+
+/// The same as **num_segments**, but with no segment straying further than the given tolerance
+/// from the true arc.
+pub fn num_segments_within(radius: Scalar, sweep: Scalar, tolerance: Scalar) -> usize {
+    const MAX_SEGMENTS: usize = 1024;
+    if radius <= tolerance {
+        return 1;
+    }
+    // The angle subtended by a segment whose distance from the arc is the tolerance.
+    let step = 2.0 * (1.0 - tolerance / radius).acos();
+    let num = (sweep.abs() / step).ceil() as usize;
+    ::std::cmp::min(::std::cmp::max(num, 1), MAX_SEGMENTS)
+}
+
+
+/// The points along the circular arc with the given centre and radius, beginning at the `start`
+/// angle and sweeping the given angle (both in radians).
+pub fn points(centre: Point, radius: Scalar, start: Scalar, sweep: Scalar) -> Vec<Point> {
+    points_within(centre, radius, start, sweep, FLATTENING_TOLERANCE)
+}
+
+/// The same as **points**, but with the arc flattened to within the given tolerance.
+pub fn points_within(centre: Point,
+                     radius: Scalar,
+                     start: Scalar,
+                     sweep: Scalar,
+                     tolerance: Scalar) -> Vec<Point>
+{
+    points_with(centre, radius, start, sweep, num_segments_within(radius, sweep, tolerance))
+}
+
+/// The points along a circular arc divided into the given number of segments.
+fn points_with(centre: Point,
+               radius: Scalar,
+               start: Scalar,
+               sweep: Scalar,
+               num: usize) -> Vec<Point>
+{
+    let step = sweep / num as Scalar;
+    (0..num + 1).map(|i| {
+        let (sin, cos) = (start + step * i as Scalar).sin_cos();
+        [centre[0] + radius * cos, centre[1] + radius * sin]
+    }).collect()
+}
+
+
+/// Whether or not the given point lies over the **Arc** stored within the given **Container**.
+///
+/// When **Outline**d, only points within half the line's thickness of the **Arc**'s outlines are
+/// over it. Otherwise, only points within the section of the circle described by the **Arc** are
+/// considered to be over it.
+pub fn is_over_widget(container: &graph::Container, xy: Point, theme: &Theme) -> bool {
+    container.unique_widget_state::<Arc>()
+        .map(|arc| {
+            let (centre, dim) = container.rect.xy_dim();
+            let radius = dim[0].min(dim[1]) / 2.0;
+            match arc.style {
+                Style::Fill(_) | Style::Gradient(_) =>
+                    arc.state.section.triangles(centre, radius).into_iter()
+                        .any(|t| is_over_triangle(t, xy)),
+                Style::Outline(line_style) => {
+                    let half_thickness = line_style.get_thickness(theme) / 2.0;
+                    arc.state.section.outlines(centre, radius).iter()
+                        .any(|path| path.windows(2)
+                            .any(|w| distance_to_segment(w[0], w[1], xy) <= half_thickness))
+                },
+            }
+        })
+        .unwrap_or_else(|| widget::is_over_rect(container, xy, theme))
+}
+
+
+impl Widget for Arc {
+    type State = State;
+    type Style = Style;
+
+    fn common(&self) -> &widget::CommonBuilder {
+        &self.common
+    }
+
+    fn common_mut(&mut self) -> &mut widget::CommonBuilder {
+        &mut self.common
+    }
+
+    fn unique_kind(&self) -> &'static str {
+        KIND
+    }
+
+    fn init_state(&self) -> State {
+        State {
+            kind: Kind::Fill,
+            section: Section {
+                start: 0.0,
+                end: 0.0,
+                inner_radius: 0.0,
+            },
+        }
+    }
+
+    fn style(&self) -> Style {
+        self.style.clone()
+    }
+
+    fn is_over(&self) -> widget::IsOverFn {
+        is_over_widget
+    }
+
+    /// Update the state of the Arc.
+    fn update<B: Backend>(self, args: widget::UpdateArgs<Self, B>) {
+        let widget::UpdateArgs { state, style, .. } = args;
+
+        let kind = match *style {
+            Style::Fill(_) | Style::Gradient(_) => Kind::Fill,
+            Style::Outline(_) => Kind::Outline,
+        };
+
+        if state.kind != kind {
+            state.update(|state| state.kind = kind);
+        }
+
+        if state.section != self.section {
+            state.update(|state| state.section = self.section);
+        }
+    }
+
+}
+
+
+impl Colorable for Arc {
+    fn color(mut self, color: Color) -> Self {
+        self.style.set_color(color);
+        self
+    }
+}
+
+
+impl Gradientable for Arc {
+    fn gradient(mut self, gradient: FillGradient) -> Self {
+        self.style = Style::Gradient(gradient);
+        self
+    }
+}
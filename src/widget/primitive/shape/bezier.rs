@@ -0,0 +1,364 @@
+use {
+    Backend,
+    Color,
+    Colorable,
+    FillGradient,
+    Gradientable,
+    LineStyle,
+    Point,
+    Positionable,
+    Rect,
+    Sizeable,
+    Theme,
+    Widget,
+};
+use super::Style as Style;
+use super::FLATTENING_TOLERANCE;
+use super::polygon;
+use graph;
+use utils::{self, bounding_box_for_points, vec2_add, vec2_sub};
+use widget;
+
+
+/// A basic, non-interactive widget for drawing a quadratic or cubic bezier **Curve**.
+///
+/// When **Outline**d, only the curve itself is drawn. When **Fill**ed, the area between the curve
+/// and the straight line joining its end points is filled.
+#[derive(Copy, Clone, Debug)]
+pub struct Bezier {
+    /// The curve described by the **Bezier**.
+    pub curve: Curve,
+    /// Data necessary and common for all widget builder types.
+    pub common: widget::CommonBuilder,
+    /// Unique styling for the **Bezier**.
+    pub style: Style,
+    /// Whether or not the curve should be automatically centred to the widget position.
+    pub should_centre_points: bool,
+}
+
+/// Unique state for the **Bezier**.
+#[derive(Clone, Debug, PartialEq)]
+pub struct State {
+    kind: Kind,
+    /// The curve described by the **Bezier**, in absolute co-ordinates.
+    pub curve: Curve,
+    /// The flattened points of the `curve`, updated only when the `curve` changes.
+    pub points: Vec<Point>,
+    /// The triangulation of the area between the `curve` and its chord, updated only when the
+    /// `curve` changes.
+    pub triangles: Vec<[Point; 3]>,
+}
+
+/// Whether the **Bezier** is drawn as an **Outline** or **Fill**ed with a color.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Kind {
+    /// Only the curve itself is drawn.
+    Outline,
+    /// The area enclosed by the curve and its chord is filled with some color.
+    Fill,
+}
+
+/// A quadratic or cubic bezier curve.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Curve {
+    /// A curve with a single control point.
+    Quadratic {
+        /// The start of the curve.
+        start: Point,
+        /// The point toward which the curve is pulled.
+        control: Point,
+        /// The end of the curve.
+        end: Point,
+    },
+    /// A curve with two control points.
+    Cubic {
+        /// The start of the curve.
+        start: Point,
+        /// The control point describing the direction in which the curve leaves the `start`.
+        start_control: Point,
+        /// The control point describing the direction in which the curve arrives at the `end`.
+        end_control: Point,
+        /// The end of the curve.
+        end: Point,
+    },
+}
+
+/// Unique Kind for the widget.
+pub const KIND: widget::Kind = "Bezier";
+
+
+impl Bezier {
+
+    /// Build a **Bezier** with the given curve and style.
+    pub fn styled(curve: Curve, style: Style) -> Self {
+        Bezier {
+            curve: curve,
+            common: widget::CommonBuilder::new(),
+            style: style,
+            should_centre_points: false,
+        }
+    }
+
+    /// Build a **Bezier** with the default **Fill** style.
+    pub fn fill(curve: Curve) -> Self {
+        Bezier::styled(curve, Style::fill())
+    }
+
+    /// Build a **Bezier** **Fill**ed with the given color.
+    pub fn fill_with(curve: Curve, color: Color) -> Self {
+        Bezier::styled(curve, Style::fill_with(color))
+    }
+
+    /// Build a **Bezier** with the default **Outline** style.
+    pub fn outline(curve: Curve) -> Self {
+        Bezier::styled(curve, Style::outline())
+    }
+
+    /// Build a **Bezier** **Outline**d with the given line style.
+    pub fn outline_styled(curve: Curve, line_style: LineStyle) -> Self {
+        Bezier::styled(curve, Style::outline_styled(line_style))
+    }
+
+    /// Build a new **Bezier** whose bounding box is fit to the absolute co-ordinates of the
+    /// curve's points.
+    ///
+    /// If you would rather centre the curve to the middle of the bounding box, use
+    /// [**Bezier::centred_styled**](./struct.Bezier#method.centred_styled) instead.
+    pub fn abs_styled(curve: Curve, style: Style) -> Self {
+        let (xy, dim) = curve.bounding_rect().xy_dim();
+        Bezier::styled(curve, style).wh(dim).xy(xy)
+    }
+
+    /// Build a new **Bezier** and shift the location of the curve so that the centre of its
+    /// bounding rectangle lies at the position determined for the **Bezier** widget.
+    ///
+    /// This is useful if the curve simply describes a shape and you want to position it using
+    /// conrod's auto-layout and/or **Positionable** methods.
+    pub fn centred_styled(curve: Curve, style: Style) -> Self {
+        let dim = curve.bounding_rect().dim();
+        let mut bezier = Bezier::styled(curve, style).wh(dim);
+        bezier.should_centre_points = true;
+        bezier
+    }
+
+}
+
+
+impl Curve {
+
+    /// A quadratic bezier curve.
+    pub fn quadratic(start: Point, control: Point, end: Point) -> Self {
+        Curve::Quadratic { start: start, control: control, end: end }
+    }
+
+    /// A cubic bezier curve.
+    pub fn cubic(start: Point, start_control: Point, end_control: Point, end: Point) -> Self {
+        Curve::Cubic {
+            start: start,
+            start_control: start_control,
+            end_control: end_control,
+            end: end,
+        }
+    }
+
+    /// The start, control and end points of the curve in order.
+    pub fn control_points(&self) -> Vec<Point> {
+        match *self {
+            Curve::Quadratic { start, control, end } => vec![start, control, end],
+            Curve::Cubic { start, start_control, end_control, end } =>
+                vec![start, start_control, end_control, end],
+        }
+    }
+
+    /// The bounding **Rect** of the curve's control points, which always contains the curve.
+    pub fn bounding_rect(&self) -> Rect {
+        bounding_box_for_points(self.control_points().into_iter())
+    }
+
+    /// The same curve with every point shifted by the given amount.
+    pub fn shift(self, amount: Point) -> Self {
+        let shift = |p: Point| vec2_add(p, amount);
+        match self {
+            Curve::Quadratic { start, control, end } =>
+                Curve::quadratic(shift(start), shift(control), shift(end)),
+            Curve::Cubic { start, start_control, end_control, end } =>
+                Curve::cubic(shift(start), shift(start_control), shift(end_control), shift(end)),
+        }
+    }
+
+    /// The point along the curve at the given position `t` within the range `0.0...1.0`.
+    pub fn point_at(&self, t: f64) -> Point {
+        let lerp = |a: Point, b: Point| [a[0] + (b[0] - a[0]) * t, a[1] + (b[1] - a[1]) * t];
+        match *self {
+            Curve::Quadratic { start, control, end } =>
+                lerp(lerp(start, control), lerp(control, end)),
+            Curve::Cubic { start, start_control, end_control, end } => {
+                let (a, b, c) = (lerp(start, start_control),
+                                 lerp(start_control, end_control),
+                                 lerp(end_control, end));
+                lerp(lerp(a, b), lerp(b, c))
+            },
+        }
+    }
+
+    /// Approximate the curve with a series of line segments, beginning at the start of the
+    /// curve and finishing at its end.
+    ///
+    /// The curve is recursively subdivided until each piece lies within `FLATTENING_TOLERANCE` of
+    /// a straight line, so the number of points depends upon the curve's size. The tolerance is
+    /// not adjusted for the widget's **Transform** (see `FLATTENING_TOLERANCE`).
+    pub fn flatten(&self) -> Vec<Point> {
+        // Limits the subdivision of degenerate curves.
+        const MAX_DEPTH: u32 = 16;
+
+        let control_points = self.control_points();
+        let mut points = vec![control_points[0]];
+        flatten_recursive(&control_points, MAX_DEPTH, &mut points);
+        points
+    }
+
+}
+
+
+/// Subdivide the bezier curve described by the given control points until flat, pushing all but
+/// the first point of each flat piece onto `points`.
+fn flatten_recursive(control_points: &[Point], depth: u32, points: &mut Vec<Point>) {
+    let n = control_points.len();
+    let (start, end) = (control_points[0], control_points[n - 1]);
+    if depth == 0 || is_flat(control_points) {
+        points.push(end);
+        return;
+    }
+    // Split the curve in half using de Casteljau's algorithm.
+    let mut left = Vec::with_capacity(n);
+    let mut right = Vec::with_capacity(n);
+    let mut level = control_points.to_vec();
+    left.push(start);
+    right.push(end);
+    while level.len() > 1 {
+        level = level.windows(2)
+            .map(|w| [(w[0][0] + w[1][0]) / 2.0, (w[0][1] + w[1][1]) / 2.0])
+            .collect();
+        left.push(level[0]);
+        right.push(level[level.len() - 1]);
+    }
+    right.reverse();
+    flatten_recursive(&left, depth - 1, points);
+    flatten_recursive(&right, depth - 1, points);
+}
+
+/// Whether or not all control points lie within `FLATTENING_TOLERANCE` of the chord.
+fn is_flat(control_points: &[Point]) -> bool {
+    let (start, end) = (control_points[0], control_points[control_points.len() - 1]);
+    let chord = vec2_sub(end, start);
+    let len = chord[0].hypot(chord[1]);
+    control_points[1..control_points.len() - 1].iter().all(|&p| {
+        let d = vec2_sub(p, start);
+        let distance = if len > 0.0 {
+            (d[0] * chord[1] - d[1] * chord[0]).abs() / len
+        } else {
+            d[0].hypot(d[1])
+        };
+        distance <= FLATTENING_TOLERANCE
+    })
+}
+
+
+/// Whether or not the given point lies over the **Bezier** stored within the given **Container**.
+///
+/// When **Outline**d, only points within half the line's thickness of the curve are over the
+/// **Bezier**. When **Fill**ed, only points within the filled area are over the **Bezier**.
+pub fn is_over_widget(container: &graph::Container, xy: Point, theme: &Theme) -> bool {
+    container.unique_widget_state::<Bezier>()
+        .map(|bezier| match bezier.style {
+            Style::Fill(_) | Style::Gradient(_) => bezier.state.triangles.iter()
+                .any(|&t| utils::is_over_triangle(t, xy)),
+            Style::Outline(line_style) => {
+                let half_thickness = line_style.get_thickness(theme) / 2.0;
+                bezier.state.points.windows(2)
+                    .any(|w| utils::distance_to_segment(w[0], w[1], xy) <= half_thickness)
+            },
+        })
+        .unwrap_or_else(|| widget::is_over_rect(container, xy, theme))
+}
+
+
+impl Widget for Bezier {
+    type State = State;
+    type Style = Style;
+
+    fn common(&self) -> &widget::CommonBuilder {
+        &self.common
+    }
+
+    fn common_mut(&mut self) -> &mut widget::CommonBuilder {
+        &mut self.common
+    }
+
+    fn unique_kind(&self) -> &'static str {
+        KIND
+    }
+
+    fn init_state(&self) -> State {
+        State {
+            kind: Kind::Outline,
+            curve: Curve::quadratic([0.0, 0.0], [0.0, 0.0], [0.0, 0.0]),
+            points: Vec::new(),
+            triangles: Vec::new(),
+        }
+    }
+
+    fn style(&self) -> Style {
+        self.style.clone()
+    }
+
+    fn is_over(&self) -> widget::IsOverFn {
+        is_over_widget
+    }
+
+    /// Update the state of the Bezier.
+    fn update<B: Backend>(self, args: widget::UpdateArgs<Self, B>) {
+        let widget::UpdateArgs { rect, state, style, .. } = args;
+        let Bezier { mut curve, should_centre_points, .. } = self;
+
+        // Check whether or not we need to shift the curve to the xy position.
+        if should_centre_points {
+            let original = curve.bounding_rect().xy();
+            curve = curve.shift(vec2_sub(rect.xy(), original));
+        }
+
+        if state.curve != curve {
+            state.update(|state| {
+                state.curve = curve;
+                state.points = curve.flatten();
+                state.triangles = polygon::triangulate(&state.points);
+            });
+        }
+
+        let kind = match *style {
+            Style::Fill(_) | Style::Gradient(_) => Kind::Fill,
+            Style::Outline(_) => Kind::Outline,
+        };
+
+        if state.kind != kind {
+            state.update(|state| state.kind = kind);
+        }
+    }
+
+}
+
+
+impl Colorable for Bezier {
+    fn color(mut self, color: Color) -> Self {
+        self.style.set_color(color);
+        self
+    }
+}
+
+
+impl Gradientable for Bezier {
+    fn gradient(mut self, gradient: FillGradient) -> Self {
+        self.style = Style::Gradient(gradient);
+        self
+    }
+}
@@ -3,7 +3,9 @@ use {
     Color,
     Colorable,
     Dimensions,
+    FillGradient,
     Frameable,
+    Gradientable,
     Scalar,
     Sizeable,
     Widget,
@@ -33,6 +35,10 @@ widget_style!{
         - frame: Scalar { theme.frame_width }
         /// The color of the frame.
         - frame_color: Color { theme.frame_color }
+        /// The radius of each of the outer corners of the frame.
+        - corner_radius: Scalar { 0.0 }
+        /// A gradient with which the inner rectangle is filled instead of its color.
+        - maybe_gradient: Option<FillGradient> { None }
     }
 }
 
@@ -46,7 +52,11 @@ impl FramedRectangle {
         }.wh(dim)
     }
 
-    builder_method!(pub with_style { style = Style });
+    builder_methods!{
+        pub with_style { style = Style }
+        pub corner_radius { style.corner_radius = Some(Scalar) }
+        pub maybe_gradient { style.maybe_gradient = Some(Option<FillGradient>) }
+    }
 
 }
 
@@ -88,6 +98,14 @@ impl Colorable for FramedRectangle {
 }
 
 
+impl Gradientable for FramedRectangle {
+    fn gradient(mut self, gradient: FillGradient) -> Self {
+        self.style.maybe_gradient = Some(Some(gradient));
+        self
+    }
+}
+
+
 impl Frameable for FramedRectangle {
     builder_methods!{
         frame { style.frame = Some(Scalar) }
@@ -1,16 +1,33 @@
 //! A module encompassing the primitive 2D shape widgets.
 
-use color::Color;
+use Scalar;
+use color::{Color, FillGradient};
 use super::line::Style as LineStyle;
 use theme::Theme;
 
+pub mod arc;
+pub mod bezier;
 pub mod circle;
 pub mod oval;
 pub mod polygon;
 pub mod rectangle;
+pub mod rounded_rectangle;
 pub mod framed_rectangle;
 
 
+/// The maximum distance between a curved shape and the line segments used to approximate it.
+///
+/// As this is measured in the same units as the shape's dimensions, larger shapes are described
+/// using more line segments.
+///
+/// **Arc**s are flattened as they are drawn, at which point the tolerance is divided by the scale
+/// of the widget's **Transform**. **Bezier** curves are flattened once when set and cached within
+/// their state, before their transform is known, so enlarging a **Bezier** with a **Transform**
+/// also enlarges the error of its approximation. This is deliberate, as re-flattening each curve
+/// every frame would defeat the cache.
+pub const FLATTENING_TOLERANCE: Scalar = 0.25;
+
+
 /// The style for some 2D shape.
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum Style {
@@ -18,6 +35,8 @@ pub enum Style {
     Outline(LineStyle),
     /// A rectangle filled with this color.
     Fill(Option<Color>),
+    /// The area of the shape filled with this gradient.
+    Gradient(FillGradient),
 }
 
 
@@ -33,6 +52,11 @@ impl Style {
         Style::Fill(Some(color))
     }
 
+    /// A `Gradient` style with the given gradient.
+    pub fn gradient(gradient: FillGradient) -> Self {
+        Style::Gradient(gradient)
+    }
+
     /// A default `Outline` style.
     pub fn outline() -> Self {
         Style::Outline(LineStyle::new())
@@ -50,18 +74,24 @@ impl Style {
     }
 
     /// Set the color for the style.
+    ///
+    /// If the style is a `Gradient`, it is replaced by a `Fill` with the given color.
     pub fn set_color(&mut self, color: Color) {
         match *self {
             Style::Fill(ref mut maybe_color) => *maybe_color = Some(color),
             Style::Outline(ref mut line_style) => line_style.set_color(color),
+            Style::Gradient(_) => *self = Style::Fill(Some(color)),
         }
     }
 
     /// Get the color of the Rectangle.
+    ///
+    /// For a `Gradient`, this is the color at the start of the gradient.
     pub fn get_color(&self, theme: &Theme) -> Color {
         match *self {
             Style::Fill(maybe_color) => maybe_color.unwrap_or(theme.shape_color),
             Style::Outline(style) => style.get_color(theme),
+            Style::Gradient(gradient) => gradient.color_at(0.0),
         }
     }
 
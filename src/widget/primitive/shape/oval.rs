@@ -2,12 +2,20 @@ use {
     Backend,
     Color,
     Colorable,
+    FillGradient,
+    Gradientable,
     Dimensions,
     LineStyle,
+    Point,
+    Rect,
+    Scalar,
     Sizeable,
+    Theme,
     Widget,
 };
 use super::Style as Style;
+use graph;
+use utils;
 use widget;
 
 
@@ -39,6 +47,9 @@ pub enum Kind {
 /// Unique Kind for the Widget.
 pub const KIND: widget::Kind = "Oval";
 
+/// The number of line segments used to approximate the circumference of an **Oval**.
+pub const CIRCLE_RESOLUTION: usize = 50;
+
 
 impl Oval {
 
@@ -73,6 +84,44 @@ impl Oval {
 }
 
 
+/// The points approximating the circumference of the ellipse bounded by the given **Rect**, with
+/// the first point repeated at the end so that the outline is closed.
+pub fn circumference(rect: Rect) -> Vec<Point> {
+    use std::f64::consts::PI;
+    let (x, y, w, h) = rect.x_y_w_h();
+    let t = 2.0 * PI / CIRCLE_RESOLUTION as Scalar;
+    let (hw, hh) = (w / 2.0, h / 2.0);
+    (0..CIRCLE_RESOLUTION + 1)
+        .map(|i| [x + hw * (t * i as Scalar).cos(), y + hh * (t * i as Scalar).sin()])
+        .collect()
+}
+
+
+/// Whether or not the given point lies over the **Oval** stored within the given **Container**.
+///
+/// When **Outline**d, only points within half the line's thickness of the circumference are over
+/// the **Oval**. Otherwise, only points within the ellipse bounded by the **Oval**'s **Rect** are
+/// considered to be over it.
+pub fn is_over_widget(container: &graph::Container, xy: Point, theme: &Theme) -> bool {
+    let (x, y, w, h) = container.rect.x_y_w_h();
+    let (radius_x, radius_y) = (w / 2.0, h / 2.0);
+    if radius_x <= 0.0 || radius_y <= 0.0 {
+        return false;
+    }
+    match container.unique_widget_state::<Oval>().map(|oval| oval.style) {
+        Some(Style::Outline(line_style)) => {
+            let half_thickness = line_style.get_thickness(theme) / 2.0;
+            circumference(container.rect).windows(2)
+                .any(|w| utils::distance_to_segment(w[0], w[1], xy) <= half_thickness)
+        },
+        _ => {
+            let (dx, dy) = ((xy[0] - x) / radius_x, (xy[1] - y) / radius_y);
+            dx * dx + dy * dy <= 1.0
+        },
+    }
+}
+
+
 impl Widget for Oval {
     type State = State;
     type Style = Style;
@@ -99,12 +148,16 @@ impl Widget for Oval {
         self.style.clone()
     }
 
+    fn is_over(&self) -> widget::IsOverFn {
+        is_over_widget
+    }
+
     /// Update the state of the Oval.
     fn update<B: Backend>(self, args: widget::UpdateArgs<Self, B>) {
         let widget::UpdateArgs { state, style, .. } = args;
 
         let kind = match *style {
-            Style::Fill(_) => Kind::Fill,
+            Style::Fill(_) | Style::Gradient(_) => Kind::Fill,
             Style::Outline(_) => Kind::Outline,
         };
 
@@ -122,3 +175,11 @@ impl Colorable for Oval {
         self
     }
 }
+
+
+impl Gradientable for Oval {
+    fn gradient(mut self, gradient: FillGradient) -> Self {
+        self.style = Style::Gradient(gradient);
+        self
+    }
+}
@@ -3,15 +3,20 @@ use {
     Backend,
     Color,
     Colorable,
+    FillGradient,
+    Gradientable,
     LineStyle,
     Point,
     Positionable,
+    Scalar,
     Sizeable,
+    Theme,
     Widget,
 };
 use super::Style;
+use graph;
 use widget;
-use utils::{bounding_box_for_points, vec2_add, vec2_sub};
+use utils::{bounding_box_for_points, distance_to_segment, is_over_triangle, vec2_add, vec2_sub};
 
 
 /// A basic, non-interactive, arbitarry **Polygon** widget.
@@ -39,6 +44,10 @@ pub struct State {
     kind: Kind,
     /// An owned version of the points yielded by the **Polygon**'s `points` iterator.
     pub points: Vec<Point>,
+    /// The triangulation of the **Polygon**'s area, updated only when the `points` change.
+    ///
+    /// See [**triangulate**](./fn.triangulate.html).
+    pub triangles: Vec<[Point; 3]>,
 }
 
 /// Whether the rectangle is drawn as an outline or a filled color.
@@ -187,6 +196,28 @@ impl<I> Polygon<I> {
 }
 
 
+/// Whether or not the given point lies over the **Polygon** stored within the given **Container**.
+///
+/// When **Outline**d, only points within half the line's thickness of the closed outline are over
+/// the **Polygon**. Otherwise, only points within its triangulated area are considered to be over
+/// it.
+pub fn is_over_widget(container: &graph::Container, xy: Point, theme: &Theme) -> bool {
+    container.state_and_style::<State, Style>()
+        .map(|polygon| match polygon.style {
+            Style::Fill(_) | Style::Gradient(_) =>
+                polygon.state.triangles.iter().any(|&t| is_over_triangle(t, xy)),
+            Style::Outline(line_style) => {
+                let half_thickness = line_style.get_thickness(theme) / 2.0;
+                let points = &polygon.state.points;
+                let n = points.len();
+                (0..n).any(|i| distance_to_segment(points[i], points[(i + 1) % n], xy)
+                               <= half_thickness)
+            },
+        })
+        .unwrap_or_else(|| widget::is_over_rect(container, xy, theme))
+}
+
+
 impl<I> Widget for Polygon<I>
     where I: IntoIterator<Item=Point>,
 {
@@ -209,6 +240,7 @@ impl<I> Widget for Polygon<I>
         State {
             kind: Kind::Fill,
             points: Vec::new(),
+            triangles: Vec::new(),
         }
     }
 
@@ -216,6 +248,10 @@ impl<I> Widget for Polygon<I>
         self.style.clone()
     }
 
+    fn is_over(&self) -> widget::IsOverFn {
+        is_over_widget
+    }
+
     /// Update the state of the Polygon.
     fn update<B: Backend>(self, args: widget::UpdateArgs<Self, B>) {
         use utils::{iter_diff, IterDiff};
@@ -223,20 +259,30 @@ impl<I> Widget for Polygon<I>
         let Polygon { points, maybe_shift_to_centre_from, .. } = self;
 
         // A function that compares the given points iterator to the points currently owned by
-        // `State` and updates only if necessary.
+        // `State` and updates only if necessary, re-triangulating the points if they change.
         fn update_points<I>(state: &mut widget::State<State>, points: I)
             where I: IntoIterator<Item=Point>,
         {
-            match iter_diff(&state.points, points) {
-                Some(IterDiff::FirstMismatch(i, mismatch)) => state.update(|state| {
-                    state.points.truncate(i);
-                    state.points.extend(mismatch);
-                }),
-                Some(IterDiff::Longer(remaining)) =>
-                    state.update(|state| state.points.extend(remaining)),
-                Some(IterDiff::Shorter(total)) =>
-                    state.update(|state| state.points.truncate(total)),
-                None => (),
+            let has_changed = match iter_diff(&state.points, points) {
+                Some(IterDiff::FirstMismatch(i, mismatch)) => {
+                    state.update(|state| {
+                        state.points.truncate(i);
+                        state.points.extend(mismatch);
+                    });
+                    true
+                },
+                Some(IterDiff::Longer(remaining)) => {
+                    state.update(|state| state.points.extend(remaining));
+                    true
+                },
+                Some(IterDiff::Shorter(total)) => {
+                    state.update(|state| state.points.truncate(total));
+                    true
+                },
+                None => false,
+            };
+            if has_changed {
+                state.update(|state| state.triangles = triangulate(&state.points));
             }
         }
 
@@ -251,7 +297,7 @@ impl<I> Widget for Polygon<I>
         }
 
         let kind = match *style {
-            Style::Fill(_) => Kind::Fill,
+            Style::Fill(_) | Style::Gradient(_) => Kind::Fill,
             Style::Outline(_) => Kind::Outline,
         };
 
@@ -263,9 +309,178 @@ impl<I> Widget for Polygon<I>
 }
 
 
+/// Triangulate the area of the polygon described by the given points.
+///
+/// Simple polygons, whether convex or concave, are triangulated by "ear clipping". The area of a
+/// polygon whose edges cross one another is determined using the even-odd rule.
+pub fn triangulate(points: &[Point]) -> Vec<[Point; 3]> {
+    // Remove consecutive duplicates, including a closing point equal to the first.
+    let mut points: Vec<Point> = points.to_vec();
+    points.dedup();
+    while points.len() > 1 && points[0] == points[points.len() - 1] {
+        points.pop();
+    }
+    if points.len() < 3 {
+        return Vec::new();
+    }
+    if is_simple(&points) {
+        ear_clip(&points)
+    } else {
+        even_odd_slabs(&points)
+    }
+}
+
+/// Twice the signed area of the triangle `abc`, positive if anti-clockwise.
+fn cross(a: Point, b: Point, c: Point) -> Scalar {
+    (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])
+}
+
+/// The position along `a0 -> a1` at which it intersects `b0 -> b1`, if they intersect.
+fn intersection(a0: Point, a1: Point, b0: Point, b1: Point) -> Option<Scalar> {
+    let d = (a1[0] - a0[0]) * (b1[1] - b0[1]) - (a1[1] - a0[1]) * (b1[0] - b0[0]);
+    if d == 0.0 {
+        return None;
+    }
+    let t = ((b0[0] - a0[0]) * (b1[1] - b0[1]) - (b0[1] - a0[1]) * (b1[0] - b0[0])) / d;
+    let u = ((b0[0] - a0[0]) * (a1[1] - a0[1]) - (b0[1] - a0[1]) * (a1[0] - a0[0])) / d;
+    if t >= 0.0 && t <= 1.0 && u >= 0.0 && u <= 1.0 { Some(t) } else { None }
+}
+
+/// Whether or not no two non-adjacent edges of the closed polygon touch.
+fn is_simple(points: &[Point]) -> bool {
+    let n = points.len();
+    for i in 0..n {
+        for j in i + 2..n {
+            // The last edge is adjacent to the first.
+            if i == 0 && j == n - 1 {
+                continue;
+            }
+            let (a0, a1) = (points[i], points[(i + 1) % n]);
+            let (b0, b1) = (points[j], points[(j + 1) % n]);
+            if intersection(a0, a1, b0, b1).is_some() {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+/// Triangulate a simple polygon by repeatedly removing "ears": vertices whose triangle with their
+/// neighbours lies within the polygon.
+fn ear_clip(points: &[Point]) -> Vec<[Point; 3]> {
+    let mut indices: Vec<usize> = (0..points.len()).collect();
+    // Ensure the vertices wind anti-clockwise.
+    let area: Scalar = (0..points.len())
+        .map(|i| cross([0.0, 0.0], points[i], points[(i + 1) % points.len()]))
+        .sum();
+    if area < 0.0 {
+        indices.reverse();
+    }
+
+    let mut triangles = Vec::with_capacity(points.len() - 2);
+    while indices.len() > 3 {
+        let n = indices.len();
+        let is_ear = |i: usize| {
+            let (a, b, c) = (points[indices[(i + n - 1) % n]],
+                             points[indices[i]],
+                             points[indices[(i + 1) % n]]);
+            if cross(a, b, c) <= 0.0 {
+                return false;
+            }
+            // No other vertex may lie within the triangle.
+            indices.iter().map(|&idx| points[idx]).all(|p| {
+                p == a || p == b || p == c
+                    || cross(a, b, p) < 0.0 || cross(b, c, p) < 0.0 || cross(c, a, p) < 0.0
+            })
+        };
+        // If no ear can be found (e.g. due to vertices lying exactly upon the edges of every
+        // candidate), the remaining area is filled using the even-odd rule instead.
+        let i = match (0..n).find(|&i| is_ear(i)) {
+            Some(i) => i,
+            None => {
+                let remaining: Vec<Point> = indices.iter().map(|&idx| points[idx]).collect();
+                triangles.extend(even_odd_slabs(&remaining));
+                return triangles;
+            },
+        };
+        triangles.push([points[indices[(i + n - 1) % n]],
+                        points[indices[i]],
+                        points[indices[(i + 1) % n]]]);
+        indices.remove(i);
+    }
+    let (a, b, c) = (points[indices[0]], points[indices[1]], points[indices[2]]);
+    if cross(a, b, c) != 0.0 {
+        triangles.push([a, b, c]);
+    }
+    triangles
+}
+
+/// Triangulate a polygon using the even-odd rule by dividing it into horizontal slabs, bounded by
+/// every vertex and edge intersection, within which no edges cross.
+fn even_odd_slabs(points: &[Point]) -> Vec<[Point; 3]> {
+    let n = points.len();
+    let edges: Vec<(Point, Point)> = (0..n)
+        .map(|i| (points[i], points[(i + 1) % n]))
+        .filter(|&(a, b)| a[1] != b[1])
+        .collect();
+
+    let mut ys: Vec<Scalar> = points.iter().map(|p| p[1]).collect();
+    for i in 0..edges.len() {
+        for j in i + 1..edges.len() {
+            let ((a0, a1), (b0, b1)) = (edges[i], edges[j]);
+            if let Some(t) = intersection(a0, a1, b0, b1) {
+                ys.push(a0[1] + (a1[1] - a0[1]) * t);
+            }
+        }
+    }
+    ys.sort_by(|a, b| a.partial_cmp(b).unwrap_or(::std::cmp::Ordering::Equal));
+    ys.dedup();
+
+    let x_at = |(a, b): (Point, Point), y: Scalar| {
+        a[0] + (y - a[1]) / (b[1] - a[1]) * (b[0] - a[0])
+    };
+    let mut triangles = Vec::new();
+    let mut crossings = Vec::new();
+    for w in ys.windows(2) {
+        let (y0, y1) = (w[0], w[1]);
+        let mid = (y0 + y1) / 2.0;
+        crossings.clear();
+        for &edge in &edges {
+            let (a, b) = edge;
+            if (a[1] < mid) != (b[1] < mid) {
+                crossings.push((x_at(edge, mid), x_at(edge, y0), x_at(edge, y1)));
+            }
+        }
+        crossings.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(::std::cmp::Ordering::Equal));
+        for pair in crossings.chunks(2) {
+            if pair.len() < 2 {
+                break;
+            }
+            let (l, r) = (pair[0], pair[1]);
+            let (l0, r0, r1, l1) = ([l.1, y0], [r.1, y0], [r.2, y1], [l.2, y1]);
+            if l0 != r0 {
+                triangles.push([l0, r0, r1]);
+            }
+            if l1 != r1 {
+                triangles.push([l0, r1, l1]);
+            }
+        }
+    }
+    triangles
+}
+
+
 impl<I> Colorable for Polygon<I> {
     fn color(mut self, color: Color) -> Self {
         self.style.set_color(color);
         self
     }
 }
+
+
+impl<I> Gradientable for Polygon<I> {
+    fn gradient(mut self, gradient: FillGradient) -> Self {
+        self.style = Style::Gradient(gradient);
+        self
+    }
+}
@@ -2,6 +2,8 @@ use {
     Backend,
     Color,
     Colorable,
+    FillGradient,
+    Gradientable,
     Dimensions,
     LineStyle,
     Sizeable,
@@ -103,7 +105,7 @@ impl Widget for Rectangle {
         let widget::UpdateArgs { state, style, .. } = args;
 
         let kind = match *style {
-            Style::Fill(_) => Kind::Fill,
+            Style::Fill(_) | Style::Gradient(_) => Kind::Fill,
             Style::Outline(_) => Kind::Outline,
         };
 
@@ -121,3 +123,11 @@ impl Colorable for Rectangle {
         self
     }
 }
+
+
+impl Gradientable for Rectangle {
+    fn gradient(mut self, gradient: FillGradient) -> Self {
+        self.style = Style::Gradient(gradient);
+        self
+    }
+}
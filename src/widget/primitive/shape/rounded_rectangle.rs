@@ -0,0 +1,283 @@
+use {
+    Backend,
+    Color,
+    Colorable,
+    FillGradient,
+    Gradientable,
+    Dimensions,
+    LineStyle,
+    Point,
+    Rect,
+    Scalar,
+    Sizeable,
+    Theme,
+    Widget,
+};
+use super::Style as Style;
+use graph;
+use utils::{distance_to_segment, is_over_triangle};
+use widget;
+
+
+/// A basic, non-interactive rectangle shape widget whose corners are rounded.
+#[derive(Copy, Clone, Debug)]
+pub struct RoundedRectangle {
+    /// Data necessary and common for all widget builder types.
+    pub common: widget::CommonBuilder,
+    /// Unique styling for the **RoundedRectangle**.
+    pub style: Style,
+    /// The radius of each corner.
+    pub radii: Radii,
+}
+
+/// Unique state for the **RoundedRectangle**.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct State {
+    kind: Kind,
+    /// The radius of each corner.
+    pub radii: Radii,
+}
+
+/// Whether the rounded rectangle is drawn as an outline or a filled color.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Kind {
+    /// Only the outline of the rounded rectangle is drawn.
+    Outline,
+    /// The rounded rectangle area is filled with some color.
+    Fill,
+}
+
+/// The radius of each corner of a **RoundedRectangle**.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Radii {
+    /// The radius of the top left corner.
+    pub top_left: Scalar,
+    /// The radius of the top right corner.
+    pub top_right: Scalar,
+    /// The radius of the bottom right corner.
+    pub bottom_right: Scalar,
+    /// The radius of the bottom left corner.
+    pub bottom_left: Scalar,
+}
+
+/// Unique Kind for the widget.
+pub const KIND: widget::Kind = "RoundedRectangle";
+
+/// The number of line segments used to describe each corner.
+pub const CORNER_RESOLUTION: usize = 8;
+
+
+impl RoundedRectangle {
+
+    /// Build a rounded rectangle with the dimensions, corner radius and style.
+    pub fn styled(dim: Dimensions, radius: Scalar, style: Style) -> Self {
+        RoundedRectangle {
+            common: widget::CommonBuilder::new(),
+            style: style,
+            radii: Radii::all(radius),
+        }.wh(dim)
+    }
+
+    /// Build a new filled rounded rectangle.
+    pub fn fill(dim: Dimensions, radius: Scalar) -> Self {
+        RoundedRectangle::styled(dim, radius, Style::fill())
+    }
+
+    /// Build a new filled rounded rectangle widget filled with the given color.
+    pub fn fill_with(dim: Dimensions, radius: Scalar, color: Color) -> Self {
+        RoundedRectangle::styled(dim, radius, Style::fill_with(color))
+    }
+
+    /// Build a new outlined rounded rectangle widget.
+    pub fn outline(dim: Dimensions, radius: Scalar) -> Self {
+        RoundedRectangle::styled(dim, radius, Style::outline())
+    }
+
+    /// Build an outlined rounded rectangle rather than a filled one.
+    pub fn outline_styled(dim: Dimensions, radius: Scalar, line_style: LineStyle) -> Self {
+        RoundedRectangle::styled(dim, radius, Style::outline_styled(line_style))
+    }
+
+    /// Specify the radius of each corner individually.
+    pub fn radii(mut self, radii: Radii) -> Self {
+        self.radii = radii;
+        self
+    }
+
+}
+
+
+impl Radii {
+
+    /// The same radius for all four corners.
+    pub fn all(radius: Scalar) -> Self {
+        Radii {
+            top_left: radius,
+            top_right: radius,
+            bottom_right: radius,
+            bottom_left: radius,
+        }
+    }
+
+    /// Shrink the radii so that they fit within a rectangle of the given dimensions.
+    ///
+    /// Negative radii are treated as zero. Where the radii of two adjacent corners sum to more
+    /// than the length of the side they share, all radii are scaled down proportionally.
+    pub fn fit(self, dim: Dimensions) -> Self {
+        let Radii { top_left, top_right, bottom_right, bottom_left } = self;
+        let (tl, tr) = (top_left.max(0.0), top_right.max(0.0));
+        let (br, bl) = (bottom_right.max(0.0), bottom_left.max(0.0));
+        let (w, h) = (dim[0].max(0.0), dim[1].max(0.0));
+        let ratio = |len: Scalar, a: Scalar, b: Scalar| {
+            if a + b > len { len / (a + b) } else { 1.0 }
+        };
+        let scale = ratio(w, tl, tr)
+            .min(ratio(w, bl, br))
+            .min(ratio(h, tl, bl))
+            .min(ratio(h, tr, br));
+        Radii {
+            top_left: tl * scale,
+            top_right: tr * scale,
+            bottom_right: br * scale,
+            bottom_left: bl * scale,
+        }
+    }
+
+    /// Reduce each radius by the given amount, e.g. to find the radii of a rectangle padded by
+    /// some frame.
+    pub fn pad(self, pad: Scalar) -> Self {
+        Radii {
+            top_left: (self.top_left - pad).max(0.0),
+            top_right: (self.top_right - pad).max(0.0),
+            bottom_right: (self.bottom_right - pad).max(0.0),
+            bottom_left: (self.bottom_left - pad).max(0.0),
+        }
+    }
+
+}
+
+
+/// The vertices of the convex polygon describing a rectangle with the given rounded corners.
+///
+/// The vertices begin at the top of the right edge and travel anti-clockwise. The first vertex is
+/// not repeated at the end.
+pub fn points(rect: Rect, radii: Radii) -> Vec<Point> {
+    use std::f64::consts::PI;
+    let (l, r, b, t) = rect.l_r_b_t();
+    let radii = radii.fit(rect.dim());
+    // The centre of each corner's arc along with its radius and the angle at which it begins.
+    let corners = [
+        ([r - radii.top_right, t - radii.top_right], radii.top_right, 0.0),
+        ([l + radii.top_left, t - radii.top_left], radii.top_left, PI / 2.0),
+        ([l + radii.bottom_left, b + radii.bottom_left], radii.bottom_left, PI),
+        ([r - radii.bottom_right, b + radii.bottom_right], radii.bottom_right, PI * 1.5),
+    ];
+    let mut points = Vec::with_capacity(4 * (CORNER_RESOLUTION + 1));
+    for &(centre, radius, start) in corners.iter() {
+        if radius > 0.0 {
+            let step = PI / 2.0 / CORNER_RESOLUTION as Scalar;
+            for i in 0..CORNER_RESOLUTION + 1 {
+                let (sin, cos) = (start + step * i as Scalar).sin_cos();
+                points.push([centre[0] + radius * cos, centre[1] + radius * sin]);
+            }
+        } else {
+            points.push(centre);
+        }
+    }
+    points
+}
+
+
+/// Whether or not the given point lies over the **RoundedRectangle** stored within the given
+/// **Container**.
+///
+/// When **Outline**d, only points within half the line's thickness of the rounded outline are over
+/// the widget. Otherwise, points within the **Rect** but beyond the curve of a rounded corner are
+/// not over the widget.
+pub fn is_over_widget(container: &graph::Container, xy: Point, theme: &Theme) -> bool {
+    container.unique_widget_state::<RoundedRectangle>()
+        .map(|rounded_rectangle| {
+            let points = points(container.rect, rounded_rectangle.state.radii);
+            match rounded_rectangle.style {
+                Style::Fill(_) | Style::Gradient(_) =>
+                    // The points describe a convex polygon, so we may fan from the first.
+                    points.windows(2).skip(1)
+                        .any(|w| is_over_triangle([points[0], w[0], w[1]], xy)),
+                Style::Outline(line_style) => {
+                    let half_thickness = line_style.get_thickness(theme) / 2.0;
+                    let n = points.len();
+                    (0..n).any(|i| distance_to_segment(points[i], points[(i + 1) % n], xy)
+                                   <= half_thickness)
+                },
+            }
+        })
+        .unwrap_or_else(|| widget::is_over_rect(container, xy, theme))
+}
+
+
+impl Widget for RoundedRectangle {
+    type State = State;
+    type Style = Style;
+
+    fn common(&self) -> &widget::CommonBuilder {
+        &self.common
+    }
+
+    fn common_mut(&mut self) -> &mut widget::CommonBuilder {
+        &mut self.common
+    }
+
+    fn unique_kind(&self) -> &'static str {
+        KIND
+    }
+
+    fn init_state(&self) -> State {
+        State {
+            kind: Kind::Fill,
+            radii: Radii::all(0.0),
+        }
+    }
+
+    fn style(&self) -> Style {
+        self.style.clone()
+    }
+
+    fn is_over(&self) -> widget::IsOverFn {
+        is_over_widget
+    }
+
+    /// Update the state of the RoundedRectangle.
+    fn update<B: Backend>(self, args: widget::UpdateArgs<Self, B>) {
+        let widget::UpdateArgs { state, style, .. } = args;
+
+        let kind = match *style {
+            Style::Fill(_) | Style::Gradient(_) => Kind::Fill,
+            Style::Outline(_) => Kind::Outline,
+        };
+
+        if state.kind != kind {
+            state.update(|state| state.kind = kind);
+        }
+
+        if state.radii != self.radii {
+            state.update(|state| state.radii = self.radii);
+        }
+    }
+
+}
+
+
+impl Colorable for RoundedRectangle {
+    fn color(mut self, color: Color) -> Self {
+        self.style.set_color(color);
+        self
+    }
+}
+
+
+impl Gradientable for RoundedRectangle {
+    fn gradient(mut self, gradient: FillGradient) -> Self {
+        self.style = Style::Gradient(gradient);
+        self
+    }
+}
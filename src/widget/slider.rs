@@ -133,6 +133,10 @@ impl<'a, T, F> Widget for Slider<'a, T, F>
         self.style.clone()
     }
 
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
     fn kid_area<C: CharacterCache>(&self, args: widget::KidAreaArgs<Self, C>) -> KidArea {
         const LABEL_PADDING: Scalar = 10.0;
         KidArea {
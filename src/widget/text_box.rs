@@ -51,6 +51,8 @@ widget_style!{
         - frame: Scalar { theme.frame_width }
         /// The color of the `FramedRecangle`'s frame.
         - frame_color: Color { theme.frame_color }
+        /// The radius of the corners of the frame. Corners are square when zero.
+        - corner_radius: Scalar { 0.0 }
         /// The color of the `TextEdit` widget.
         - text_color: Color { theme.label_color }
         /// The font size for the text.
@@ -99,6 +101,7 @@ impl<'a, F> TextBox<'a, F> {
         pub react { maybe_react = Some(F) }
         pub x_align_text { style.x_align = Some(Align) }
         pub pad_text { style.text_padding = Some(Scalar) }
+        pub corner_radius { style.corner_radius = Some(Scalar) }
     }
 
 }
@@ -153,6 +156,7 @@ impl<'a, F> Widget for TextBox<'a, F>
         let rectangle_idx = state.rectangle_idx.get(&mut ui);
         let color = style.color(ui.theme());
         let frame_color = style.frame_color(ui.theme());
+        let corner_radius = style.corner_radius(ui.theme());
         FramedRectangle::new(rect.dim())
             .xy(rect.xy())
             .graphics_for(idx)
@@ -160,6 +164,7 @@ impl<'a, F> Widget for TextBox<'a, F>
             .frame(frame)
             .color(color)
             .frame_color(frame_color)
+            .corner_radius(corner_radius)
             .set(rectangle_idx, &mut ui);
 
         let text_edit_idx = state.text_edit_idx.get(&mut ui);
@@ -214,6 +214,10 @@ impl<'a, F> Widget for TextEdit<'a, F>
         self.style.clone()
     }
 
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
     /// Update the state of the TextEdit.
     fn update<B: Backend>(self, args: widget::UpdateArgs<Self, B>) {
         let widget::UpdateArgs { idx, state, rect, style, mut ui, .. } = args;
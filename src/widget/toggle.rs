@@ -46,6 +46,8 @@ widget_style!{
         - frame: Scalar { theme.frame_width }
         /// The color of the Toggle's frame.
         - frame_color: Color { theme.frame_color }
+        /// The radius of the corners of the frame. Corners are square when zero.
+        - corner_radius: Scalar { 0.0 }
         /// The color of the Toggle's Text label.
         - label_color: Color { theme.label_color }
         /// The font size for the Toggle's Text label.
@@ -79,6 +81,7 @@ impl<'a, F> Toggle<'a, F> {
     builder_methods!{
         pub react { maybe_react = Some(F) }
         pub enabled { enabled = bool }
+        pub corner_radius { style.corner_radius = Some(Scalar) }
     }
 
 }
@@ -113,6 +116,10 @@ impl<'a, F> Widget for Toggle<'a, F>
         self.style.clone()
     }
 
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
     /// Update the state of the Toggle.
     fn update<B: Backend>(self, args: widget::UpdateArgs<Self, B>) {
         let widget::UpdateArgs { idx, state, style, rect, mut ui, .. } = args;
@@ -148,12 +155,14 @@ impl<'a, F> Widget for Toggle<'a, F>
             }
         };
         let frame_color = style.frame_color(ui.theme());
+        let corner_radius = style.corner_radius(ui.theme());
         FramedRectangle::new(dim)
             .middle_of(idx)
             .graphics_for(idx)
             .color(color)
             .frame(frame)
             .frame_color(frame_color)
+            .corner_radius(corner_radius)
             .set(rectangle_idx, &mut ui);
 
         // Label widget.
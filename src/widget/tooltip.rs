@@ -0,0 +1,227 @@
+use {
+    Backend,
+    Color,
+    Colorable,
+    Dimension,
+    FontSize,
+    Frameable,
+    FramedRectangle,
+    IndexSlot,
+    Point,
+    Positionable,
+    Scalar,
+    Text,
+    Theme,
+    Ui,
+};
+use graph;
+use widget::{self, Widget};
+
+
+/// A small floating popup displaying some text once the mouse has rested over some target widget
+/// for the `Theme`'s `tooltip_delay`. Moving the mouse restarts the delay.
+///
+/// The **Tooltip** itself is invisible and should be set alongside its target widget on every
+/// update. Once shown, the popup appears near the mouse cursor, clamped to the window, and is drawn
+/// above all other widgets.
+pub struct Tooltip<'a> {
+    /// Data necessary and common for all widget builder types.
+    pub common: widget::CommonBuilder,
+    /// Unique styling for the **Tooltip**.
+    pub style: Style,
+    /// The widget over which the mouse must rest for the **Tooltip** to be shown.
+    pub target: widget::Index,
+    /// The text displayed by the **Tooltip**.
+    pub text: &'a str,
+}
+
+/// Unique state for the **Tooltip** widget.
+#[derive(Clone, Debug, PartialEq)]
+pub struct State {
+    rectangle_idx: IndexSlot,
+    text_idx: IndexSlot,
+    /// The centre of the popup if it is currently shown.
+    maybe_xy: Option<Point>,
+}
+
+widget_style!{
+    KIND;
+    /// Unique styling for the **Tooltip** widget.
+    style Style {
+        /// The color of the popup's rectangle surface.
+        - color: Color { theme.shape_color }
+        /// The width of the frame surrounding the popup.
+        - frame: Scalar { theme.frame_width }
+        /// The color of the popup's frame.
+        - frame_color: Color { theme.frame_color }
+        /// The color of the popup's text.
+        - text_color: Color { theme.label_color }
+        /// The font size of the popup's text.
+        - font_size: FontSize { theme.font_size_small }
+        /// The distance between the popup's frame and its text.
+        - padding: Scalar { 4.0 }
+    }
+}
+
+/// Unique kind for the widget type.
+pub const KIND: widget::Kind = "Tooltip";
+
+/// The offset of the top-left corner of the popup from the mouse cursor, leaving room for the
+/// cursor itself.
+const CURSOR_OFFSET: Point = [12.0, -20.0];
+
+
+impl<'a> Tooltip<'a> {
+
+    /// Construct a new **Tooltip** displaying the given text for the widget at the given index.
+    pub fn new<I>(target: I, text: &'a str) -> Self
+        where I: Into<widget::Index> + Copy,
+    {
+        Tooltip {
+            common: widget::CommonBuilder::new(),
+            style: Style::new(),
+            target: target.into(),
+            text: text,
+        }.middle_of(target)
+    }
+
+    builder_methods!{
+        pub text_color { style.text_color = Some(Color) }
+        pub font_size { style.font_size = Some(FontSize) }
+        pub padding { style.padding = Some(Scalar) }
+    }
+
+}
+
+
+/// The **Tooltip** itself is never considered to be under the mouse.
+fn is_never_over(_container: &graph::Container, _xy: Point, _theme: &Theme) -> bool {
+    false
+}
+
+
+impl<'a> Widget for Tooltip<'a> {
+    type State = State;
+    type Style = Style;
+
+    fn common(&self) -> &widget::CommonBuilder {
+        &self.common
+    }
+
+    fn common_mut(&mut self) -> &mut widget::CommonBuilder {
+        &mut self.common
+    }
+
+    fn unique_kind(&self) -> &'static str {
+        KIND
+    }
+
+    fn init_state(&self) -> State {
+        State {
+            rectangle_idx: IndexSlot::new(),
+            text_idx: IndexSlot::new(),
+            maybe_xy: None,
+        }
+    }
+
+    fn style(&self) -> Style {
+        self.style.clone()
+    }
+
+    fn default_x_dimension<B: Backend>(&self, _ui: &Ui<B>) -> Dimension {
+        Dimension::Absolute(0.0)
+    }
+
+    fn default_y_dimension<B: Backend>(&self, _ui: &Ui<B>) -> Dimension {
+        Dimension::Absolute(0.0)
+    }
+
+    fn is_over(&self) -> widget::IsOverFn {
+        is_never_over
+    }
+
+    /// Update the state of the Tooltip.
+    fn update<B: Backend>(self, args: widget::UpdateArgs<Self, B>) {
+        let widget::UpdateArgs { state, style, mut ui, .. } = args;
+        let Tooltip { target, text, .. } = self;
+
+        let frame = style.frame(ui.theme());
+        let padding = style.padding(ui.theme());
+        let font_size = style.font_size(ui.theme());
+        let w = ui.glyph_cache().width(font_size, text) + (padding + frame) * 2.0;
+        let h = font_size as Scalar + (padding + frame) * 2.0;
+
+        // The popup is shown once the mouse has rested over the target for the theme's delay,
+        // remains shown while the target is hovered, and is hidden while any mouse button is
+        // pressed.
+        let delay = ui.theme().tooltip_delay;
+        let last_mouse_move = ui.global_input().last_mouse_move;
+        let is_shown = ui.widget_input(target).hover_start()
+            .map(|start| last_mouse_move.map_or(start, |moved| ::std::cmp::max(start, moved)))
+            .map(|rest_start| state.maybe_xy.is_some() || rest_start.elapsed() >= delay)
+            .unwrap_or(false)
+            && ui.global_input().current.mouse.buttons.pressed().next().is_none();
+
+        // Once shown, the popup remains where it first appeared.
+        let maybe_xy = match (is_shown, state.maybe_xy) {
+            (false, _) => None,
+            (true, Some(xy)) => Some(xy),
+            (true, None) => {
+                let mouse_xy = ui.global_input().current.mouse.xy;
+                let (half_win_w, half_win_h) = (ui.win_w / 2.0, ui.win_h / 2.0);
+                let left = (mouse_xy[0] + CURSOR_OFFSET[0]).min(half_win_w - w).max(-half_win_w);
+                let top = (mouse_xy[1] + CURSOR_OFFSET[1]).max(h - half_win_h).min(half_win_h);
+                Some([left + w / 2.0, top - h / 2.0])
+            },
+        };
+        if state.maybe_xy != maybe_xy {
+            state.update(|state| state.maybe_xy = maybe_xy);
+        }
+
+        let xy = match maybe_xy {
+            Some(xy) => xy,
+            None => return,
+        };
+
+        // The popup's surface. It is a graphic of the target so that hovering the popup itself
+        // does not steal the hover from the target.
+        let rectangle_idx = state.rectangle_idx.get(&mut ui);
+        let color = style.color(ui.theme());
+        let frame_color = style.frame_color(ui.theme());
+        let window = ui.window;
+        FramedRectangle::new([w, h])
+            .color(color)
+            .frame(frame)
+            .frame_color(frame_color)
+            .xy(xy)
+            .parent(window)
+            .floating(true)
+            .overlay(true)
+            .graphics_for(target)
+            .set(rectangle_idx, &mut ui);
+
+        // The popup's text.
+        let text_idx = state.text_idx.get(&mut ui);
+        let text_color = style.text_color(ui.theme());
+        Text::new(text)
+            .no_line_wrap()
+            .middle_of(rectangle_idx)
+            .color(text_color)
+            .font_size(font_size)
+            .graphics_for(target)
+            .set(text_idx, &mut ui);
+    }
+
+}
+
+
+impl<'a> Colorable for Tooltip<'a> {
+    builder_method!(color { style.color = Some(Color) });
+}
+
+impl<'a> Frameable for Tooltip<'a> {
+    builder_methods!{
+        frame { style.frame = Some(Scalar) }
+        frame_color { style.frame_color = Some(Color) }
+    }
+}
@@ -131,6 +131,10 @@ impl<'a, X, Y, F> Widget for XYPad<'a, X, Y, F>
         self.style.clone()
     }
 
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
     /// Update the XYPad's cached state.
     fn update<B: Backend>(self, args: widget::UpdateArgs<Self, B>) {
         use position::{Direction, Edge};